@@ -0,0 +1,192 @@
+//! Load benchmark for a running server.
+//!
+//! Creates a set of accounts, then keeps `CONCURRENCY` clients busy until
+//! `REQUESTS` requests have completed, alternating CREDIT events and balance
+//! reads, and reports throughput and latency percentiles.
+//!
+//! ```bash
+//! cargo run --release                     # in one terminal
+//! cargo run --release --example load_test # in another
+//! ```
+//!
+//! Settings: `BASE_URL` (default `http://localhost:3000`), `CONCURRENCY`
+//! (default 64), `REQUESTS` (default 10000) and `ACCOUNTS` (default 16). Set
+//! `API_KEY` when the server requires authentication; the key needs the
+//! `accounts:write`, `events:write` and `balances:read` scopes.
+
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+struct Settings {
+    base_url: String,
+    concurrency: usize,
+    requests: usize,
+    accounts: usize,
+}
+
+impl Settings {
+    fn from_env() -> Self {
+        Self {
+            base_url: std::env::var("BASE_URL")
+                .unwrap_or_else(|_| "http://localhost:3000".to_string()),
+            concurrency: env_or("CONCURRENCY", 64),
+            requests: env_or("REQUESTS", 10_000),
+            accounts: env_or("ACCOUNTS", 16),
+        }
+    }
+}
+
+fn env_or(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(default)
+}
+
+#[derive(Default)]
+struct WorkerStats {
+    latencies: Vec<Duration>,
+    errors: usize,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let settings = Settings::from_env();
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Ok(api_key) = std::env::var("API_KEY") {
+        headers.insert("x-api-key", api_key.parse()?);
+    }
+    let client = reqwest::Client::builder()
+        .pool_max_idle_per_host(settings.concurrency)
+        .default_headers(headers)
+        .build()?;
+
+    let account_ids = create_accounts(&client, &settings).await?;
+    println!(
+        "Running {} requests against {} with {} concurrent clients on {} accounts",
+        settings.requests,
+        settings.base_url,
+        settings.concurrency,
+        account_ids.len()
+    );
+
+    let account_ids = Arc::new(account_ids);
+    let next_request = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+
+    let workers: Vec<_> = (0..settings.concurrency)
+        .map(|_| {
+            let client = client.clone();
+            let base_url = settings.base_url.clone();
+            let account_ids = account_ids.clone();
+            let next_request = next_request.clone();
+            let requests = settings.requests;
+
+            tokio::spawn(async move {
+                let mut stats = WorkerStats::default();
+                loop {
+                    let n = next_request.fetch_add(1, Ordering::Relaxed);
+                    if n >= requests {
+                        return stats;
+                    }
+                    let account_id = account_ids[n % account_ids.len()];
+
+                    let request_started = Instant::now();
+                    let response = if n.is_multiple_of(2) {
+                        client
+                            .post(format!("{}/events", base_url))
+                            .json(&json!({
+                                "account_id": account_id,
+                                "event_type": "CREDIT",
+                                "amount": 100,
+                                "description": "load test"
+                            }))
+                            .send()
+                            .await
+                    } else {
+                        client
+                            .get(format!("{}/accounts/{}/balance", base_url, account_id))
+                            .send()
+                            .await
+                    };
+
+                    match response {
+                        Ok(response) if response.status().is_success() => {
+                            stats.latencies.push(request_started.elapsed());
+                        }
+                        _ => stats.errors += 1,
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut latencies = Vec::with_capacity(settings.requests);
+    let mut errors = 0;
+    for worker in workers {
+        let stats = worker.await?;
+        latencies.extend(stats.latencies);
+        errors += stats.errors;
+    }
+    let elapsed = started.elapsed();
+
+    report(&mut latencies, errors, elapsed);
+    Ok(())
+}
+
+async fn create_accounts(
+    client: &reqwest::Client,
+    settings: &Settings,
+) -> anyhow::Result<Vec<i64>> {
+    let run = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    let mut account_ids = Vec::with_capacity(settings.accounts);
+    for i in 0..settings.accounts {
+        let response: Value = client
+            .post(format!("{}/accounts", settings.base_url))
+            .json(&json!({
+                "account_number": format!("LOAD{}{:04}", run, i),
+                "account_name": format!("Load test account {}", i)
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let id = response["id"]
+            .as_i64()
+            .ok_or_else(|| anyhow::anyhow!("unexpected create account response: {}", response))?;
+        account_ids.push(id);
+    }
+    Ok(account_ids)
+}
+
+fn report(latencies: &mut [Duration], errors: usize, elapsed: Duration) {
+    latencies.sort();
+    let percentile = |p: f64| -> Duration {
+        if latencies.is_empty() {
+            return Duration::ZERO;
+        }
+        let index = ((latencies.len() as f64 * p).ceil() as usize).clamp(1, latencies.len());
+        latencies[index - 1]
+    };
+
+    let completed = latencies.len();
+    println!("Completed:  {} ok, {} failed", completed, errors);
+    println!("Duration:   {:.2?}", elapsed);
+    println!(
+        "Throughput: {:.0} requests/s",
+        completed as f64 / elapsed.as_secs_f64()
+    );
+    println!(
+        "Latency:    p50 {:.2?}  p90 {:.2?}  p99 {:.2?}  max {:.2?}",
+        percentile(0.50),
+        percentile(0.90),
+        percentile(0.99),
+        latencies.last().copied().unwrap_or_default()
+    );
+}
@@ -0,0 +1,6 @@
+-- Unlink ledger events from transfers
+DROP INDEX IF EXISTS idx_ledger_events_transfer_id;
+ALTER TABLE ledger_events DROP COLUMN transfer_id;
+
+-- Drop transfers table
+DROP TABLE transfers;
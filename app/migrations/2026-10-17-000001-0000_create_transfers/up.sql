@@ -0,0 +1,18 @@
+-- Create transfers table linking the paired DEBIT/CREDIT events of a transfer
+CREATE TABLE transfers (
+    id SERIAL PRIMARY KEY,
+    from_account_id INTEGER NOT NULL REFERENCES accounts(id),
+    to_account_id INTEGER NOT NULL REFERENCES accounts(id),
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    description TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (from_account_id <> to_account_id)
+);
+
+CREATE INDEX idx_transfers_from_account ON transfers(from_account_id);
+CREATE INDEX idx_transfers_to_account ON transfers(to_account_id);
+
+-- Both legs of a transfer reference the same transfer id
+ALTER TABLE ledger_events ADD COLUMN transfer_id INTEGER REFERENCES transfers(id);
+
+CREATE INDEX idx_ledger_events_transfer_id ON ledger_events(transfer_id);
@@ -0,0 +1,4 @@
+-- Remove balance policy columns from accounts table
+ALTER TABLE accounts DROP CONSTRAINT accounts_overdraft_limit_policy;
+ALTER TABLE accounts DROP COLUMN overdraft_limit;
+ALTER TABLE accounts DROP COLUMN balance_policy;
@@ -0,0 +1,11 @@
+-- Add per-account balance policy governing how far DEBIT events may take the balance.
+-- Existing accounts keep the previous behaviour (no balance checks); new accounts
+-- default to no overdraft.
+ALTER TABLE accounts ADD COLUMN balance_policy VARCHAR(20) NOT NULL DEFAULT 'UNRESTRICTED'
+    CHECK (balance_policy IN ('NO_OVERDRAFT', 'OVERDRAFT', 'UNRESTRICTED'));
+ALTER TABLE accounts ALTER COLUMN balance_policy SET DEFAULT 'NO_OVERDRAFT';
+
+-- Overdraft limit is only set for the OVERDRAFT policy
+ALTER TABLE accounts ADD COLUMN overdraft_limit BIGINT CHECK (overdraft_limit >= 0);
+ALTER TABLE accounts ADD CONSTRAINT accounts_overdraft_limit_policy
+    CHECK ((balance_policy = 'OVERDRAFT') = (overdraft_limit IS NOT NULL));
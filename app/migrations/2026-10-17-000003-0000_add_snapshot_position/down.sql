@@ -0,0 +1,4 @@
+-- Remove snapshot positions
+DROP INDEX IF EXISTS idx_ledger_events_account_id_id;
+DROP INDEX IF EXISTS idx_account_balances_account_position;
+ALTER TABLE account_balances DROP COLUMN last_event_id;
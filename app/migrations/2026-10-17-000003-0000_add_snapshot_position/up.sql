@@ -0,0 +1,9 @@
+-- Record the newest ledger event folded into each snapshot so balances can be
+-- computed as snapshot + events after that position. Snapshots taken before this
+-- migration have no known position (NULL) and are not used for incremental reads.
+ALTER TABLE account_balances ADD COLUMN last_event_id INTEGER CHECK (last_event_id >= 0);
+
+CREATE INDEX idx_account_balances_account_position ON account_balances(account_id, last_event_id);
+
+-- Range scans of an account's events after a snapshot position
+CREATE INDEX idx_ledger_events_account_id_id ON ledger_events(account_id, id);
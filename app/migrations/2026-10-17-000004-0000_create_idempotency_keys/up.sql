@@ -0,0 +1,14 @@
+-- Create idempotency_keys table storing the outcome of write requests sent with an
+-- Idempotency-Key header, so retries replay the original response
+CREATE TABLE idempotency_keys (
+    key VARCHAR(255) PRIMARY KEY,
+    request_fingerprint VARCHAR(64) NOT NULL,
+    response_status INTEGER,
+    response_body TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    CHECK ((response_status IS NULL) = (response_body IS NULL))
+);
+
+-- Create index for purging expired keys
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
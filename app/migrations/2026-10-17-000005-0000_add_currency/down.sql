@@ -0,0 +1,7 @@
+ALTER TABLE ledger_events DROP CONSTRAINT ledger_events_account_currency_fkey;
+ALTER TABLE accounts DROP CONSTRAINT accounts_id_currency_key;
+
+ALTER TABLE account_balances DROP COLUMN currency;
+ALTER TABLE transfers DROP COLUMN currency;
+ALTER TABLE ledger_events DROP COLUMN currency;
+ALTER TABLE accounts DROP COLUMN currency;
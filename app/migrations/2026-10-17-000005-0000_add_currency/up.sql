@@ -0,0 +1,18 @@
+-- Add an ISO 4217 currency to accounts and to every amount recorded against them.
+-- Existing rows predate currencies and are assumed to be USD.
+ALTER TABLE accounts ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'USD'
+    CHECK (currency ~ '^[A-Z]{3}$');
+
+ALTER TABLE ledger_events ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'USD';
+ALTER TABLE ledger_events ALTER COLUMN currency DROP DEFAULT;
+
+ALTER TABLE transfers ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'USD';
+ALTER TABLE transfers ALTER COLUMN currency DROP DEFAULT;
+
+ALTER TABLE account_balances ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'USD';
+ALTER TABLE account_balances ALTER COLUMN currency DROP DEFAULT;
+
+-- An event must be in the currency of its account
+ALTER TABLE accounts ADD CONSTRAINT accounts_id_currency_key UNIQUE (id, currency);
+ALTER TABLE ledger_events ADD CONSTRAINT ledger_events_account_currency_fkey
+    FOREIGN KEY (account_id, currency) REFERENCES accounts (id, currency);
@@ -0,0 +1,3 @@
+-- Add account lifecycle status. Only ACTIVE accounts accept ledger events.
+ALTER TABLE accounts ADD COLUMN status VARCHAR(20) NOT NULL DEFAULT 'ACTIVE'
+    CHECK (status IN ('ACTIVE', 'FROZEN', 'CLOSED'));
@@ -0,0 +1,2 @@
+-- Drop account_status_changes table
+DROP TABLE account_status_changes;
@@ -0,0 +1,14 @@
+-- Audit trail of account status transitions (freeze, unfreeze, close) with the
+-- actor who made each change and why
+CREATE TABLE account_status_changes (
+    id SERIAL PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts(id),
+    from_status VARCHAR(20) NOT NULL CHECK (from_status IN ('ACTIVE', 'FROZEN', 'CLOSED')),
+    to_status VARCHAR(20) NOT NULL CHECK (to_status IN ('ACTIVE', 'FROZEN', 'CLOSED')),
+    actor VARCHAR(255) NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (from_status <> to_status)
+);
+
+CREATE INDEX idx_account_status_changes_account_id ON account_status_changes(account_id, id);
@@ -0,0 +1,7 @@
+-- Unlink ledger events from journal entries
+DROP INDEX IF EXISTS idx_ledger_events_journal_entry_id;
+ALTER TABLE ledger_events DROP CONSTRAINT IF EXISTS ledger_events_single_parent;
+ALTER TABLE ledger_events DROP COLUMN journal_entry_id;
+
+-- Drop journal_entries table
+DROP TABLE journal_entries;
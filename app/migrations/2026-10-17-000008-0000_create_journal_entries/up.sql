@@ -0,0 +1,16 @@
+-- Create journal_entries table grouping the balanced DEBIT/CREDIT legs of a
+-- multi-account posting (fees, splits, settlements)
+CREATE TABLE journal_entries (
+    id SERIAL PRIMARY KEY,
+    description TEXT,
+    currency VARCHAR(3) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Every leg of an entry references it; an event is a leg of a transfer or of a
+-- journal entry, never both
+ALTER TABLE ledger_events ADD COLUMN journal_entry_id INTEGER REFERENCES journal_entries(id);
+ALTER TABLE ledger_events ADD CONSTRAINT ledger_events_single_parent
+    CHECK (transfer_id IS NULL OR journal_entry_id IS NULL);
+
+CREATE INDEX idx_ledger_events_journal_entry_id ON ledger_events(journal_entry_id);
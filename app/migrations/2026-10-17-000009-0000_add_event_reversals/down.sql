@@ -0,0 +1,3 @@
+-- Drop event reversal reference
+DROP INDEX IF EXISTS idx_ledger_events_reverses_event_id;
+ALTER TABLE ledger_events DROP COLUMN reverses_event_id;
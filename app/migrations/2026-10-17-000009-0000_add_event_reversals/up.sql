@@ -0,0 +1,5 @@
+-- A reversal is a compensating event referencing the event it cancels. The unique
+-- index guarantees an event is reversed at most once.
+ALTER TABLE ledger_events ADD COLUMN reverses_event_id INTEGER REFERENCES ledger_events(id);
+
+CREATE UNIQUE INDEX idx_ledger_events_reverses_event_id ON ledger_events(reverses_event_id);
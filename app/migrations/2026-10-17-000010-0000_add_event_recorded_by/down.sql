@@ -0,0 +1,2 @@
+-- Drop event audit identity
+ALTER TABLE ledger_events DROP COLUMN recorded_by;
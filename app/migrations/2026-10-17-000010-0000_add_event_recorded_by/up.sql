@@ -0,0 +1,3 @@
+-- Client that recorded each event, taken from the authenticated caller. NULL for
+-- events recorded while authentication was disabled.
+ALTER TABLE ledger_events ADD COLUMN recorded_by VARCHAR(255);
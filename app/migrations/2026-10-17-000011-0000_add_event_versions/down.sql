@@ -0,0 +1,6 @@
+-- Drop event versions and the global sequence
+DROP INDEX IF EXISTS idx_ledger_events_sequence;
+DROP INDEX IF EXISTS idx_ledger_events_account_version;
+ALTER TABLE ledger_events DROP COLUMN sequence;
+ALTER TABLE ledger_events DROP COLUMN version;
+DROP SEQUENCE IF EXISTS ledger_events_sequence_seq;
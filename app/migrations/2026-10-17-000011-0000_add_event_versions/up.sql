@@ -0,0 +1,28 @@
+-- Give events a total order: a per-account version and a global sequence.
+-- created_at is not unique, so existing events are numbered in id order, which
+-- is the order they were committed in per account since appends lock the account.
+ALTER TABLE ledger_events ADD COLUMN version BIGINT;
+ALTER TABLE ledger_events ADD COLUMN sequence BIGINT;
+
+UPDATE ledger_events
+SET version = numbered.version, sequence = numbered.sequence
+FROM (
+    SELECT id,
+           ROW_NUMBER() OVER (PARTITION BY account_id ORDER BY id) AS version,
+           ROW_NUMBER() OVER (ORDER BY id) AS sequence
+    FROM ledger_events
+) numbered
+WHERE ledger_events.id = numbered.id;
+
+CREATE SEQUENCE ledger_events_sequence_seq AS BIGINT OWNED BY ledger_events.sequence;
+SELECT setval('ledger_events_sequence_seq', COALESCE(MAX(sequence), 0) + 1, false) FROM ledger_events;
+
+ALTER TABLE ledger_events
+    ALTER COLUMN version SET NOT NULL,
+    ALTER COLUMN sequence SET NOT NULL,
+    ALTER COLUMN sequence SET DEFAULT nextval('ledger_events_sequence_seq'),
+    ADD CONSTRAINT ledger_events_version_positive CHECK (version > 0);
+
+-- Versions are assigned under the account lock; the index guards against gaps in that
+CREATE UNIQUE INDEX idx_ledger_events_account_version ON ledger_events(account_id, version);
+CREATE UNIQUE INDEX idx_ledger_events_sequence ON ledger_events(sequence);
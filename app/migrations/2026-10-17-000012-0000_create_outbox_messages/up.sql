@@ -0,0 +1,17 @@
+-- Create outbox_messages table: every ledger event gets a message written in the
+-- same transaction, which a relay then publishes to downstream consumers
+CREATE TABLE outbox_messages (
+    id SERIAL PRIMARY KEY,
+    topic VARCHAR(100) NOT NULL,
+    message_key VARCHAR(100) NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    published_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- The relay only ever looks for unpublished messages that are due
+CREATE INDEX idx_outbox_messages_pending ON outbox_messages(next_attempt_at, id)
+    WHERE published_at IS NULL;
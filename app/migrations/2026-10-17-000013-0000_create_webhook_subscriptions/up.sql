@@ -0,0 +1,32 @@
+-- Create webhook_subscriptions table: client endpoints notified of selected event types
+CREATE TABLE webhook_subscriptions (
+    id SERIAL PRIMARY KEY,
+    url TEXT NOT NULL,
+    event_types TEXT[] NOT NULL,
+    secret VARCHAR(100) NOT NULL,
+    account_id INTEGER REFERENCES accounts(id),
+    balance_threshold BIGINT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create webhook_deliveries table: one row per subscription and outbox message,
+-- written in the message's transaction and sent by the webhook dispatcher
+CREATE TABLE webhook_deliveries (
+    id SERIAL PRIMARY KEY,
+    subscription_id INTEGER NOT NULL REFERENCES webhook_subscriptions(id) ON DELETE CASCADE,
+    outbox_message_id INTEGER NOT NULL REFERENCES outbox_messages(id),
+    event_type VARCHAR(100) NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    delivered_at TIMESTAMP,
+    dead_lettered_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- The dispatcher only ever looks for deliveries that are still due
+CREATE INDEX idx_webhook_deliveries_pending ON webhook_deliveries(next_attempt_at, id)
+    WHERE delivered_at IS NULL AND dead_lettered_at IS NULL;
+
+CREATE INDEX idx_webhook_deliveries_subscription_id ON webhook_deliveries(subscription_id, id);
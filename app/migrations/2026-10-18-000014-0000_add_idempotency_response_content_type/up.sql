@@ -0,0 +1,3 @@
+-- Keep the Content-Type of stored responses, so replays are served with the original one.
+-- Rows stored before this column existed have none.
+ALTER TABLE idempotency_keys ADD COLUMN response_content_type VARCHAR(255);
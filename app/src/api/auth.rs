@@ -0,0 +1,467 @@
+use crate::api::{ApiError, AppState};
+use crate::config::{AuthConfig, ConfigError, JwtAlgorithm};
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, MatchedPath, Request, State},
+    http::{header, request::Parts, HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::str::FromStr;
+use tracing::warn;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Permission to call a group of endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    AccountsRead,
+    AccountsWrite,
+    BalancesRead,
+    BalancesWrite,
+    EventsRead,
+    EventsWrite,
+    WebhooksRead,
+    WebhooksWrite,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 8] = [
+        Scope::AccountsRead,
+        Scope::AccountsWrite,
+        Scope::BalancesRead,
+        Scope::BalancesWrite,
+        Scope::EventsRead,
+        Scope::EventsWrite,
+        Scope::WebhooksRead,
+        Scope::WebhooksWrite,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::AccountsRead => "accounts:read",
+            Scope::AccountsWrite => "accounts:write",
+            Scope::BalancesRead => "balances:read",
+            Scope::BalancesWrite => "balances:write",
+            Scope::EventsRead => "events:read",
+            Scope::EventsWrite => "events:write",
+            Scope::WebhooksRead => "webhooks:read",
+            Scope::WebhooksWrite => "webhooks:write",
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Scope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| format!("unknown scope {:?}", s))
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// What a request needs before it reaches its handler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Public,
+    /// Any authenticated caller
+    Authenticated,
+    Scope(Scope),
+}
+
+/// Access rule for a route, keyed by the route template it matched.
+///
+/// Transfers, journal entries and reversals append ledger events, so they need
+/// `events:write` like `POST /events`.
+fn required_access(method: &Method, path: &str) -> Access {
+    let write = method != Method::GET && method != Method::HEAD;
+    match path {
+        "/health/live" | "/health/ready" | "/metrics" => Access::Public,
+        "/accounts/:id/balance" | "/accounts/:id/snapshots" | "/accounts/:id/snapshots/verify" => {
+            Access::Scope(Scope::BalancesRead)
+        }
+        "/balances/snapshot" | "/balances/snapshots/prune" => Access::Scope(Scope::BalancesWrite),
+        "/events" | "/events/:id/reverse" | "/transfers" | "/journal-entries" if write => {
+            Access::Scope(Scope::EventsWrite)
+        }
+        "/events" | "/accounts/:id/statement" | "/outbox" => Access::Scope(Scope::EventsRead),
+        _ if path.starts_with("/accounts") && write => Access::Scope(Scope::AccountsWrite),
+        _ if path.starts_with("/accounts") => Access::Scope(Scope::AccountsRead),
+        _ if path.starts_with("/webhooks") && write => Access::Scope(Scope::WebhooksWrite),
+        _ if path.starts_with("/webhooks") => Access::Scope(Scope::WebhooksRead),
+        _ => Access::Authenticated,
+    }
+}
+
+/// Authenticated client, attached to the request for handlers to read
+#[derive(Debug, Clone)]
+pub struct Caller {
+    pub client_id: String,
+    pub scopes: HashSet<Scope>,
+}
+
+impl Caller {
+    fn new<'a>(client_id: String, scopes: impl IntoIterator<Item = &'a str>) -> Self {
+        // Unknown scopes are ignored so tokens can carry scopes meant for other services
+        let scopes = scopes
+            .into_iter()
+            .filter_map(|scope| scope.parse().ok())
+            .collect();
+        Self { client_id, scopes }
+    }
+
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// Client id of the authenticated caller, None when authentication is disabled
+pub struct CallerId(pub Option<String>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CallerId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(CallerId(
+            parts
+                .extensions
+                .get::<Caller>()
+                .map(|caller| caller.client_id.clone()),
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    #[serde(default)]
+    scope: String,
+}
+
+struct JwtVerifier {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+/// Checks API keys and JWTs against the configured credentials
+pub struct Authenticator {
+    /// Callers by the hex SHA-256 of their API key
+    api_keys: HashMap<String, Caller>,
+    jwt: Option<JwtVerifier>,
+}
+
+impl Authenticator {
+    pub fn from_config(config: &AuthConfig) -> Result<Self, ConfigError> {
+        let api_keys = config
+            .api_keys
+            .iter()
+            .map(|key| {
+                let caller =
+                    Caller::new(key.client_id.clone(), key.scopes.iter().map(String::as_str));
+                (key.key_sha256.to_lowercase(), caller)
+            })
+            .collect();
+
+        let jwt = match &config.jwt {
+            Some(jwt) => {
+                let (algorithm, key) = match jwt.algorithm {
+                    JwtAlgorithm::HS256 => {
+                        let secret = jwt.secret.as_deref().unwrap_or_default();
+                        (
+                            Algorithm::HS256,
+                            DecodingKey::from_secret(secret.as_bytes()),
+                        )
+                    }
+                    JwtAlgorithm::RS256 => {
+                        let path = jwt.public_key_file.clone().unwrap_or_default();
+                        let pem = std::fs::read(&path).map_err(|source| ConfigError::Read {
+                            path: path.clone(),
+                            source,
+                        })?;
+                        let key = DecodingKey::from_rsa_pem(&pem).map_err(|e| {
+                            ConfigError::Invalid(format!(
+                                "auth.jwt.public_key_file {}: {}",
+                                path.display(),
+                                e
+                            ))
+                        })?;
+                        (Algorithm::RS256, key)
+                    }
+                };
+
+                let mut validation = Validation::new(algorithm);
+                validation.set_required_spec_claims(&["exp", "sub"]);
+                if let Some(issuer) = &jwt.issuer {
+                    validation.set_issuer(&[issuer]);
+                }
+                match &jwt.audience {
+                    Some(audience) => validation.set_audience(&[audience]),
+                    None => validation.validate_aud = false,
+                }
+                Some(JwtVerifier { key, validation })
+            }
+            None => None,
+        };
+
+        Ok(Self { api_keys, jwt })
+    }
+
+    /// Identifies the caller from the `X-API-Key` or `Authorization: Bearer` header
+    pub fn authenticate(&self, headers: &HeaderMap) -> Result<Caller, ApiError> {
+        if let Some(key) = headers.get(API_KEY_HEADER) {
+            let hash = hex_sha256(key.as_bytes());
+            return self
+                .api_keys
+                .get(&hash)
+                .cloned()
+                .ok_or_else(|| unauthorized("Invalid API key"));
+        }
+
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim);
+        match (token, &self.jwt) {
+            (Some(token), Some(jwt)) => {
+                let data = jsonwebtoken::decode::<Claims>(token, &jwt.key, &jwt.validation)
+                    .map_err(|e| unauthorized(format!("Invalid bearer token: {}", e)))?;
+                let claims = data.claims;
+                Ok(Caller::new(claims.sub, claims.scope.split_whitespace()))
+            }
+            (Some(_), None) => Err(unauthorized("Bearer tokens are not accepted")),
+            (None, _) => Err(unauthorized(
+                "Missing credentials: send an X-API-Key or Authorization: Bearer header",
+            )),
+        }
+    }
+}
+
+/// Rejects requests lacking the credentials or scope their route requires, and
+/// attaches the authenticated [`Caller`] to the others
+pub async fn authorize(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(authenticator) = state.authenticator.as_ref() else {
+        return next.run(request).await;
+    };
+
+    // Unmatched paths still need credentials so they cannot be used to probe routes
+    let access = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| required_access(request.method(), path.as_str()))
+        .unwrap_or(Access::Authenticated);
+    if access == Access::Public {
+        return next.run(request).await;
+    }
+
+    let caller = match authenticator.authenticate(request.headers()) {
+        Ok(caller) => caller,
+        Err(e) => {
+            warn!(
+                "Rejected unauthenticated request to {}",
+                request.uri().path()
+            );
+            return e.into_response();
+        }
+    };
+    if let Access::Scope(scope) = access {
+        if !caller.has_scope(scope) {
+            warn!(
+                "Rejected request from {} to {}: missing scope {}",
+                caller.client_id,
+                request.uri().path(),
+                scope
+            );
+            return ApiError::new(
+                StatusCode::FORBIDDEN,
+                "FORBIDDEN",
+                format!("This request requires the {} scope", scope),
+            )
+            .with_extension("required_scope", scope.as_str())
+            .into_response();
+        }
+    }
+
+    request.extensions_mut().insert(caller);
+    next.run(request).await
+}
+
+fn unauthorized(detail: impl Into<String>) -> ApiError {
+    ApiError::new(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", detail)
+}
+
+fn hex_sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ApiKeyConfig, JwtConfig};
+    use axum::http::HeaderValue;
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::json;
+
+    const SECRET: &str = "test-secret";
+
+    fn authenticator() -> Authenticator {
+        Authenticator::from_config(&AuthConfig {
+            enabled: true,
+            api_keys: vec![ApiKeyConfig {
+                client_id: "billing".to_string(),
+                key_sha256: hex_sha256(b"billing-key"),
+                scopes: vec!["events:write".to_string()],
+            }],
+            jwt: Some(JwtConfig {
+                secret: Some(SECRET.to_string()),
+                ..JwtConfig::default()
+            }),
+        })
+        .unwrap()
+    }
+
+    fn headers(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn token(claims: serde_json::Value) -> String {
+        jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_api_key() {
+        let authenticator = authenticator();
+
+        let caller = authenticator
+            .authenticate(&headers(
+                header::HeaderName::from_static(API_KEY_HEADER),
+                "billing-key",
+            ))
+            .unwrap();
+        assert_eq!(caller.client_id, "billing");
+        assert!(caller.has_scope(Scope::EventsWrite));
+        assert!(!caller.has_scope(Scope::AccountsWrite));
+
+        let err = authenticator
+            .authenticate(&headers(
+                header::HeaderName::from_static(API_KEY_HEADER),
+                "wrong-key",
+            ))
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::UNAUTHORIZED);
+        assert!(authenticator.authenticate(&HeaderMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let authenticator = authenticator();
+        let exp = chrono::Utc::now().timestamp() + 60;
+
+        let valid =
+            token(json!({"sub": "reporting", "scope": "accounts:read other:scope", "exp": exp}));
+        let caller = authenticator
+            .authenticate(&headers(
+                header::AUTHORIZATION,
+                &format!("Bearer {}", valid),
+            ))
+            .unwrap();
+        assert_eq!(caller.client_id, "reporting");
+        assert_eq!(caller.scopes, HashSet::from([Scope::AccountsRead]));
+
+        let expired = token(json!({"sub": "reporting", "exp": exp - 3600}));
+        assert!(authenticator
+            .authenticate(&headers(
+                header::AUTHORIZATION,
+                &format!("Bearer {}", expired)
+            ))
+            .is_err());
+    }
+
+    #[test]
+    fn test_required_access() {
+        assert_eq!(
+            required_access(&Method::GET, "/health/ready"),
+            Access::Public
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/accounts"),
+            Access::Scope(Scope::AccountsWrite)
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/accounts/:id/freeze"),
+            Access::Scope(Scope::AccountsWrite)
+        );
+        assert_eq!(
+            required_access(&Method::GET, "/accounts/:id/balance"),
+            Access::Scope(Scope::BalancesRead)
+        );
+        assert_eq!(
+            required_access(&Method::GET, "/accounts/:id/snapshots/verify"),
+            Access::Scope(Scope::BalancesRead)
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/balances/snapshots/prune"),
+            Access::Scope(Scope::BalancesWrite)
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/transfers"),
+            Access::Scope(Scope::EventsWrite)
+        );
+        assert_eq!(
+            required_access(&Method::GET, "/events"),
+            Access::Scope(Scope::EventsRead)
+        );
+        assert_eq!(
+            required_access(&Method::GET, "/accounts/:id/statement"),
+            Access::Scope(Scope::EventsRead)
+        );
+        assert_eq!(
+            required_access(&Method::GET, "/outbox"),
+            Access::Scope(Scope::EventsRead)
+        );
+        assert_eq!(
+            required_access(&Method::DELETE, "/webhooks/:id"),
+            Access::Scope(Scope::WebhooksWrite)
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/webhooks/deliveries/:id/replay"),
+            Access::Scope(Scope::WebhooksWrite)
+        );
+        assert_eq!(
+            required_access(&Method::GET, "/webhooks/dead-letters"),
+            Access::Scope(Scope::WebhooksRead)
+        );
+        assert_eq!(
+            required_access(&Method::GET, "/unknown"),
+            Access::Authenticated
+        );
+    }
+}
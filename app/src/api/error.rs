@@ -0,0 +1,299 @@
+use crate::domain::DomainError;
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    extract::{FromRequest, FromRequestParts},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde_json::{json, Map, Value};
+use tracing::error;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// API error rendered as an RFC 7807 `application/problem+json` document.
+///
+/// Besides the standard `type`, `title`, `status` and `detail` members, every
+/// problem carries a stable `code` clients can branch on, plus error specific
+/// members such as `required` and `available` for `INSUFFICIENT_BALANCE`.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    detail: String,
+    extensions: Map<String, Value>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            detail: detail.into(),
+            extensions: Map::new(),
+        }
+    }
+
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "BAD_REQUEST", detail)
+    }
+
+    pub fn internal() -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            "An internal error occurred",
+        )
+    }
+
+    pub fn with_extension(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.extensions.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    fn body(&self) -> Value {
+        let mut body = json!({
+            "type": format!("/problems/{}", self.code.to_lowercase().replace('_', "-")),
+            "title": self.status.canonical_reason().unwrap_or("Error"),
+            "status": self.status.as_u16(),
+            "detail": self.detail,
+            "code": self.code,
+        });
+        if let Value::Object(members) = &mut body {
+            members.extend(self.extensions.clone());
+        }
+        body
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            [(header::CONTENT_TYPE, PROBLEM_JSON)],
+            self.body().to_string(),
+        )
+            .into_response()
+    }
+}
+
+impl From<DomainError> for ApiError {
+    fn from(err: DomainError) -> Self {
+        let code = err.code();
+        let detail = err.to_string();
+
+        match err {
+            DomainError::AccountNotFound(_)
+            | DomainError::EventNotFound(_)
+            | DomainError::WebhookSubscriptionNotFound(_)
+            | DomainError::WebhookDeliveryNotFound(_) => {
+                Self::new(StatusCode::NOT_FOUND, code, detail)
+            }
+            DomainError::AccountFrozen(account_id) | DomainError::AccountClosed(account_id) => {
+                Self::new(StatusCode::CONFLICT, code, detail)
+                    .with_extension("account_id", account_id)
+            }
+            DomainError::InvalidStatusTransition {
+                account_id,
+                from,
+                to,
+            } => Self::new(StatusCode::CONFLICT, code, detail)
+                .with_extension("account_id", account_id)
+                .with_extension("from", from)
+                .with_extension("to", to),
+            DomainError::BalanceNotZero {
+                account_id,
+                balance,
+            } => Self::new(StatusCode::CONFLICT, code, detail)
+                .with_extension("account_id", account_id)
+                .with_extension("balance", balance),
+            DomainError::EventAlreadyReversed {
+                event_id,
+                reversal_event_id,
+            } => Self::new(StatusCode::CONFLICT, code, detail)
+                .with_extension("event_id", event_id)
+                .with_extension("reversal_event_id", reversal_event_id),
+            DomainError::VersionConflict {
+                account_id,
+                expected,
+                actual,
+            } => Self::new(StatusCode::CONFLICT, code, detail)
+                .with_extension("account_id", account_id)
+                .with_extension("expected_version", expected)
+                .with_extension("current_version", actual),
+            DomainError::DeliveryNotReplayable {
+                delivery_id,
+                status,
+            } => Self::new(StatusCode::CONFLICT, code, detail)
+                .with_extension("delivery_id", delivery_id)
+                .with_extension("status", status),
+            DomainError::DuplicateAccountNumber(account_number) => {
+                Self::new(StatusCode::CONFLICT, code, detail)
+                    .with_extension("account_number", account_number)
+            }
+            DomainError::InsufficientBalance {
+                required,
+                available,
+            } => Self::new(StatusCode::UNPROCESSABLE_ENTITY, code, detail)
+                .with_extension("required", required)
+                .with_extension("available", available),
+            DomainError::UnbalancedJournalEntry { debits, credits } => {
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, code, detail)
+                    .with_extension("debits", debits)
+                    .with_extension("credits", credits)
+            }
+            DomainError::CurrencyMismatch { expected, actual } => {
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, code, detail)
+                    .with_extension("expected", expected)
+                    .with_extension("actual", actual)
+            }
+            DomainError::InvalidAmount(amount) => {
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, code, detail)
+                    .with_extension("amount", amount)
+            }
+            DomainError::InvalidAccountNumber(_) => {
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, code, detail)
+            }
+            DomainError::ValidationError(_) => Self::new(StatusCode::BAD_REQUEST, code, detail),
+            DomainError::RepositoryError(_) => {
+                // Database details stay in the logs
+                error!("Repository error: {}", detail);
+                Self::internal()
+            }
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(
+            rejection.status(),
+            "INVALID_REQUEST_BODY",
+            rejection.body_text(),
+        )
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::bad_request(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::bad_request(rejection.body_text())
+    }
+}
+
+/// `Json` extractor whose rejections are reported as problem documents
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct ApiJson<T>(pub T);
+
+/// `Path` extractor whose rejections are reported as problem documents
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiError))]
+pub struct ApiPath<T>(pub T);
+
+/// `Query` extractor whose rejections are reported as problem documents
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_found() {
+        let err = ApiError::from(DomainError::AccountNotFound(
+            "Account with id 9 not found".to_string(),
+        ));
+
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        assert_eq!(err.code(), "ACCOUNT_NOT_FOUND");
+    }
+
+    #[test]
+    fn test_duplicate_account_number() {
+        let err = ApiError::from(DomainError::DuplicateAccountNumber("ACC001".to_string()));
+
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(err.body()["account_number"], "ACC001");
+    }
+
+    #[test]
+    fn test_insufficient_balance_details() {
+        let err = ApiError::from(DomainError::InsufficientBalance {
+            required: 1500,
+            available: 1000,
+        });
+        let body = err.body();
+
+        assert_eq!(err.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["type"], "/problems/insufficient-balance");
+        assert_eq!(body["status"], 422);
+        assert_eq!(body["code"], "INSUFFICIENT_BALANCE");
+        assert_eq!(body["required"], 1500);
+        assert_eq!(body["available"], 1000);
+    }
+
+    #[test]
+    fn test_account_frozen() {
+        let err = ApiError::from(DomainError::AccountFrozen(7));
+
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(err.body()["code"], "ACCOUNT_FROZEN");
+        assert_eq!(err.body()["account_id"], 7);
+    }
+
+    #[test]
+    fn test_version_conflict() {
+        let err = ApiError::from(DomainError::VersionConflict {
+            account_id: 3,
+            expected: 4,
+            actual: 6,
+        });
+        let body = err.body();
+
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(body["code"], "VERSION_CONFLICT");
+        assert_eq!(body["expected_version"], 4);
+        assert_eq!(body["current_version"], 6);
+    }
+
+    #[test]
+    fn test_validation_error() {
+        let err = ApiError::from(DomainError::ValidationError("bad".to_string()));
+
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(err.code(), "VALIDATION_ERROR");
+    }
+
+    #[test]
+    fn test_repository_error_hides_details() {
+        let err = ApiError::from(DomainError::RepositoryError(
+            "connection refused".to_string(),
+        ));
+        let body = err.body();
+
+        assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["code"], "INTERNAL_ERROR");
+        assert!(!body["detail"].as_str().unwrap().contains("connection"));
+    }
+
+    #[test]
+    fn test_content_type() {
+        let response = ApiError::bad_request("bad").into_response();
+
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::api::AppState;
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde_json::{json, Map, Value};
+
+/// Liveness probe: the process is up and serving requests
+pub async fn live() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// Readiness probe: the storage backend is reachable and its schema is up to date.
+/// Answers 503 while any check fails so the orchestrator holds back traffic.
+pub async fn ready(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let checks = state.storage_health.check().await;
+    let ready = checks.iter().all(|check| check.is_ok());
+
+    let checks: Map<String, Value> = checks
+        .into_iter()
+        .map(|check| {
+            let result = match check.error {
+                None => json!({ "status": "ok" }),
+                Some(error) => json!({ "status": "failed", "error": error }),
+            };
+            (check.name.to_string(), result)
+        })
+        .collect();
+
+    let (status, label) = if ready {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+    };
+
+    (status, Json(json!({ "status": label, "checks": checks })))
+}
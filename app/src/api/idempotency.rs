@@ -0,0 +1,442 @@
+use crate::api::auth::Caller;
+use crate::api::error::PROBLEM_JSON;
+use crate::api::{ApiError, AppState};
+use crate::domain::{DomainResult, IdempotencyRepository};
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Keys are kept for 24 hours unless configured otherwise
+pub const DEFAULT_IDEMPOTENCY_TTL_SECONDS: i64 = 24 * 60 * 60;
+
+const MAX_KEY_LENGTH: usize = 255;
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+/// Attempts at storing a response, waiting a little longer after each failure
+const COMPLETE_ATTEMPTS: u32 = 3;
+const COMPLETE_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Middleware making POST requests carrying an `Idempotency-Key` header safe to retry.
+///
+/// The first request with a key is processed and its response stored alongside a
+/// fingerprint of the request. Retries with the same key and request get the stored
+/// response back, a different request with the same key is rejected with 422, and a
+/// retry arriving while the original is still running is rejected with 409.
+/// Server errors are not stored, so the request can be retried with the same key,
+/// and neither are panics, which release the key.
+pub async fn idempotency(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+
+    let key = match request.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        None => return next.run(request).await,
+        Some(value) => match parse_key(value) {
+            Some(key) => key,
+            None => {
+                return ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    "IDEMPOTENCY_KEY_INVALID",
+                    format!(
+                        "Idempotency-Key must be 1 to {} visible ASCII characters",
+                        MAX_KEY_LENGTH
+                    ),
+                )
+                .into_response()
+            }
+        },
+    };
+
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            return ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "PAYLOAD_TOO_LARGE",
+                e.to_string(),
+            )
+            .into_response()
+        }
+    };
+    // Keys are shared by all callers, so another client reusing one is told so
+    // instead of being handed the original caller's response
+    let caller = parts
+        .extensions
+        .get::<Caller>()
+        .map(|caller| caller.client_id.as_str());
+    let request_fingerprint =
+        fingerprint(&parts.method, parts.uri.to_string().as_str(), caller, &body);
+
+    let repository = &state.idempotency_repository;
+    match repository
+        .reserve(&key, &request_fingerprint, state.idempotency_ttl)
+        .await
+    {
+        Ok(None) => {}
+        Ok(Some(record)) if !record.matches(&request_fingerprint) => {
+            warn!("Idempotency-Key reused with a different request: {}", key);
+            return ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "IDEMPOTENCY_KEY_REUSED",
+                format!(
+                    "Idempotency-Key {} was already used with a different request",
+                    key
+                ),
+            )
+            .into_response();
+        }
+        Ok(Some(record)) => {
+            return match (record.response_status, record.response_body) {
+                (Some(status), Some(body)) => {
+                    info!("Replaying stored response for Idempotency-Key {}", key);
+                    replay_response(status, record.response_content_type.as_deref(), body)
+                }
+                _ => ApiError::new(
+                    StatusCode::CONFLICT,
+                    "IDEMPOTENCY_KEY_IN_PROGRESS",
+                    format!(
+                        "A request with Idempotency-Key {} is still being processed",
+                        key
+                    ),
+                )
+                .into_response(),
+            };
+        }
+        Err(e) => {
+            error!("Failed to reserve Idempotency-Key {}: {}", key, e);
+            return ApiError::internal().into_response();
+        }
+    }
+
+    // The handler runs in its own task, so a client that disconnects cannot cancel
+    // it between its write and the response being recorded
+    let reservation = Reservation::new(repository.clone(), key.clone());
+    let request = Request::from_parts(parts, Body::from(body));
+    match tokio::spawn(run_and_record(reservation, next, request)).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("Request with Idempotency-Key {} failed: {}", key, e);
+            ApiError::internal().into_response()
+        }
+    }
+}
+
+async fn run_and_record(reservation: Reservation, next: Next, request: Request) -> Response {
+    let response = next.run(request).await;
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            error!(
+                "Failed to buffer response for Idempotency-Key {}: {}",
+                reservation.key, e
+            );
+            reservation.release().await;
+            return ApiError::internal().into_response();
+        }
+    };
+
+    if parts.status.is_server_error() {
+        reservation.release().await;
+    } else {
+        let key = reservation.key.clone();
+        let content_type = parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        if let Err(e) = reservation
+            .complete(
+                parts.status.as_u16(),
+                content_type,
+                &String::from_utf8_lossy(&body),
+            )
+            .await
+        {
+            // The write went through but retries of it cannot be answered; tell the
+            // client rather than report a success it cannot replay
+            error!(
+                "Failed to store response for Idempotency-Key {}: {}",
+                key, e
+            );
+            return ApiError::internal().into_response();
+        }
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Key reserved for the request being processed. Dropping it before a response
+/// was recorded or the key released, as when the handler panics, releases the key
+/// so that the request can be retried instead of being answered with 409 until
+/// the key expires.
+struct Reservation {
+    repository: Arc<dyn IdempotencyRepository>,
+    key: String,
+    settled: bool,
+}
+
+impl Reservation {
+    fn new(repository: Arc<dyn IdempotencyRepository>, key: String) -> Self {
+        Self {
+            repository,
+            key,
+            settled: false,
+        }
+    }
+
+    /// Stores the response, retrying failed writes a few times since a key left
+    /// without a response blocks every retry until it expires
+    async fn complete(
+        mut self,
+        response_status: u16,
+        response_content_type: Option<String>,
+        response_body: &str,
+    ) -> DomainResult<()> {
+        self.settled = true;
+        let mut attempt = 1;
+        loop {
+            match self
+                .repository
+                .complete(
+                    &self.key,
+                    response_status,
+                    response_content_type.clone(),
+                    response_body,
+                )
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < COMPLETE_ATTEMPTS => {
+                    warn!(
+                        "Failed to store response for Idempotency-Key {} (attempt {}): {}",
+                        self.key, attempt, e
+                    );
+                    tokio::time::sleep(COMPLETE_RETRY_DELAY * attempt).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn release(mut self) {
+        self.settled = true;
+        release(self.repository.as_ref(), &self.key).await;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if self.settled {
+            return;
+        }
+        let repository = self.repository.clone();
+        let key = std::mem::take(&mut self.key);
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move { release(repository.as_ref(), &key).await });
+        }
+    }
+}
+
+/// Periodically deletes expired idempotency keys
+pub fn spawn_expired_key_purge(
+    repository: Arc<dyn IdempotencyRepository>,
+    period: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match repository.delete_expired().await {
+                Ok(0) => {}
+                Ok(count) => info!("Purged {} expired idempotency keys", count),
+                Err(e) => error!("Failed to purge expired idempotency keys: {}", e),
+            }
+        }
+    })
+}
+
+fn parse_key(value: &HeaderValue) -> Option<String> {
+    let key = value.to_str().ok()?.trim();
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return None;
+    }
+    Some(key.to_string())
+}
+
+/// Hex-encoded SHA-256 of the request method, URI, authenticated caller and body
+fn fingerprint(method: &Method, uri: &str, caller: Option<&str>, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(uri.as_bytes());
+    hasher.update(b"\n");
+    if let Some(caller) = caller {
+        hasher.update(caller.as_bytes());
+        hasher.update(b"\n");
+    }
+    hasher.update(body);
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+async fn release(repository: &dyn IdempotencyRepository, key: &str) {
+    if let Err(e) = repository.release(key).await {
+        error!("Failed to release Idempotency-Key {}: {}", key, e);
+    }
+}
+
+fn replay_response(status: u16, content_type: Option<&str>, body: String) -> Response {
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::OK);
+    // Responses stored without their Content-Type are JSON, with 4xx ones being
+    // problem documents
+    let content_type = content_type.unwrap_or(if status.is_client_error() {
+        PROBLEM_JSON
+    } else {
+        "application/json"
+    });
+    (
+        status,
+        [
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::HeaderName::from_static(IDEMPOTENT_REPLAYED_HEADER),
+                "true",
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::idempotency_repository::MockIdempotencyRepository;
+    use crate::domain::DomainError;
+    use crate::infrastructure::memory::{InMemoryIdempotencyRepository, InMemoryStore};
+
+    #[test]
+    fn test_replay_response_keeps_stored_content_type() {
+        let response = replay_response(201, Some("text/csv"), "a,b".to_string());
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+        assert_eq!(response.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+
+        let response = replay_response(422, None, "{}".to_string());
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+    }
+
+    #[tokio::test]
+    async fn test_reservation_released_when_dropped_unsettled() {
+        let repository: Arc<dyn IdempotencyRepository> =
+            Arc::new(InMemoryIdempotencyRepository::new(InMemoryStore::new()));
+        let ttl = chrono::Duration::hours(1);
+        repository.reserve("retry-1", "request", ttl).await.unwrap();
+
+        let reservation = Reservation::new(repository.clone(), "retry-1".to_string());
+        let handler = tokio::spawn(async move {
+            let _reservation = reservation;
+            panic!("handler panicked");
+        });
+        assert!(handler.await.unwrap_err().is_panic());
+
+        // The release runs in a task of its own
+        for _ in 0..100 {
+            if repository
+                .reserve("retry-1", "request", ttl)
+                .await
+                .unwrap()
+                .is_none()
+            {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("key was not released");
+    }
+
+    #[tokio::test]
+    async fn test_complete_retries_failed_writes() {
+        let mut mock_repo = MockIdempotencyRepository::new();
+        let mut calls = 0;
+        mock_repo
+            .expect_complete()
+            .times(2)
+            .returning(move |_, _, _, _| {
+                calls += 1;
+                if calls == 1 {
+                    Err(DomainError::RepositoryError("connection reset".to_string()))
+                } else {
+                    Ok(())
+                }
+            });
+        // Neither released on drop after completing
+        mock_repo.expect_release().never();
+
+        let reservation = Reservation::new(Arc::new(mock_repo), "retry-1".to_string());
+        reservation
+            .complete(201, Some("application/json".to_string()), "{}")
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let first = fingerprint(&Method::POST, "/events", None, br#"{"amount":100}"#);
+
+        assert_eq!(first.len(), 64);
+        assert_eq!(
+            first,
+            fingerprint(&Method::POST, "/events", None, br#"{"amount":100}"#)
+        );
+        assert_ne!(
+            first,
+            fingerprint(&Method::POST, "/events", None, br#"{"amount":200}"#)
+        );
+        assert_ne!(
+            first,
+            fingerprint(&Method::POST, "/accounts", None, br#"{"amount":100}"#)
+        );
+        assert_ne!(
+            first,
+            fingerprint(
+                &Method::POST,
+                "/events",
+                Some("billing"),
+                br#"{"amount":100}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(
+            parse_key(&HeaderValue::from_static("retry-1")),
+            Some("retry-1".to_string())
+        );
+        assert_eq!(parse_key(&HeaderValue::from_static("  ")), None);
+        assert_eq!(
+            parse_key(&HeaderValue::from_str(&"k".repeat(256)).unwrap()),
+            None
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::api::AppState;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use metrics::{counter, histogram};
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+pub const REQUEST_DURATION_METRIC: &str = "http_request_duration_seconds";
+
+/// Request latency histogram buckets, in seconds
+const REQUEST_DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Installs the global Prometheus recorder. Can only be called once per process.
+pub fn install_recorder() -> Result<PrometheusHandle, BuildError> {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(REQUEST_DURATION_METRIC.to_string()),
+            REQUEST_DURATION_BUCKETS,
+        )?
+        .install_recorder()
+}
+
+/// Periodically drains histogram samples into the recorder's buckets, which the
+/// recorder only does on its own when it is scraped
+pub fn spawn_upkeep(handle: PrometheusHandle, period: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            handle.run_upkeep();
+        }
+    })
+}
+
+/// Counts requests and records their latency per route template, so `/accounts/1`
+/// and `/accounts/2` are reported together as `/accounts/:id`
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let elapsed = started.elapsed().as_secs_f64();
+
+    let status = response.status().as_u16().to_string();
+    counter!("http_requests_total", "method" => method.clone(), "path" => path.clone(), "status" => status)
+        .increment(1);
+    histogram!(REQUEST_DURATION_METRIC, "method" => method, "path" => path).record(elapsed);
+
+    response
+}
+
+/// Prometheus scrape endpoint
+pub async fn metrics(State(state): State<AppState>) -> Response {
+    let Some(handle) = &state.metrics else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    state.storage_health.record_pool_metrics();
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        handle.render(),
+    )
+        .into_response()
+}
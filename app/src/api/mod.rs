@@ -1,7 +1,15 @@
+pub mod auth;
+pub mod error;
+pub mod health;
+pub mod idempotency;
+pub mod metrics;
 pub mod routes;
 pub mod server;
+pub mod shutdown;
 pub mod state;
 
+pub use error::ApiError;
 pub use routes::create_router;
-pub use server::start_server;
+pub use server::{serve, start_server};
+pub use shutdown::shutdown_signal;
 pub use state::AppState;
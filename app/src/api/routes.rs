@@ -1,239 +1,1536 @@
+use crate::api::auth::{authorize, CallerId};
+use crate::api::error::{ApiError, ApiJson, ApiPath, ApiQuery};
+use crate::api::idempotency::idempotency;
 use crate::api::AppState;
+use crate::api::{health, metrics};
 use crate::application::commands::{
-    CreateAccountCommand, CreateBalanceSnapshotCommand, CreateLedgerEventCommand,
+    ChangeAccountStatusCommand, CreateAccountCommand, CreateBalanceSnapshotCommand,
+    CreateJournalEntryCommand, CreateLedgerEventCommand, CreateTransferCommand,
+    CreateWebhookSubscriptionCommand, DeleteWebhookSubscriptionCommand, JournalEntryLegCommand,
+    PruneBalanceSnapshotsCommand, ReplayWebhookDeliveryCommand, ReverseLedgerEventCommand,
+    SnapshotAccountsCommand,
+};
+use crate::application::queries::{
+    GetAccountBalanceQuery, GetAccountQuery, GetAccountSnapshotsQuery, GetAccountStatementQuery,
+    GetAccountStatusHistoryQuery, ListAccountsQuery, ListLedgerEventsQuery,
+    ListOutboxMessagesQuery, ListWebhookDeliveriesQuery, ListWebhookSubscriptionsQuery,
+    VerifyAccountSnapshotsQuery,
+};
+use crate::domain::{
+    Account, AccountBalance, AccountStatement, AccountStatusChange, DomainError, LedgerEvent,
+    OutboxMessage, WebhookDelivery, WebhookSubscription,
 };
-use crate::application::queries::{GetAccountBalanceQuery, GetAccountQuery, ListAccountsQuery, ListLedgerEventsQuery};
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    response::{IntoResponse, Json, Response},
-    routing::{get, post},
+    middleware,
+    response::Json,
+    routing::{delete, get, post},
     Router,
 };
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tracing::{error, info};
+use tracing::info;
 
 // DTOs for API requests/responses
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateAccountRequest {
     pub account_number: String,
     pub account_name: String,
+    pub currency: Option<String>, // ISO 4217 code, defaults to "USD"
+    pub balance_policy: Option<String>, // "NO_OVERDRAFT", "OVERDRAFT" or "UNRESTRICTED"
+    pub overdraft_limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeAccountStatusRequest {
+    pub actor: Option<String>, // Only used without authentication, else the caller's client id
+    pub reason: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateLedgerEventRequest {
     pub account_id: i32,
     pub event_type: String, // "DEBIT" or "CREDIT"
+    pub amount: i64,        // In minor units of the currency
+    pub currency: Option<String>,
+    pub description: Option<String>,
+    pub expected_version: Option<i64>, // Account version the client last saw
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReverseLedgerEventRequest {
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateTransferRequest {
+    pub from_account_id: i32,
+    pub to_account_id: i32,
     pub amount: i64,
+    pub currency: Option<String>,
     pub description: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalEntryLegRequest {
+    pub account_id: i32,
+    pub event_type: String, // "DEBIT" or "CREDIT"
+    pub amount: i64,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateJournalEntryRequest {
+    pub description: Option<String>,
+    pub currency: Option<String>,
+    pub legs: Vec<JournalEntryLegRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateBalanceSnapshotRequest {
+    pub account_id: Option<i32>,
+    /// Snapshot every account with events after its latest snapshot instead of one
+    #[serde(default)]
+    pub all_accounts: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PruneBalanceSnapshotsRequest {
+    pub retain_days: i64,
+    pub account_id: Option<i32>, // Every account when omitted
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWebhookSubscriptionRequest {
+    pub url: String,
+    pub event_types: Vec<String>, // e.g. "ledger_event.created", "account.created"
+    pub account_id: Option<i32>,  // Only events of this account when set
+    pub balance_threshold: Option<i64>, // Required for "balance.below_threshold"
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ListEventsQuery {
     pub account_id: Option<i32>,
+    pub event_type: Option<String>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub description: Option<String>,
+    pub cursor: Option<i64>,
+    pub limit: Option<i64>,
 }
 
-// Error response helper
-struct ApiError(anyhow::Error);
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListOutboxParams {
+    pub status: Option<String>, // "PENDING", "RETRYING" or "PUBLISHED"
+    pub topic: Option<String>,
+    pub cursor: Option<i32>,
+    pub limit: Option<i64>,
+}
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        error!("API error: {:?}", self.0);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "error": self.0.to_string()
-            })),
-        )
-            .into_response()
-    }
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListWebhooksParams {
+    pub cursor: Option<i32>,
+    pub limit: Option<i64>,
 }
 
-impl<E> From<E> for ApiError
-where
-    E: Into<anyhow::Error>,
-{
-    fn from(err: E) -> Self {
-        Self(err.into())
-    }
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListDeadLettersParams {
+    pub subscription_id: Option<i32>,
+    pub cursor: Option<i32>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListAccountsParams {
+    pub search: Option<String>,
+    pub balance_policy: Option<String>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub cursor: Option<i32>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BalanceParams {
+    pub as_of: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatementParams {
+    pub from: NaiveDateTime,
+    pub to: NaiveDateTime,
 }
 
 /// Create the API router
 pub fn create_router(state: AppState) -> Router {
-    Router::new()
+    let router = Router::new()
+        // Probes for the orchestrator
+        .route("/health/live", get(health::live))
+        .route("/health/ready", get(health::ready))
+        .route("/metrics", get(metrics::metrics))
         // Account routes
         .route("/accounts", post(create_account))
         .route("/accounts", get(list_accounts))
         .route("/accounts/:id", get(get_account))
         .route("/accounts/:id/balance", get(get_account_balance))
+        .route("/accounts/:id/statement", get(get_account_statement))
+        .route("/accounts/:id/snapshots", get(list_account_snapshots))
+        .route(
+            "/accounts/:id/snapshots/verify",
+            get(verify_account_snapshots),
+        )
+        .route("/accounts/:id/freeze", post(freeze_account))
+        .route("/accounts/:id/unfreeze", post(unfreeze_account))
+        .route("/accounts/:id/close", post(close_account))
+        .route("/accounts/:id/status-history", get(get_status_history))
         // Ledger event routes
         .route("/events", post(create_ledger_event))
         .route("/events", get(list_ledger_events))
+        .route("/events/:id/reverse", post(reverse_ledger_event))
+        // Transfer routes
+        .route("/transfers", post(create_transfer))
+        // Journal entry routes
+        .route("/journal-entries", post(create_journal_entry))
         // Balance snapshot routes
         .route("/balances/snapshot", post(create_balance_snapshot))
+        .route("/balances/snapshots/prune", post(prune_balance_snapshots))
+        // Outbox routes
+        .route("/outbox", get(list_outbox_messages))
+        // Webhook routes
+        .route("/webhooks", post(create_webhook_subscription))
+        .route("/webhooks", get(list_webhook_subscriptions))
+        .route("/webhooks/:id", delete(delete_webhook_subscription))
+        .route("/webhooks/dead-letters", get(list_webhook_dead_letters))
+        .route(
+            "/webhooks/deliveries/:id/replay",
+            post(replay_webhook_delivery),
+        );
+
+    // Replay stored responses for retried writes carrying an Idempotency-Key
+    let router = if state.idempotency_enabled {
+        router.layer(middleware::from_fn_with_state(state.clone(), idempotency))
+    } else {
+        router
+    };
+
+    // Authentication runs before idempotency so rejected requests never claim a key
+    let router = if state.authenticator.is_some() {
+        router.layer(middleware::from_fn_with_state(state.clone(), authorize))
+    } else {
+        router
+    };
+
+    router
+        .layer(middleware::from_fn(metrics::track_requests))
         .with_state(state)
 }
 
+fn account_json(account: &Account) -> serde_json::Value {
+    json!({
+        "id": account.id,
+        "account_number": account.account_number.value(),
+        "account_name": account.account_name,
+        "currency": account.currency.code(),
+        "status": account.status.as_str(),
+        "balance_policy": account.balance_policy.as_str(),
+        "overdraft_limit": account.balance_policy.overdraft_limit(),
+        "created_at": account.created_at,
+        "updated_at": account.updated_at
+    })
+}
+
+fn status_change_json(change: &AccountStatusChange) -> serde_json::Value {
+    json!({
+        "id": change.id,
+        "account_id": change.account_id,
+        "from_status": change.from_status.as_str(),
+        "to_status": change.to_status.as_str(),
+        "actor": change.actor,
+        "reason": change.reason,
+        "created_at": change.created_at
+    })
+}
+
+fn ledger_event_json(event: &LedgerEvent) -> serde_json::Value {
+    json!({
+        "id": event.id,
+        "account_id": event.account_id,
+        "event_type": event.event_type.to_string(),
+        "amount": event.amount.value(),
+        "currency": event.amount.currency().code(),
+        "description": event.description,
+        "transfer_id": event.transfer_id,
+        "journal_entry_id": event.journal_entry_id,
+        "reverses_event_id": event.reverses_event_id,
+        "reversed_by_event_id": event.reversed_by_event_id,
+        "recorded_by": event.recorded_by,
+        "version": event.version,
+        "sequence": event.sequence,
+        "created_at": event.created_at
+    })
+}
+
+fn outbox_message_json(message: &OutboxMessage) -> serde_json::Value {
+    json!({
+        "id": message.id,
+        "topic": message.topic,
+        "key": message.key,
+        "status": message.status().as_str(),
+        "attempts": message.attempts,
+        "last_error": message.last_error,
+        "next_attempt_at": message.next_attempt_at,
+        "published_at": message.published_at,
+        "created_at": message.created_at,
+        "payload": message.payload
+    })
+}
+
+// The secret is only returned once, when the subscription is created
+fn webhook_subscription_json(subscription: &WebhookSubscription) -> serde_json::Value {
+    json!({
+        "id": subscription.id,
+        "url": subscription.url,
+        "event_types": subscription.event_types,
+        "account_id": subscription.account_id,
+        "balance_threshold": subscription.balance_threshold,
+        "created_at": subscription.created_at
+    })
+}
+
+fn webhook_delivery_json(delivery: &WebhookDelivery) -> serde_json::Value {
+    json!({
+        "id": delivery.id,
+        "subscription_id": delivery.subscription_id,
+        "outbox_message_id": delivery.outbox_message_id,
+        "event_type": delivery.event_type,
+        "status": delivery.status().as_str(),
+        "attempts": delivery.attempts,
+        "last_error": delivery.last_error,
+        "next_attempt_at": delivery.next_attempt_at,
+        "delivered_at": delivery.delivered_at,
+        "dead_lettered_at": delivery.dead_lettered_at,
+        "created_at": delivery.created_at,
+        "payload": delivery.payload
+    })
+}
+
+fn snapshot_json(snapshot: &AccountBalance) -> serde_json::Value {
+    json!({
+        "id": snapshot.id,
+        "account_id": snapshot.account_id,
+        "balance": snapshot.balance.value(),
+        "currency": snapshot.currency.code(),
+        "last_event_id": snapshot.last_event_id,
+        "snapshot_at": snapshot.snapshot_at
+    })
+}
+
+fn statement_json(statement: &AccountStatement) -> serde_json::Value {
+    let currency = statement.currency;
+    let lines_json: Vec<_> = statement
+        .lines
+        .iter()
+        .map(|line| {
+            let mut line_json = ledger_event_json(&line.event);
+            line_json["running_balance"] = json!(line.running_balance.value());
+            line_json
+        })
+        .collect();
+
+    json!({
+        "account_id": statement.account_id,
+        "currency": currency.code(),
+        "from": statement.from,
+        "to": statement.to,
+        "opening_balance": statement.opening_balance.value(),
+        "formatted_opening_balance": currency.format_minor_units(statement.opening_balance.value()),
+        "events": lines_json,
+        "count": lines_json.len(),
+        "total_debits": statement.total_debits,
+        "total_credits": statement.total_credits,
+        "closing_balance": statement.closing_balance.value(),
+        "formatted_closing_balance": currency.format_minor_units(statement.closing_balance.value())
+    })
+}
+
 // Handlers
 
 async fn create_account(
     State(state): State<AppState>,
-    Json(req): Json<CreateAccountRequest>,
+    ApiJson(req): ApiJson<CreateAccountRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
     info!("Creating account: {:?}", req);
 
-    let command = CreateAccountCommand::new(req.account_number, req.account_name);
+    let command = CreateAccountCommand {
+        currency: req.currency,
+        balance_policy: req.balance_policy,
+        overdraft_limit: req.overdraft_limit,
+        ..CreateAccountCommand::new(req.account_number, req.account_name)
+    };
 
     let account = state.mediator.send_create_account(command).await?;
 
-    Ok(Json(json!({
-        "id": account.id,
-        "account_number": account.account_number.value(),
-        "account_name": account.account_name,
-        "created_at": account.created_at,
-        "updated_at": account.updated_at
-    })))
+    Ok(Json(account_json(&account)))
 }
 
 async fn get_account(
     State(state): State<AppState>,
-    Path(id): Path<i32>,
+    ApiPath(id): ApiPath<i32>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
     info!("Getting account: id={}", id);
 
     let query = GetAccountQuery::new(id);
     let account = state.mediator.send_get_account(query).await?;
 
-    Ok(Json(json!({
-        "id": account.id,
-        "account_number": account.account_number.value(),
-        "account_name": account.account_name,
-        "created_at": account.created_at,
-        "updated_at": account.updated_at
-    })))
+    Ok(Json(account_json(&account)))
 }
 
 async fn list_accounts(
     State(state): State<AppState>,
+    ApiQuery(params): ApiQuery<ListAccountsParams>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    info!("Listing all accounts");
+    info!("Listing accounts: {:?}", params);
 
-    let query = ListAccountsQuery::new();
-    let accounts = state.mediator.send_list_accounts(query).await?;
+    let query = ListAccountsQuery {
+        search: params.search,
+        balance_policy: params.balance_policy,
+        created_from: params.created_from,
+        created_to: params.created_to,
+        ..ListAccountsQuery::new()
+    }
+    .with_page(params.cursor, params.limit);
 
-    let accounts_json: Vec<_> = accounts
-        .into_iter()
-        .map(|acc| {
-            json!({
-                "id": acc.id,
-                "account_number": acc.account_number.value(),
-                "account_name": acc.account_name,
-                "created_at": acc.created_at,
-                "updated_at": acc.updated_at
-            })
-        })
-        .collect();
+    let page = state.mediator.send_list_accounts(query).await?;
+
+    let accounts_json: Vec<_> = page.items.iter().map(account_json).collect();
 
     Ok(Json(json!({
         "accounts": accounts_json,
-        "count": accounts_json.len()
+        "count": accounts_json.len(),
+        "next_cursor": page.next_cursor
+    })))
+}
+
+async fn freeze_account(
+    State(state): State<AppState>,
+    caller: CallerId,
+    ApiPath(id): ApiPath<i32>,
+    ApiJson(req): ApiJson<ChangeAccountStatusRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Freezing account: id={}", id);
+
+    let actor = status_actor(caller, req.actor);
+    let command = ChangeAccountStatusCommand::freeze(id, actor, req.reason);
+    let change = state.mediator.send_change_account_status(command).await?;
+
+    Ok(Json(status_change_json(&change)))
+}
+
+async fn unfreeze_account(
+    State(state): State<AppState>,
+    caller: CallerId,
+    ApiPath(id): ApiPath<i32>,
+    ApiJson(req): ApiJson<ChangeAccountStatusRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Unfreezing account: id={}", id);
+
+    let actor = status_actor(caller, req.actor);
+    let command = ChangeAccountStatusCommand::unfreeze(id, actor, req.reason);
+    let change = state.mediator.send_change_account_status(command).await?;
+
+    Ok(Json(status_change_json(&change)))
+}
+
+async fn close_account(
+    State(state): State<AppState>,
+    caller: CallerId,
+    ApiPath(id): ApiPath<i32>,
+    ApiJson(req): ApiJson<ChangeAccountStatusRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Closing account: id={}", id);
+
+    let actor = status_actor(caller, req.actor);
+    let command = ChangeAccountStatusCommand::close(id, actor, req.reason);
+    let change = state.mediator.send_change_account_status(command).await?;
+
+    Ok(Json(status_change_json(&change)))
+}
+
+/// The authenticated client, never the body, is recorded for audit; `actor` from
+/// the body is only used with authentication disabled. A missing actor fails validation.
+fn status_actor(CallerId(client_id): CallerId, actor: Option<String>) -> String {
+    client_id.or(actor).unwrap_or_default()
+}
+
+async fn get_status_history(
+    State(state): State<AppState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Getting status history: account_id={}", id);
+
+    let query = GetAccountStatusHistoryQuery::new(id);
+    let changes = state
+        .mediator
+        .send_get_account_status_history(query)
+        .await?;
+
+    let changes_json: Vec<_> = changes.iter().map(status_change_json).collect();
+
+    Ok(Json(json!({
+        "changes": changes_json,
+        "count": changes_json.len()
     })))
 }
 
 async fn get_account_balance(
     State(state): State<AppState>,
-    Path(account_id): Path<i32>,
+    ApiPath(account_id): ApiPath<i32>,
+    ApiQuery(params): ApiQuery<BalanceParams>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    info!("Getting balance for account_id={}", account_id);
+    info!(
+        "Getting balance for account_id={}: {:?}",
+        account_id, params
+    );
 
-    let query = GetAccountBalanceQuery::new(account_id);
+    let query = GetAccountBalanceQuery::with_snapshot(account_id).with_as_of(params.as_of);
     let balance = state.mediator.send_get_account_balance(query).await?;
 
     Ok(Json(json!({
         "account_id": balance.account_id,
+        "as_of": params.as_of,
         "balance": balance.balance.value(),
+        "currency": balance.currency.code(),
+        "formatted_balance": balance.currency.format_minor_units(balance.balance.value()),
+        "last_event_id": balance.last_event_id,
         "snapshot_at": balance.snapshot_at
     })))
 }
 
+async fn get_account_statement(
+    State(state): State<AppState>,
+    ApiPath(account_id): ApiPath<i32>,
+    ApiQuery(params): ApiQuery<StatementParams>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!(
+        "Getting statement for account_id={}: {:?}",
+        account_id, params
+    );
+
+    let query = GetAccountStatementQuery::new(account_id, params.from, params.to);
+    let statement = state.mediator.send_get_account_statement(query).await?;
+
+    Ok(Json(statement_json(&statement)))
+}
+
+async fn list_account_snapshots(
+    State(state): State<AppState>,
+    ApiPath(account_id): ApiPath<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Listing balance snapshots for account_id={}", account_id);
+
+    let query = GetAccountSnapshotsQuery::new(account_id);
+    let snapshots = state.mediator.send_get_account_snapshots(query).await?;
+
+    let snapshots_json: Vec<_> = snapshots.iter().map(snapshot_json).collect();
+
+    Ok(Json(json!({
+        "snapshots": snapshots_json,
+        "count": snapshots_json.len()
+    })))
+}
+
+async fn verify_account_snapshots(
+    State(state): State<AppState>,
+    ApiPath(account_id): ApiPath<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Verifying balance snapshots for account_id={}", account_id);
+
+    let query = VerifyAccountSnapshotsQuery::new(account_id);
+    let verifications = state.mediator.send_verify_account_snapshots(query).await?;
+
+    let mut mismatches = 0;
+    let snapshots_json: Vec<_> = verifications
+        .iter()
+        .map(|verification| {
+            let status = match verification.matches() {
+                Some(true) => "OK",
+                Some(false) => {
+                    mismatches += 1;
+                    "MISMATCH"
+                }
+                None => "UNVERIFIABLE",
+            };
+            let mut snapshot_json = snapshot_json(&verification.snapshot);
+            snapshot_json["expected_balance"] =
+                json!(verification.expected_balance.map(|balance| balance.value()));
+            snapshot_json["status"] = json!(status);
+            snapshot_json
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "account_id": account_id,
+        "checked": snapshots_json.len(),
+        "mismatches": mismatches,
+        "snapshots": snapshots_json
+    })))
+}
+
 async fn create_ledger_event(
     State(state): State<AppState>,
-    Json(req): Json<CreateLedgerEventRequest>,
+    CallerId(recorded_by): CallerId,
+    ApiJson(req): ApiJson<CreateLedgerEventRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
     info!("Creating ledger event: {:?}", req);
 
-    let command = CreateLedgerEventCommand::new(
-        req.account_id,
-        req.event_type,
-        req.amount,
-        req.description,
-    );
+    let command = CreateLedgerEventCommand {
+        currency: req.currency,
+        recorded_by,
+        expected_version: req.expected_version,
+        ..CreateLedgerEventCommand::new(req.account_id, req.event_type, req.amount, req.description)
+    };
 
     let event = state.mediator.send_create_ledger_event(command).await?;
 
-    Ok(Json(json!({
-        "id": event.id,
-        "account_id": event.account_id,
-        "event_type": event.event_type.to_string(),
-        "amount": event.amount.value(),
-        "description": event.description,
-        "created_at": event.created_at
-    })))
+    Ok(Json(ledger_event_json(&event)))
 }
 
 async fn list_ledger_events(
     State(state): State<AppState>,
-    Query(params): Query<ListEventsQuery>,
+    ApiQuery(params): ApiQuery<ListEventsQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
     info!("Listing ledger events: {:?}", params);
 
-    let query = if let Some(account_id) = params.account_id {
-        ListLedgerEventsQuery::for_account(account_id)
-    } else {
-        ListLedgerEventsQuery::new()
+    let query = ListLedgerEventsQuery {
+        account_id: params.account_id,
+        event_type: params.event_type,
+        min_amount: params.min_amount,
+        max_amount: params.max_amount,
+        created_from: params.created_from,
+        created_to: params.created_to,
+        description: params.description,
+        ..ListLedgerEventsQuery::new()
+    }
+    .with_page(params.cursor, params.limit);
+
+    let page = state.mediator.send_list_ledger_events(query).await?;
+
+    let events_json: Vec<_> = page.items.iter().map(ledger_event_json).collect();
+
+    Ok(Json(json!({
+        "events": events_json,
+        "count": events_json.len(),
+        "next_cursor": page.next_cursor
+    })))
+}
+
+async fn list_outbox_messages(
+    State(state): State<AppState>,
+    ApiQuery(params): ApiQuery<ListOutboxParams>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Listing outbox messages: {:?}", params);
+
+    let query = ListOutboxMessagesQuery {
+        status: params.status,
+        topic: params.topic,
+        ..ListOutboxMessagesQuery::new()
+    }
+    .with_page(params.cursor, params.limit);
+
+    let page = state.mediator.send_list_outbox_messages(query).await?;
+
+    let messages_json: Vec<_> = page.items.iter().map(outbox_message_json).collect();
+
+    Ok(Json(json!({
+        "messages": messages_json,
+        "count": messages_json.len(),
+        "next_cursor": page.next_cursor
+    })))
+}
+
+async fn create_webhook_subscription(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<CreateWebhookSubscriptionRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!(
+        "Creating webhook subscription: url={} events={:?}",
+        req.url, req.event_types
+    );
+
+    let command = CreateWebhookSubscriptionCommand::new(req.url, req.event_types)
+        .with_account(req.account_id)
+        .with_balance_threshold(req.balance_threshold);
+    let subscription = state
+        .mediator
+        .send_create_webhook_subscription(command)
+        .await?;
+
+    let mut body = webhook_subscription_json(&subscription);
+    body["secret"] = json!(subscription.secret);
+    Ok(Json(body))
+}
+
+async fn list_webhook_subscriptions(
+    State(state): State<AppState>,
+    ApiQuery(params): ApiQuery<ListWebhooksParams>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Listing webhook subscriptions: {:?}", params);
+
+    let query = ListWebhookSubscriptionsQuery::new(params.cursor, params.limit);
+    let page = state
+        .mediator
+        .send_list_webhook_subscriptions(query)
+        .await?;
+
+    let subscriptions_json: Vec<_> = page.items.iter().map(webhook_subscription_json).collect();
+
+    Ok(Json(json!({
+        "subscriptions": subscriptions_json,
+        "count": subscriptions_json.len(),
+        "next_cursor": page.next_cursor
+    })))
+}
+
+async fn delete_webhook_subscription(
+    State(state): State<AppState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<StatusCode, ApiError> {
+    info!("Deleting webhook subscription: id={}", id);
+
+    let command = DeleteWebhookSubscriptionCommand::new(id);
+    state
+        .mediator
+        .send_delete_webhook_subscription(command)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_webhook_dead_letters(
+    State(state): State<AppState>,
+    ApiQuery(params): ApiQuery<ListDeadLettersParams>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Listing webhook dead letters: {:?}", params);
+
+    let query = ListWebhookDeliveriesQuery::dead_letters(params.subscription_id)
+        .with_page(params.cursor, params.limit);
+    let page = state.mediator.send_list_webhook_deliveries(query).await?;
+
+    let deliveries_json: Vec<_> = page.items.iter().map(webhook_delivery_json).collect();
+
+    Ok(Json(json!({
+        "deliveries": deliveries_json,
+        "count": deliveries_json.len(),
+        "next_cursor": page.next_cursor
+    })))
+}
+
+async fn replay_webhook_delivery(
+    State(state): State<AppState>,
+    ApiPath(id): ApiPath<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Replaying webhook delivery: id={}", id);
+
+    let command = ReplayWebhookDeliveryCommand::new(id);
+    let delivery = state.mediator.send_replay_webhook_delivery(command).await?;
+
+    Ok(Json(webhook_delivery_json(&delivery)))
+}
+
+async fn reverse_ledger_event(
+    State(state): State<AppState>,
+    CallerId(recorded_by): CallerId,
+    ApiPath(id): ApiPath<i32>,
+    ApiJson(req): ApiJson<ReverseLedgerEventRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Reversing ledger event: id={}", id);
+
+    let command = ReverseLedgerEventCommand {
+        recorded_by,
+        ..ReverseLedgerEventCommand::new(id, req.description)
     };
+    let reversal = state.mediator.send_reverse_ledger_event(command).await?;
+
+    Ok(Json(ledger_event_json(&reversal)))
+}
+
+async fn create_transfer(
+    State(state): State<AppState>,
+    CallerId(recorded_by): CallerId,
+    ApiJson(req): ApiJson<CreateTransferRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Creating transfer: {:?}", req);
 
-    let events = state.mediator.send_list_ledger_events(query).await?;
+    let command = CreateTransferCommand {
+        currency: req.currency,
+        recorded_by,
+        ..CreateTransferCommand::new(
+            req.from_account_id,
+            req.to_account_id,
+            req.amount,
+            req.description,
+        )
+    };
 
-    let events_json: Vec<_> = events
+    let transfer = state.mediator.send_create_transfer(command).await?;
+
+    Ok(Json(json!({
+        "id": transfer.id,
+        "from_account_id": transfer.from_account_id,
+        "to_account_id": transfer.to_account_id,
+        "amount": transfer.amount.value(),
+        "currency": transfer.amount.currency().code(),
+        "description": transfer.description,
+        "created_at": transfer.created_at,
+        "debit_event": transfer.debit_event.as_ref().map(ledger_event_json),
+        "credit_event": transfer.credit_event.as_ref().map(ledger_event_json)
+    })))
+}
+
+async fn create_journal_entry(
+    State(state): State<AppState>,
+    CallerId(recorded_by): CallerId,
+    ApiJson(req): ApiJson<CreateJournalEntryRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Creating journal entry: {:?}", req);
+
+    let legs = req
+        .legs
         .into_iter()
-        .map(|event| {
-            json!({
-                "id": event.id,
-                "account_id": event.account_id,
-                "event_type": event.event_type.to_string(),
-                "amount": event.amount.value(),
-                "description": event.description,
-                "created_at": event.created_at
-            })
+        .map(|leg| JournalEntryLegCommand {
+            account_id: leg.account_id,
+            event_type: leg.event_type,
+            amount: leg.amount,
+            description: leg.description,
         })
         .collect();
+    let command = CreateJournalEntryCommand {
+        currency: req.currency,
+        recorded_by,
+        ..CreateJournalEntryCommand::new(req.description, legs)
+    };
+
+    let entry = state.mediator.send_create_journal_entry(command).await?;
 
     Ok(Json(json!({
-        "events": events_json,
-        "count": events_json.len()
+        "id": entry.id,
+        "description": entry.description,
+        "currency": entry.currency().map(|currency| currency.code()),
+        "created_at": entry.created_at,
+        "legs": entry.legs.iter().map(ledger_event_json).collect::<Vec<_>>()
     })))
 }
 
 async fn create_balance_snapshot(
     State(state): State<AppState>,
-    Json(account_id_json): Json<serde_json::Value>,
+    ApiJson(req): ApiJson<CreateBalanceSnapshotRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let account_id = account_id_json
-        .get("account_id")
-        .and_then(|v| v.as_i64())
-        .ok_or_else(|| anyhow::anyhow!("Missing account_id"))? as i32;
+    let account_id = match (req.account_id, req.all_accounts) {
+        (Some(account_id), false) => account_id,
+        (None, true) => return snapshot_all_accounts(&state).await,
+        _ => {
+            return Err(DomainError::ValidationError(
+                "Set either account_id or all_accounts".to_string(),
+            )
+            .into())
+        }
+    };
 
     info!("Creating balance snapshot for account_id={}", account_id);
 
     let command = CreateBalanceSnapshotCommand::new(account_id);
     let snapshot = state.mediator.send_create_balance_snapshot(command).await?;
 
+    Ok(Json(snapshot_json(&snapshot)))
+}
+
+async fn snapshot_all_accounts(state: &AppState) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Creating balance snapshots for all accounts");
+
+    let run = state
+        .mediator
+        .send_snapshot_accounts(SnapshotAccountsCommand::all())
+        .await?;
+
+    let snapshots_json: Vec<_> = run.snapshots.iter().map(snapshot_json).collect();
+
     Ok(Json(json!({
-        "id": snapshot.id,
-        "account_id": snapshot.account_id,
-        "balance": snapshot.balance.value(),
-        "snapshot_at": snapshot.snapshot_at
+        "snapshots": snapshots_json,
+        "count": snapshots_json.len(),
+        "accounts_checked": run.accounts_checked
     })))
 }
+
+async fn prune_balance_snapshots(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<PruneBalanceSnapshotsRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!("Pruning balance snapshots: {:?}", req);
+
+    let command = PruneBalanceSnapshotsCommand {
+        account_id: req.account_id,
+        ..PruneBalanceSnapshotsCommand::new(req.retain_days)
+    };
+    let prune = state.mediator.send_prune_balance_snapshots(command).await?;
+
+    Ok(Json(json!({
+        "deleted": prune.deleted,
+        "accounts_checked": prune.accounts_checked
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::auth::{Authenticator, API_KEY_HEADER};
+    use crate::application::Mediator;
+    use crate::config::{ApiKeyConfig, AuthConfig};
+    use crate::infrastructure::memory::{
+        InMemoryAccountBalanceRepository, InMemoryAccountRepository, InMemoryIdempotencyRepository,
+        InMemoryJournalEntryRepository, InMemoryLedgerEventRepository, InMemoryOutboxRepository,
+        InMemoryStorageHealth, InMemoryStore, InMemoryTransferRepository,
+        InMemoryWebhookRepository,
+    };
+    use axum::body::{to_bytes, Body};
+    use axum::http::{header, Method, Request, StatusCode};
+    use serde_json::Value;
+    use sha2::{Digest, Sha256};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn state() -> AppState {
+        let store = InMemoryStore::new();
+        let mediator = Mediator::new(
+            Arc::new(InMemoryAccountRepository::new(store.clone())),
+            Arc::new(InMemoryLedgerEventRepository::new(store.clone())),
+            Arc::new(InMemoryAccountBalanceRepository::new(store.clone())),
+            Arc::new(InMemoryTransferRepository::new(store.clone())),
+            Arc::new(InMemoryJournalEntryRepository::new(store.clone())),
+            Arc::new(InMemoryOutboxRepository::new(store.clone())),
+            Arc::new(InMemoryWebhookRepository::new(store.clone())),
+        );
+        AppState::new(
+            mediator,
+            Arc::new(InMemoryIdempotencyRepository::new(store)),
+            Arc::new(InMemoryStorageHealth),
+        )
+    }
+
+    fn app() -> Router {
+        create_router(state())
+    }
+
+    async fn send(app: &Router, method: Method, uri: &str, body: Value) -> (StatusCode, Value) {
+        send_with_key(app, None, method, uri, body).await
+    }
+
+    async fn send_with_key(
+        app: &Router,
+        api_key: Option<&str>,
+        method: Method,
+        uri: &str,
+        body: Value,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(api_key) = api_key {
+            request = request.header(API_KEY_HEADER, api_key);
+        }
+        let request = request.body(Body::from(body.to_string())).unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        (status, body)
+    }
+
+    #[tokio::test]
+    async fn test_transfer_between_accounts() {
+        let app = app();
+        let (_, alice) = send(
+            &app,
+            Method::POST,
+            "/accounts",
+            json!({"account_number": "ACC001", "account_name": "Alice"}),
+        )
+        .await;
+        let (_, bob) = send(
+            &app,
+            Method::POST,
+            "/accounts",
+            json!({"account_number": "ACC002", "account_name": "Bob"}),
+        )
+        .await;
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/events",
+            json!({"account_id": alice["id"], "event_type": "CREDIT", "amount": 1000}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/transfers",
+            json!({"from_account_id": alice["id"], "to_account_id": bob["id"], "amount": 400}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, problem) = send(
+            &app,
+            Method::POST,
+            "/transfers",
+            json!({"from_account_id": alice["id"], "to_account_id": bob["id"], "amount": 700}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem["code"], "INSUFFICIENT_BALANCE");
+
+        let uri = format!("/accounts/{}/balance", alice["id"]);
+        let (_, balance) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(balance["balance"], 600);
+
+        let uri = format!("/accounts/{}/balance", bob["id"]);
+        let (_, balance) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(balance["balance"], 400);
+    }
+
+    #[tokio::test]
+    async fn test_account_statement() {
+        let app = app();
+        let (_, account) = send(
+            &app,
+            Method::POST,
+            "/accounts",
+            json!({"account_number": "ACC001", "account_name": "Alice"}),
+        )
+        .await;
+        for (event_type, amount) in [("CREDIT", 1000), ("DEBIT", 300)] {
+            send(
+                &app,
+                Method::POST,
+                "/events",
+                json!({"account_id": account["id"], "event_type": event_type, "amount": amount}),
+            )
+            .await;
+        }
+        send(
+            &app,
+            Method::POST,
+            "/balances/snapshot",
+            json!({"account_id": account["id"]}),
+        )
+        .await;
+
+        let uri = format!(
+            "/accounts/{}/statement?from=2000-01-01T00:00:00&to=2100-01-01T00:00:00",
+            account["id"]
+        );
+        let (status, statement) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(statement["opening_balance"], 0);
+        assert_eq!(statement["count"], 2);
+        assert_eq!(statement["events"][0]["running_balance"], 1000);
+        assert_eq!(statement["events"][1]["running_balance"], 700);
+        assert_eq!(statement["total_credits"], 1000);
+        assert_eq!(statement["total_debits"], 300);
+        assert_eq!(statement["closing_balance"], 700);
+
+        // A period after every event opens and closes at the current balance
+        let uri = format!(
+            "/accounts/{}/statement?from=2100-01-01T00:00:00&to=2100-02-01T00:00:00",
+            account["id"]
+        );
+        let (_, statement) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(statement["opening_balance"], 700);
+        assert_eq!(statement["count"], 0);
+        assert_eq!(statement["closing_balance"], 700);
+
+        let uri = format!(
+            "/accounts/{}/statement?from=2100-01-01T00:00:00&to=2000-01-01T00:00:00",
+            account["id"]
+        );
+        let (status, _) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let uri = format!("/accounts/{}/statement", account["id"]);
+        let (status, _) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_all_accounts() {
+        let app = app();
+        let mut ids = Vec::new();
+        for number in ["ACC001", "ACC002", "ACC003"] {
+            let (_, account) = send(
+                &app,
+                Method::POST,
+                "/accounts",
+                json!({"account_number": number, "account_name": "Test"}),
+            )
+            .await;
+            ids.push(account["id"].clone());
+        }
+        for id in &ids[..2] {
+            send(
+                &app,
+                Method::POST,
+                "/events",
+                json!({"account_id": id, "event_type": "CREDIT", "amount": 500}),
+            )
+            .await;
+        }
+
+        let (status, run) = send(
+            &app,
+            Method::POST,
+            "/balances/snapshot",
+            json!({"all_accounts": true}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(run["accounts_checked"], 3);
+        assert_eq!(run["count"], 2);
+        assert_eq!(run["snapshots"][0]["balance"], 500);
+
+        // Accounts are only snapshotted again once they have new events
+        let (_, run) = send(
+            &app,
+            Method::POST,
+            "/balances/snapshot",
+            json!({"all_accounts": true}),
+        )
+        .await;
+        assert_eq!(run["count"], 0);
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/balances/snapshot",
+            json!({"account_id": ids[0], "all_accounts": true}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_balance_as_of() {
+        let app = app();
+        let (_, account) = send(
+            &app,
+            Method::POST,
+            "/accounts",
+            json!({"account_number": "ACC001", "account_name": "Alice"}),
+        )
+        .await;
+        send(
+            &app,
+            Method::POST,
+            "/events",
+            json!({"account_id": account["id"], "event_type": "CREDIT", "amount": 1000}),
+        )
+        .await;
+        send(
+            &app,
+            Method::POST,
+            "/balances/snapshot",
+            json!({"account_id": account["id"]}),
+        )
+        .await;
+
+        // Before the first event, the snapshot taken since then must not be used
+        let uri = format!(
+            "/accounts/{}/balance?as_of=2000-01-01T00:00:00",
+            account["id"]
+        );
+        let (status, balance) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(balance["balance"], 0);
+        assert_eq!(balance["as_of"], "2000-01-01T00:00:00");
+
+        let uri = format!(
+            "/accounts/{}/balance?as_of=2100-01-01T00:00:00",
+            account["id"]
+        );
+        let (_, balance) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(balance["balance"], 1000);
+
+        let uri = format!("/accounts/{}/balance?as_of=yesterday", account["id"]);
+        let (status, _) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_history_verify_and_prune() {
+        let app = app();
+        let (_, account) = send(
+            &app,
+            Method::POST,
+            "/accounts",
+            json!({"account_number": "ACC001", "account_name": "Alice"}),
+        )
+        .await;
+        for amount in [1000, 250] {
+            send(
+                &app,
+                Method::POST,
+                "/events",
+                json!({"account_id": account["id"], "event_type": "CREDIT", "amount": amount}),
+            )
+            .await;
+            send(
+                &app,
+                Method::POST,
+                "/balances/snapshot",
+                json!({"account_id": account["id"]}),
+            )
+            .await;
+        }
+
+        let uri = format!("/accounts/{}/snapshots", account["id"]);
+        let (status, history) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(history["count"], 2);
+        assert_eq!(history["snapshots"][0]["balance"], 1250);
+
+        let uri = format!("/accounts/{}/snapshots/verify", account["id"]);
+        let (status, report) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report["checked"], 2);
+        assert_eq!(report["mismatches"], 0);
+        assert_eq!(report["snapshots"][1]["expected_balance"], 1000);
+        assert_eq!(report["snapshots"][1]["status"], "OK");
+
+        // Both snapshots are recent, and the latest one is always kept
+        let (status, prune) = send(
+            &app,
+            Method::POST,
+            "/balances/snapshots/prune",
+            json!({"retain_days": 30}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(prune["deleted"], 0);
+        assert_eq!(prune["accounts_checked"], 1);
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/balances/snapshots/prune",
+            json!({"retain_days": -1}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_create_event_with_expected_version() {
+        let app = app();
+        let (_, account) = send(
+            &app,
+            Method::POST,
+            "/accounts",
+            json!({"account_number": "ACC001", "account_name": "Alice"}),
+        )
+        .await;
+
+        let (status, first) = send(
+            &app,
+            Method::POST,
+            "/events",
+            json!({"account_id": account["id"], "event_type": "CREDIT", "amount": 100, "expected_version": 0}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(first["version"], 1);
+
+        let (_, second) = send(
+            &app,
+            Method::POST,
+            "/events",
+            json!({"account_id": account["id"], "event_type": "CREDIT", "amount": 100}),
+        )
+        .await;
+        assert_eq!(second["version"], 2);
+        assert!(second["sequence"].as_i64() > first["sequence"].as_i64());
+
+        // Written against version 1, after the second event was appended
+        let (status, conflict) = send(
+            &app,
+            Method::POST,
+            "/events",
+            json!({"account_id": account["id"], "event_type": "DEBIT", "amount": 50, "expected_version": 1}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(conflict["code"], "VERSION_CONFLICT");
+        assert_eq!(conflict["current_version"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_events_pages_by_sequence() {
+        let app = app();
+        for number in ["ACC001", "ACC002"] {
+            let (_, account) = send(
+                &app,
+                Method::POST,
+                "/accounts",
+                json!({"account_number": number, "account_name": "Alice"}),
+            )
+            .await;
+            send(
+                &app,
+                Method::POST,
+                "/events",
+                json!({"account_id": account["id"], "event_type": "CREDIT", "amount": 100}),
+            )
+            .await;
+        }
+
+        let (status, first) = send(&app, Method::GET, "/events?limit=1", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(first["next_cursor"], first["events"][0]["sequence"]);
+
+        let uri = format!("/events?limit=1&cursor={}", first["next_cursor"]);
+        let (_, second) = send(&app, Method::GET, &uri, Value::Null).await;
+        assert_eq!(second["count"], 1);
+        assert!(second["events"][0]["sequence"].as_i64() > first["next_cursor"].as_i64());
+        assert_eq!(second["next_cursor"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_ledger_events_are_recorded_in_outbox() {
+        let app = app();
+        let (_, account) = send(
+            &app,
+            Method::POST,
+            "/accounts",
+            json!({"account_number": "ACC001", "account_name": "Alice"}),
+        )
+        .await;
+        let (_, event) = send(
+            &app,
+            Method::POST,
+            "/events",
+            json!({"account_id": account["id"], "event_type": "CREDIT", "amount": 100}),
+        )
+        .await;
+
+        let (status, body) = send(&app, Method::GET, "/outbox?status=pending", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["count"], 2);
+        assert_eq!(body["messages"][0]["topic"], "account.created");
+        let message = &body["messages"][1];
+        assert_eq!(message["topic"], "ledger_event.created");
+        assert_eq!(message["key"], account["id"].to_string());
+        assert_eq!(message["status"], "PENDING");
+        assert_eq!(message["payload"]["id"], event["id"]);
+        assert_eq!(message["payload"]["version"], 1);
+
+        let (_, published) = send(&app, Method::GET, "/outbox?status=published", Value::Null).await;
+        assert_eq!(published["count"], 0);
+
+        let (status, _) = send(&app, Method::GET, "/outbox?status=lost", Value::Null).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_subscriptions_receive_deliveries() {
+        let app = app();
+        let (_, account) = send(
+            &app,
+            Method::POST,
+            "/accounts",
+            json!({"account_number": "ACC001", "account_name": "Alice"}),
+        )
+        .await;
+
+        let (status, subscription) = send(
+            &app,
+            Method::POST,
+            "/webhooks",
+            json!({
+                "url": "https://hooks.example.com/ledger",
+                "event_types": ["ledger_event.created", "balance.below_threshold"],
+                "account_id": account["id"],
+                "balance_threshold": 50
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(subscription["secret"]
+            .as_str()
+            .unwrap()
+            .starts_with("whsec_"));
+
+        let (status, invalid) = send(
+            &app,
+            Method::POST,
+            "/webhooks",
+            json!({"url": "ftp://hooks.example.com", "event_types": ["account.created"]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(invalid["code"], "VALIDATION_ERROR");
+
+        let (_, listed) = send(&app, Method::GET, "/webhooks", Value::Null).await;
+        assert_eq!(listed["count"], 1);
+        assert!(listed["subscriptions"][0].get("secret").is_none());
+
+        for (event_type, amount) in [("CREDIT", 100), ("DEBIT", 60)] {
+            send(
+                &app,
+                Method::POST,
+                "/events",
+                json!({"account_id": account["id"], "event_type": event_type, "amount": amount}),
+            )
+            .await;
+        }
+
+        // Nothing has failed yet, so there is nothing to replay
+        let (_, dead_letters) = send(
+            &app,
+            Method::GET,
+            &format!(
+                "/webhooks/dead-letters?subscription_id={}",
+                subscription["id"]
+            ),
+            Value::Null,
+        )
+        .await;
+        assert_eq!(dead_letters["count"], 0);
+
+        // Two ledger events plus the balance dropping from 100 to 40
+        let (status, conflict) = send(
+            &app,
+            Method::POST,
+            "/webhooks/deliveries/3/replay",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(conflict["code"], "DELIVERY_NOT_REPLAYABLE");
+        assert_eq!(conflict["status"], "PENDING");
+
+        let (status, _) = send(
+            &app,
+            Method::POST,
+            "/webhooks/deliveries/4/replay",
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let uri = format!("/webhooks/{}", subscription["id"]);
+        let (status, _) = send(&app, Method::DELETE, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&app, Method::DELETE, &uri, Value::Null).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_health_probes() {
+        let app = app();
+
+        let (status, _) = send(&app, Method::GET, "/health/live", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = send(&app, Method::GET, "/health/ready", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["checks"]["storage"]["status"], "ok");
+
+        // Not served unless a metrics recorder was installed
+        let (status, _) = send(&app, Method::GET, "/metrics", Value::Null).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_account_number() {
+        let app = app();
+        let request = json!({"account_number": "ACC001", "account_name": "Alice"});
+
+        let (status, _) = send(&app, Method::POST, "/accounts", request.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, problem) = send(&app, Method::POST, "/accounts", request).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(problem["code"], "DUPLICATE_ACCOUNT_NUMBER");
+    }
+
+    #[tokio::test]
+    async fn test_authentication() {
+        let api_key = |client_id: &str, key: &str, scopes: &[&str]| ApiKeyConfig {
+            client_id: client_id.to_string(),
+            key_sha256: format!("{:x}", Sha256::digest(key.as_bytes())),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+        };
+        let authenticator = Authenticator::from_config(&AuthConfig {
+            enabled: true,
+            api_keys: vec![
+                api_key("ops", "ops-key", &["accounts:write", "events:write"]),
+                api_key("reporting", "reporting-key", &["accounts:read"]),
+            ],
+            jwt: None,
+        })
+        .unwrap();
+        let app = create_router(state().with_authenticator(authenticator));
+        let account = json!({"account_number": "ACC001", "account_name": "Alice"});
+
+        let (status, _) = send(&app, Method::GET, "/health/live", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, problem) = send(&app, Method::POST, "/accounts", account.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(problem["code"], "UNAUTHORIZED");
+
+        let (status, problem) = send_with_key(
+            &app,
+            Some("reporting-key"),
+            Method::POST,
+            "/accounts",
+            account.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(problem["required_scope"], "accounts:write");
+
+        let (status, alice) =
+            send_with_key(&app, Some("ops-key"), Method::POST, "/accounts", account).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, event) = send_with_key(
+            &app,
+            Some("ops-key"),
+            Method::POST,
+            "/events",
+            json!({"account_id": alice["id"], "event_type": "CREDIT", "amount": 1000}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(event["recorded_by"], "ops");
+
+        // The body cannot put someone else in the status history
+        let (status, change) = send_with_key(
+            &app,
+            Some("ops-key"),
+            Method::POST,
+            &format!("/accounts/{}/freeze", alice["id"]),
+            json!({"actor": "someone-else", "reason": "Chargeback review"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(change["actor"], "ops");
+    }
+}
@@ -1,19 +1,161 @@
-use crate::api::{create_router, AppState};
+use crate::api::shutdown::{track_in_flight, InFlightRequests};
+use crate::api::{create_router, ApiError, AppState};
+use crate::config::ServerConfig;
 use anyhow::Result;
+use axum::{error_handling::HandleErrorLayer, http::StatusCode, middleware, BoxError};
+use std::future::{Future, IntoFuture};
 use tokio::net::TcpListener;
-use tracing::info;
+use tokio::sync::oneshot;
+use tower::timeout::{error::Elapsed, TimeoutLayer};
+use tower::ServiceBuilder;
+use tracing::{error, info, warn};
 
-pub async fn start_server(state: AppState, port: u16) -> Result<()> {
-    let app = create_router(state);
-
-    let addr = format!("0.0.0.0:{}", port);
+/// Serves the API until `shutdown` completes, then drains in-flight requests
+pub async fn start_server(
+    state: AppState,
+    config: &ServerConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    let addr = config.bind_address;
     info!("Starting REST API server on {}", addr);
 
-    let listener = TcpListener::bind(&addr).await?;
+    let listener = TcpListener::bind(addr).await?;
 
     info!("✓ REST API server listening on http://{}", addr);
 
-    axum::serve(listener, app).await?;
+    serve(listener, state, config, shutdown).await
+}
+
+/// Serves the API on an already bound listener.
+///
+/// Once `shutdown` completes the listener is closed, idle connections are dropped
+/// and requests already running get `shutdown_timeout` to finish. Requests still
+/// running after that are abandoned.
+pub async fn serve(
+    listener: TcpListener,
+    state: AppState,
+    config: &ServerConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    let in_flight = InFlightRequests::default();
+    let app = create_router(state).layer(
+        ServiceBuilder::new()
+            .layer(middleware::from_fn_with_state(
+                in_flight.clone(),
+                track_in_flight,
+            ))
+            .layer(HandleErrorLayer::new(handle_timeout_error))
+            .layer(TimeoutLayer::new(config.request_timeout())),
+    );
+
+    let (draining_tx, draining_rx) = oneshot::channel();
+    let server = axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            let _ = draining_tx.send(());
+        })
+        .into_future();
+    tokio::pin!(server);
+
+    tokio::select! {
+        result = &mut server => return Ok(result?),
+        _ = draining_rx => {}
+    }
+
+    let deadline = config.shutdown_timeout();
+    let finished_before = in_flight.finished();
+    info!(
+        "Stopped accepting connections, draining {} in-flight request(s) within {}s",
+        in_flight.running(),
+        deadline.as_secs()
+    );
+
+    // Connections that were already open may still deliver requests they had sent,
+    // so the drained count covers everything finished after shutdown started
+    let result = tokio::time::timeout(deadline, server).await;
+    let drained = in_flight.finished() - finished_before;
+    match result {
+        Ok(result) => {
+            result?;
+            info!("✓ Drained {} request(s)", drained);
+        }
+        Err(_) => warn!(
+            "Shutdown deadline passed after draining {} request(s), abandoning {} still running",
+            drained,
+            in_flight.running()
+        ),
+    }
 
     Ok(())
 }
+
+async fn handle_timeout_error(err: BoxError) -> ApiError {
+    if err.is::<Elapsed>() {
+        ApiError::new(
+            StatusCode::REQUEST_TIMEOUT,
+            "REQUEST_TIMEOUT",
+            "The request took too long to process",
+        )
+    } else {
+        error!("Unhandled middleware error: {}", err);
+        ApiError::internal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::Mediator;
+    use crate::infrastructure::memory::{
+        InMemoryAccountBalanceRepository, InMemoryAccountRepository, InMemoryIdempotencyRepository,
+        InMemoryJournalEntryRepository, InMemoryLedgerEventRepository, InMemoryOutboxRepository,
+        InMemoryStorageHealth, InMemoryStore, InMemoryTransferRepository,
+        InMemoryWebhookRepository,
+    };
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn state() -> AppState {
+        let store = InMemoryStore::new();
+        let mediator = Mediator::new(
+            Arc::new(InMemoryAccountRepository::new(store.clone())),
+            Arc::new(InMemoryLedgerEventRepository::new(store.clone())),
+            Arc::new(InMemoryAccountBalanceRepository::new(store.clone())),
+            Arc::new(InMemoryTransferRepository::new(store.clone())),
+            Arc::new(InMemoryJournalEntryRepository::new(store.clone())),
+            Arc::new(InMemoryOutboxRepository::new(store.clone())),
+            Arc::new(InMemoryWebhookRepository::new(store.clone())),
+        );
+        AppState::new(
+            mediator,
+            Arc::new(InMemoryIdempotencyRepository::new(store)),
+            Arc::new(InMemoryStorageHealth),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_serve_stops_on_shutdown() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            let shutdown = async {
+                let _ = shutdown_rx.await;
+            };
+            serve(listener, state(), &ServerConfig::default(), shutdown).await
+        });
+
+        let url = format!("http://{}/health/live", addr);
+        let response = reqwest::get(&url).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+        shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server did not stop")
+            .unwrap()
+            .unwrap();
+
+        assert!(reqwest::get(&url).await.is_err());
+    }
+}
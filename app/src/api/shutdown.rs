@@ -0,0 +1,85 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::info;
+
+/// Completes once the process receives SIGINT (Ctrl+C) or, on Unix, SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install the SIGINT handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install the SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received SIGINT, shutting down"),
+        _ = terminate => info!("Received SIGTERM, shutting down"),
+    }
+}
+
+/// Counts requests being handled and finished, reported while draining
+#[derive(Clone, Default)]
+pub struct InFlightRequests {
+    counters: Arc<Counters>,
+}
+
+#[derive(Default)]
+struct Counters {
+    running: AtomicUsize,
+    finished: AtomicUsize,
+}
+
+impl InFlightRequests {
+    /// Requests currently being handled
+    pub fn running(&self) -> usize {
+        self.counters.running.load(Ordering::SeqCst)
+    }
+
+    /// Requests handled since the server started
+    pub fn finished(&self) -> usize {
+        self.counters.finished.load(Ordering::SeqCst)
+    }
+
+    fn enter(&self) -> InFlightGuard {
+        self.counters.running.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            counters: self.counters.clone(),
+        }
+    }
+}
+
+/// Releases the request when dropped, so requests cancelled mid-way are counted too
+struct InFlightGuard {
+    counters: Arc<Counters>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.counters.running.fetch_sub(1, Ordering::SeqCst);
+        self.counters.finished.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+pub async fn track_in_flight(
+    State(in_flight): State<InFlightRequests>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let _guard = in_flight.enter();
+    next.run(request).await
+}
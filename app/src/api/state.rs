@@ -1,15 +1,60 @@
+use crate::api::auth::Authenticator;
+use crate::api::idempotency::DEFAULT_IDEMPOTENCY_TTL_SECONDS;
 use crate::application::Mediator;
+use crate::domain::IdempotencyRepository;
+use crate::infrastructure::StorageHealth;
+use chrono::Duration;
+use metrics_exporter_prometheus::PrometheusHandle;
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub mediator: Arc<Mediator>,
+    pub idempotency_repository: Arc<dyn IdempotencyRepository>,
+    pub idempotency_ttl: Duration,
+    /// Whether `Idempotency-Key` headers are honoured on write requests
+    pub idempotency_enabled: bool,
+    pub storage_health: Arc<dyn StorageHealth>,
+    /// Renders `/metrics`; the endpoint answers 404 when None
+    pub metrics: Option<PrometheusHandle>,
+    /// Required credentials; every caller is trusted when None
+    pub authenticator: Option<Arc<Authenticator>>,
 }
 
 impl AppState {
-    pub fn new(mediator: Mediator) -> Self {
+    pub fn new(
+        mediator: Mediator,
+        idempotency_repository: Arc<dyn IdempotencyRepository>,
+        storage_health: Arc<dyn StorageHealth>,
+    ) -> Self {
         Self {
             mediator: Arc::new(mediator),
+            idempotency_repository,
+            idempotency_ttl: Duration::seconds(DEFAULT_IDEMPOTENCY_TTL_SECONDS),
+            idempotency_enabled: true,
+            storage_health,
+            metrics: None,
+            authenticator: None,
         }
     }
+
+    pub fn with_idempotency_ttl(mut self, idempotency_ttl: Duration) -> Self {
+        self.idempotency_ttl = idempotency_ttl;
+        self
+    }
+
+    pub fn with_idempotency_enabled(mut self, idempotency_enabled: bool) -> Self {
+        self.idempotency_enabled = idempotency_enabled;
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: PrometheusHandle) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn with_authenticator(mut self, authenticator: Authenticator) -> Self {
+        self.authenticator = Some(Arc::new(authenticator));
+        self
+    }
 }
@@ -0,0 +1,33 @@
+use crate::domain::AccountStatus;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeAccountStatusCommand {
+    pub account_id: i32,
+    pub to_status: AccountStatus,
+    pub actor: String,
+    pub reason: String,
+}
+
+impl ChangeAccountStatusCommand {
+    pub fn new(account_id: i32, to_status: AccountStatus, actor: String, reason: String) -> Self {
+        Self {
+            account_id,
+            to_status,
+            actor,
+            reason,
+        }
+    }
+
+    pub fn freeze(account_id: i32, actor: String, reason: String) -> Self {
+        Self::new(account_id, AccountStatus::Frozen, actor, reason)
+    }
+
+    pub fn unfreeze(account_id: i32, actor: String, reason: String) -> Self {
+        Self::new(account_id, AccountStatus::Active, actor, reason)
+    }
+
+    pub fn close(account_id: i32, actor: String, reason: String) -> Self {
+        Self::new(account_id, AccountStatus::Closed, actor, reason)
+    }
+}
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 pub struct CreateAccountCommand {
     pub account_number: String,
     pub account_name: String,
+    pub currency: Option<String>, // ISO 4217 code, USD when omitted
+    pub balance_policy: Option<String>, // "NO_OVERDRAFT", "OVERDRAFT" or "UNRESTRICTED"
+    pub overdraft_limit: Option<i64>,
 }
 
 impl CreateAccountCommand {
@@ -11,6 +14,24 @@ impl CreateAccountCommand {
         Self {
             account_number,
             account_name,
+            currency: None,
+            balance_policy: None,
+            overdraft_limit: None,
         }
     }
+
+    pub fn with_currency(mut self, currency: String) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    pub fn with_balance_policy(
+        mut self,
+        balance_policy: String,
+        overdraft_limit: Option<i64>,
+    ) -> Self {
+        self.balance_policy = Some(balance_policy);
+        self.overdraft_limit = overdraft_limit;
+        self
+    }
 }
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntryLegCommand {
+    pub account_id: i32,
+    pub event_type: String, // "DEBIT" or "CREDIT"
+    pub amount: i64,
+    pub description: Option<String>, // Defaults to the entry's description
+}
+
+impl JournalEntryLegCommand {
+    pub fn debit(account_id: i32, amount: i64) -> Self {
+        Self {
+            account_id,
+            event_type: "DEBIT".to_string(),
+            amount,
+            description: None,
+        }
+    }
+
+    pub fn credit(account_id: i32, amount: i64) -> Self {
+        Self {
+            account_id,
+            event_type: "CREDIT".to_string(),
+            amount,
+            description: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateJournalEntryCommand {
+    pub description: Option<String>,
+    pub currency: Option<String>, // Must match every leg's account, defaults to the first one's
+    pub legs: Vec<JournalEntryLegCommand>,
+    pub recorded_by: Option<String>, // Authenticated client, stored on the events
+}
+
+impl CreateJournalEntryCommand {
+    pub fn new(description: Option<String>, legs: Vec<JournalEntryLegCommand>) -> Self {
+        Self {
+            description,
+            currency: None,
+            legs,
+            recorded_by: None,
+        }
+    }
+
+    pub fn with_currency(mut self, currency: String) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    pub fn with_recorded_by(mut self, recorded_by: String) -> Self {
+        self.recorded_by = Some(recorded_by);
+        self
+    }
+}
@@ -5,7 +5,12 @@ pub struct CreateLedgerEventCommand {
     pub account_id: i32,
     pub event_type: String, // "DEBIT" or "CREDIT"
     pub amount: i64,
+    pub currency: Option<String>, // Must match the account's currency, defaults to it
     pub description: Option<String>,
+    pub recorded_by: Option<String>, // Authenticated client, stored on the events
+    /// Version the caller last saw for the account; the event is rejected with a
+    /// conflict if another event was appended since
+    pub expected_version: Option<i64>,
 }
 
 impl CreateLedgerEventCommand {
@@ -19,10 +24,28 @@ impl CreateLedgerEventCommand {
             account_id,
             event_type,
             amount,
+            currency: None,
             description,
+            recorded_by: None,
+            expected_version: None,
         }
     }
 
+    pub fn with_currency(mut self, currency: String) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    pub fn with_recorded_by(mut self, recorded_by: String) -> Self {
+        self.recorded_by = Some(recorded_by);
+        self
+    }
+
+    pub fn with_expected_version(mut self, expected_version: i64) -> Self {
+        self.expected_version = Some(expected_version);
+        self
+    }
+
     pub fn new_debit(account_id: i32, amount: i64, description: Option<String>) -> Self {
         Self::new(account_id, "DEBIT".to_string(), amount, description)
     }
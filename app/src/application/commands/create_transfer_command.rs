@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTransferCommand {
+    pub from_account_id: i32,
+    pub to_account_id: i32,
+    pub amount: i64,
+    pub currency: Option<String>, // Must match both accounts' currency, defaults to it
+    pub description: Option<String>,
+    pub recorded_by: Option<String>, // Authenticated client, stored on the events
+}
+
+impl CreateTransferCommand {
+    pub fn new(
+        from_account_id: i32,
+        to_account_id: i32,
+        amount: i64,
+        description: Option<String>,
+    ) -> Self {
+        Self {
+            from_account_id,
+            to_account_id,
+            amount,
+            currency: None,
+            description,
+            recorded_by: None,
+        }
+    }
+
+    pub fn with_currency(mut self, currency: String) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    pub fn with_recorded_by(mut self, recorded_by: String) -> Self {
+        self.recorded_by = Some(recorded_by);
+        self
+    }
+}
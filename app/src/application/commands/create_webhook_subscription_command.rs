@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookSubscriptionCommand {
+    pub url: String,
+    pub event_types: Vec<String>, // e.g. "ledger_event.created", "account.created"
+    pub account_id: Option<i32>,  // Only events of this account when set
+    pub balance_threshold: Option<i64>, // Required for "balance.below_threshold"
+}
+
+impl CreateWebhookSubscriptionCommand {
+    pub fn new(url: String, event_types: Vec<String>) -> Self {
+        Self {
+            url,
+            event_types,
+            account_id: None,
+            balance_threshold: None,
+        }
+    }
+
+    pub fn with_account(mut self, account_id: Option<i32>) -> Self {
+        self.account_id = account_id;
+        self
+    }
+
+    pub fn with_balance_threshold(mut self, balance_threshold: Option<i64>) -> Self {
+        self.balance_threshold = balance_threshold;
+        self
+    }
+}
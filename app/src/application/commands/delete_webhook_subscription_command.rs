@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteWebhookSubscriptionCommand {
+    pub subscription_id: i32,
+}
+
+impl DeleteWebhookSubscriptionCommand {
+    pub fn new(subscription_id: i32) -> Self {
+        Self { subscription_id }
+    }
+}
@@ -1,9 +1,25 @@
+pub mod change_account_status_command;
 pub mod create_account_command;
 pub mod create_balance_snapshot_command;
+pub mod create_journal_entry_command;
 pub mod create_ledger_event_command;
-pub mod create_transaction_command;
+pub mod create_transfer_command;
+pub mod create_webhook_subscription_command;
+pub mod delete_webhook_subscription_command;
+pub mod prune_balance_snapshots_command;
+pub mod replay_webhook_delivery_command;
+pub mod reverse_ledger_event_command;
+pub mod snapshot_accounts_command;
 
+pub use change_account_status_command::ChangeAccountStatusCommand;
 pub use create_account_command::CreateAccountCommand;
 pub use create_balance_snapshot_command::CreateBalanceSnapshotCommand;
+pub use create_journal_entry_command::{CreateJournalEntryCommand, JournalEntryLegCommand};
 pub use create_ledger_event_command::CreateLedgerEventCommand;
-pub use create_transaction_command::CreateTransactionCommand;
+pub use create_transfer_command::CreateTransferCommand;
+pub use create_webhook_subscription_command::CreateWebhookSubscriptionCommand;
+pub use delete_webhook_subscription_command::DeleteWebhookSubscriptionCommand;
+pub use prune_balance_snapshots_command::PruneBalanceSnapshotsCommand;
+pub use replay_webhook_delivery_command::ReplayWebhookDeliveryCommand;
+pub use reverse_ledger_event_command::ReverseLedgerEventCommand;
+pub use snapshot_accounts_command::SnapshotAccountsCommand;
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Deletes snapshots older than `retain_days`, except the last snapshot of each
+/// calendar month and each account's latest snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneBalanceSnapshotsCommand {
+    pub account_id: Option<i32>, // Every account when None
+    pub retain_days: i64,
+}
+
+impl PruneBalanceSnapshotsCommand {
+    pub fn new(retain_days: i64) -> Self {
+        Self {
+            account_id: None,
+            retain_days,
+        }
+    }
+
+    pub fn for_account(account_id: i32, retain_days: i64) -> Self {
+        Self {
+            account_id: Some(account_id),
+            retain_days,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayWebhookDeliveryCommand {
+    pub delivery_id: i32,
+}
+
+impl ReplayWebhookDeliveryCommand {
+    pub fn new(delivery_id: i32) -> Self {
+        Self { delivery_id }
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReverseLedgerEventCommand {
+    pub event_id: i32,
+    pub description: Option<String>, // Defaults to "Reversal of event <id>"
+    pub recorded_by: Option<String>, // Authenticated client, stored on the events
+}
+
+impl ReverseLedgerEventCommand {
+    pub fn new(event_id: i32, description: Option<String>) -> Self {
+        Self {
+            event_id,
+            description,
+            recorded_by: None,
+        }
+    }
+
+    pub fn with_recorded_by(mut self, recorded_by: String) -> Self {
+        self.recorded_by = Some(recorded_by);
+        self
+    }
+}
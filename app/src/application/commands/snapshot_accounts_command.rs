@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Snapshots every account whose latest snapshot is behind. An account is due once
+/// `event_threshold` events were appended after its snapshot, or when it has any new
+/// event and its snapshot is older than `max_age_seconds` (or it has none).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotAccountsCommand {
+    pub event_threshold: i64,
+    pub max_age_seconds: Option<i64>,
+}
+
+impl SnapshotAccountsCommand {
+    /// Every account with at least one event after its latest snapshot
+    pub fn all() -> Self {
+        Self {
+            event_threshold: 1,
+            max_age_seconds: None,
+        }
+    }
+
+    pub fn due(event_threshold: i64, max_age_seconds: i64) -> Self {
+        Self {
+            event_threshold,
+            max_age_seconds: Some(max_age_seconds),
+        }
+    }
+}
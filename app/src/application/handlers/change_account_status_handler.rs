@@ -0,0 +1,145 @@
+use crate::application::commands::ChangeAccountStatusCommand;
+use crate::application::metrics;
+use crate::domain::{AccountRepository, AccountStatusChange, DomainResult};
+use std::sync::Arc;
+use tracing::info;
+
+/// Freezes, unfreezes or closes an account and records who did it and why
+pub struct ChangeAccountStatusHandler {
+    account_repository: Arc<dyn AccountRepository>,
+}
+
+impl ChangeAccountStatusHandler {
+    pub fn new(account_repository: Arc<dyn AccountRepository>) -> Self {
+        Self { account_repository }
+    }
+
+    pub async fn handle(
+        &self,
+        command: ChangeAccountStatusCommand,
+    ) -> DomainResult<AccountStatusChange> {
+        info!(
+            "Changing status of account {} to {} by {}",
+            command.account_id, command.to_status, command.actor
+        );
+
+        let account = self
+            .account_repository
+            .find_by_id(command.account_id)
+            .await?;
+
+        let change = AccountStatusChange::new(
+            command.account_id,
+            account.status,
+            command.to_status,
+            command.actor,
+            command.reason,
+        );
+
+        // Rejects invalid transitions early; the repository re-checks them and the
+        // zero balance required for closing under the account's row lock
+        change.validate()?;
+
+        let saved_change = self.account_repository.change_status(&change).await?;
+        metrics::record_account_status_changed(saved_change.to_status);
+
+        Ok(saved_change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Account;
+    use crate::domain::errors::DomainError;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::value_objects::{AccountNumber, AccountStatus, Currency};
+
+    fn account_repo(status: AccountStatus) -> MockAccountRepository {
+        let mut mock_repo = MockAccountRepository::new();
+        mock_repo.expect_find_by_id().returning(move |id| {
+            let mut account = Account::new(
+                AccountNumber::new("ACC001".to_string()).unwrap(),
+                "Test Account".to_string(),
+                Currency::USD,
+            );
+            account.id = Some(id);
+            account.status = status;
+            Ok(account)
+        });
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_freeze_active_account() {
+        let mut mock_repo = account_repo(AccountStatus::Active);
+        mock_repo.expect_change_status().once().returning(|change| {
+            let mut saved = change.clone();
+            saved.id = Some(1);
+            Ok(saved)
+        });
+
+        let handler = ChangeAccountStatusHandler::new(Arc::new(mock_repo));
+        let command = ChangeAccountStatusCommand::freeze(
+            1,
+            "ops@example.com".to_string(),
+            "Chargeback investigation".to_string(),
+        );
+
+        let change = handler.handle(command).await.unwrap();
+        assert_eq!(change.id, Some(1));
+        assert_eq!(change.from_status, AccountStatus::Active);
+        assert_eq!(change.to_status, AccountStatus::Frozen);
+        assert_eq!(change.actor, "ops@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_unfreeze_active_account_is_rejected() {
+        let mut mock_repo = account_repo(AccountStatus::Active);
+        mock_repo.expect_change_status().times(0);
+
+        let handler = ChangeAccountStatusHandler::new(Arc::new(mock_repo));
+        let command =
+            ChangeAccountStatusCommand::unfreeze(1, "ops".to_string(), "Cleared".to_string());
+
+        let result = handler.handle(command).await;
+        assert!(matches!(
+            result,
+            Err(DomainError::InvalidStatusTransition { from, to, .. })
+                if from == "ACTIVE" && to == "ACTIVE"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_close_requires_reason() {
+        let mut mock_repo = account_repo(AccountStatus::Frozen);
+        mock_repo.expect_change_status().times(0);
+
+        let handler = ChangeAccountStatusHandler::new(Arc::new(mock_repo));
+        let command = ChangeAccountStatusCommand::close(1, "ops".to_string(), "".to_string());
+
+        let result = handler.handle(command).await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_close_with_balance_is_rejected() {
+        let mut mock_repo = account_repo(AccountStatus::Active);
+        mock_repo.expect_change_status().once().returning(|change| {
+            Err(DomainError::BalanceNotZero {
+                account_id: change.account_id,
+                balance: 250,
+            })
+        });
+
+        let handler = ChangeAccountStatusHandler::new(Arc::new(mock_repo));
+        let command =
+            ChangeAccountStatusCommand::close(1, "ops".to_string(), "Customer request".to_string());
+
+        let result = handler.handle(command).await;
+        assert!(matches!(
+            result,
+            Err(DomainError::BalanceNotZero { balance: 250, .. })
+        ));
+    }
+}
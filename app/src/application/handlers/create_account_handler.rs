@@ -1,5 +1,8 @@
 use crate::application::commands::CreateAccountCommand;
-use crate::domain::{Account, AccountNumber, AccountRepository, DomainResult};
+use crate::application::metrics;
+use crate::domain::{
+    Account, AccountNumber, AccountRepository, BalancePolicy, Currency, DomainResult,
+};
 use std::sync::Arc;
 use tracing::{error, info};
 
@@ -33,14 +36,27 @@ impl CreateAccountHandler {
             ));
         }
 
+        let currency = match command.currency.as_deref() {
+            Some(code) => Currency::from_code(code)?,
+            None => Currency::USD,
+        };
+
+        let balance_policy = BalancePolicy::from_parts(
+            command.balance_policy.as_deref().unwrap_or("NO_OVERDRAFT"),
+            command.overdraft_limit,
+            currency,
+        )?;
+
         // Create account entity (no balance - will be calculated from events)
-        let account = Account::new(account_number, command.account_name);
+        let account = Account::new(account_number, command.account_name, currency)
+            .with_balance_policy(balance_policy);
 
         // Validate account
         account.validate()?;
 
         // Save account
         let saved_account = self.account_repository.save(&account).await?;
+        metrics::record_account_created();
 
         info!("Account created successfully: id={:?}", saved_account.id);
         Ok(saved_account)
@@ -50,6 +66,7 @@ impl CreateAccountHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::{AccountFilter, AccountStatusChange, Page, PageRequest};
     use mockall::mock;
     use mockall::predicate::*;
 
@@ -62,7 +79,10 @@ mod tests {
             async fn find_by_id(&self, id: i32) -> DomainResult<Account>;
             async fn find_by_account_number(&self, account_number: &AccountNumber) -> DomainResult<Account>;
             async fn find_all(&self) -> DomainResult<Vec<Account>>;
+            async fn find_page(&self, filter: &AccountFilter, page: &PageRequest) -> DomainResult<Page<Account>>;
             async fn update(&self, account: &Account) -> DomainResult<Account>;
+            async fn change_status(&self, change: &AccountStatusChange) -> DomainResult<AccountStatusChange>;
+            async fn find_status_changes(&self, account_id: i32) -> DomainResult<Vec<AccountStatusChange>>;
             async fn exists_by_account_number(&self, account_number: &AccountNumber) -> DomainResult<bool>;
         }
     }
@@ -77,7 +97,8 @@ mod tests {
             .returning(|_| Ok(false));
 
         let account_number = AccountNumber::new("ACC001".to_string()).unwrap();
-        let mut expected_account = Account::new(account_number, "Test Account".to_string());
+        let mut expected_account =
+            Account::new(account_number, "Test Account".to_string(), Currency::USD);
         expected_account.id = Some(1);
 
         let expected_account_clone = expected_account.clone();
@@ -96,6 +117,72 @@ mod tests {
         assert_eq!(account.id, Some(1));
     }
 
+    #[tokio::test]
+    async fn test_create_account_with_overdraft_policy() {
+        let mut mock_repo = MockAccountRepo::new();
+
+        mock_repo
+            .expect_exists_by_account_number()
+            .times(1)
+            .returning(|_| Ok(false));
+
+        mock_repo.expect_save().times(1).returning(|account| {
+            let mut saved = account.clone();
+            saved.id = Some(1);
+            Ok(saved)
+        });
+
+        let handler = CreateAccountHandler::new(Arc::new(mock_repo));
+        let command = CreateAccountCommand::new("ACC001".to_string(), "Test Account".to_string())
+            .with_balance_policy("OVERDRAFT".to_string(), Some(500));
+
+        let account = handler.handle(command).await.unwrap();
+
+        assert_eq!(account.balance_policy.as_str(), "OVERDRAFT");
+        assert_eq!(account.balance_policy.overdraft_limit(), Some(500));
+    }
+
+    #[tokio::test]
+    async fn test_create_account_with_currency() {
+        let mut mock_repo = MockAccountRepo::new();
+
+        mock_repo
+            .expect_exists_by_account_number()
+            .times(1)
+            .returning(|_| Ok(false));
+
+        mock_repo.expect_save().times(1).returning(|account| {
+            let mut saved = account.clone();
+            saved.id = Some(1);
+            Ok(saved)
+        });
+
+        let handler = CreateAccountHandler::new(Arc::new(mock_repo));
+        let command = CreateAccountCommand::new("ACC001".to_string(), "Test Account".to_string())
+            .with_currency("eur".to_string());
+
+        let account = handler.handle(command).await.unwrap();
+
+        assert_eq!(account.currency, Currency::EUR);
+    }
+
+    #[tokio::test]
+    async fn test_create_account_unsupported_currency() {
+        let mut mock_repo = MockAccountRepo::new();
+
+        mock_repo
+            .expect_exists_by_account_number()
+            .times(1)
+            .returning(|_| Ok(false));
+        mock_repo.expect_save().times(0);
+
+        let handler = CreateAccountHandler::new(Arc::new(mock_repo));
+        let command = CreateAccountCommand::new("ACC001".to_string(), "Test Account".to_string())
+            .with_currency("ABC".to_string());
+
+        assert!(handler.handle(command).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_create_account_duplicate() {
         let mut mock_repo = MockAccountRepo::new();
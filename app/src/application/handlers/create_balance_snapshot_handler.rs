@@ -1,22 +1,27 @@
 use crate::application::commands::CreateBalanceSnapshotCommand;
+use crate::application::metrics;
 use crate::domain::entities::AccountBalance;
 use crate::domain::errors::DomainResult;
-use crate::domain::repositories::{AccountBalanceRepository, LedgerEventRepository};
-use crate::domain::value_objects::Money;
+use crate::domain::repositories::{
+    AccountBalanceRepository, AccountRepository, LedgerEventRepository,
+};
 use std::sync::Arc;
 use tracing::info;
 
 pub struct CreateBalanceSnapshotHandler {
+    account_repository: Arc<dyn AccountRepository>,
     event_repository: Arc<dyn LedgerEventRepository>,
     balance_repository: Arc<dyn AccountBalanceRepository>,
 }
 
 impl CreateBalanceSnapshotHandler {
     pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
         event_repository: Arc<dyn LedgerEventRepository>,
         balance_repository: Arc<dyn AccountBalanceRepository>,
     ) -> Self {
         Self {
+            account_repository,
             event_repository,
             balance_repository,
         }
@@ -31,22 +36,33 @@ impl CreateBalanceSnapshotHandler {
             command.account_id
         );
 
-        // Calculate current balance from events
-        let balance_value = self
-            .event_repository
-            .calculate_balance(command.account_id)
+        let account = self
+            .account_repository
+            .find_by_id(command.account_id)
             .await?;
 
-        let balance = Money::new(balance_value)?;
+        // Roll the latest snapshot forward with the events appended after it
+        let base = self
+            .balance_repository
+            .find_latest_by_account_id(command.account_id)
+            .await?
+            .unwrap_or_else(|| AccountBalance::initial(command.account_id, account.currency));
+
+        let delta = self
+            .event_repository
+            .calculate_balance_since(command.account_id, base.last_event_id.unwrap_or(0))
+            .await?;
 
-        let snapshot = AccountBalance::new(command.account_id, balance);
+        let snapshot = base.apply(&delta)?;
 
         let saved_snapshot = self.balance_repository.save(&snapshot).await?;
+        metrics::record_snapshots_created(1);
 
         info!(
-            "Balance snapshot created successfully: id={:?}, balance={}",
+            "Balance snapshot created successfully: id={:?}, balance={}, last_event_id={:?}",
             saved_snapshot.id,
-            saved_snapshot.balance.value()
+            saved_snapshot.balance.value(),
+            saved_snapshot.last_event_id
         );
 
         Ok(saved_snapshot)
@@ -56,20 +72,43 @@ impl CreateBalanceSnapshotHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::Account;
     use crate::domain::repositories::account_balance_repository::MockAccountBalanceRepository;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
     use crate::domain::repositories::ledger_event_repository::MockLedgerEventRepository;
+    use crate::domain::value_objects::{AccountNumber, BalanceDelta, Currency};
     use mockall::predicate::*;
 
+    fn account_repo(currency: Currency) -> Arc<MockAccountRepository> {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(move |id| {
+            let mut account = Account::new(
+                AccountNumber::new("ACC001".to_string()).unwrap(),
+                "Test Account".to_string(),
+                currency,
+            );
+            account.id = Some(id);
+            Ok(account)
+        });
+        Arc::new(mock_account_repo)
+    }
+
     #[tokio::test]
     async fn test_create_balance_snapshot() {
         let mut mock_event_repo = MockLedgerEventRepository::new();
         let mut mock_balance_repo = MockAccountBalanceRepository::new();
 
-        mock_event_repo
-            .expect_calculate_balance()
+        mock_balance_repo
+            .expect_find_latest_by_account_id()
             .with(eq(1))
             .once()
-            .returning(|_| Ok(5000));
+            .returning(|_| Ok(None));
+
+        mock_event_repo
+            .expect_calculate_balance_since()
+            .with(eq(1), eq(0))
+            .once()
+            .returning(|_, _| Ok(BalanceDelta::new(5000, Some(4), 4)));
 
         mock_balance_repo
             .expect_save()
@@ -81,6 +120,7 @@ mod tests {
             });
 
         let handler = CreateBalanceSnapshotHandler::new(
+            account_repo(Currency::USD),
             Arc::new(mock_event_repo),
             Arc::new(mock_balance_repo),
         );
@@ -92,5 +132,38 @@ mod tests {
         let snapshot = result.unwrap();
         assert_eq!(snapshot.id, Some(1));
         assert_eq!(snapshot.balance.value(), 5000);
+        assert_eq!(snapshot.last_event_id, Some(4));
+    }
+
+    #[tokio::test]
+    async fn test_create_negative_balance_snapshot() {
+        let mut mock_event_repo = MockLedgerEventRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        mock_balance_repo
+            .expect_find_latest_by_account_id()
+            .returning(|_| Ok(None));
+
+        mock_event_repo
+            .expect_calculate_balance_since()
+            .returning(|_, _| Ok(BalanceDelta::new(-2500, Some(3), 2)));
+
+        mock_balance_repo
+            .expect_save()
+            .once()
+            .returning(|snapshot| Ok(snapshot.clone()));
+
+        let handler = CreateBalanceSnapshotHandler::new(
+            account_repo(Currency::EUR),
+            Arc::new(mock_event_repo),
+            Arc::new(mock_balance_repo),
+        );
+
+        let snapshot = handler
+            .handle(CreateBalanceSnapshotCommand::new(1))
+            .await
+            .unwrap();
+        assert_eq!(snapshot.balance.value(), -2500);
+        assert_eq!(snapshot.currency, Currency::EUR);
     }
 }
@@ -0,0 +1,188 @@
+use crate::application::commands::CreateJournalEntryCommand;
+use crate::application::metrics;
+use crate::domain::entities::{Account, EventType, JournalEntry, LedgerEvent};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{AccountRepository, JournalEntryRepository};
+use crate::domain::value_objects::{Currency, Money};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
+
+pub struct CreateJournalEntryHandler {
+    account_repository: Arc<dyn AccountRepository>,
+    journal_entry_repository: Arc<dyn JournalEntryRepository>,
+}
+
+impl CreateJournalEntryHandler {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        journal_entry_repository: Arc<dyn JournalEntryRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            journal_entry_repository,
+        }
+    }
+
+    pub async fn handle(&self, command: CreateJournalEntryCommand) -> DomainResult<JournalEntry> {
+        info!("Creating journal entry with {} legs", command.legs.len());
+
+        let first_leg = command.legs.first().ok_or_else(|| {
+            DomainError::ValidationError("A journal entry needs at least two legs".to_string())
+        })?;
+
+        // Every account is resolved up front so unknown, frozen and closed accounts
+        // are reported as such; the repository re-checks them under the account locks
+        let mut accounts: HashMap<i32, Account> = HashMap::new();
+        for leg in &command.legs {
+            if let Entry::Vacant(slot) = accounts.entry(leg.account_id) {
+                let account = self.account_repository.find_by_id(leg.account_id).await?;
+                account.ensure_accepts_events()?;
+                slot.insert(account);
+            }
+        }
+
+        // All legs are booked in one currency, which every account must hold
+        let currency = match command.currency.as_deref() {
+            Some(code) => Currency::from_code(code)?,
+            None => accounts[&first_leg.account_id].currency,
+        };
+
+        let mut legs = Vec::with_capacity(command.legs.len());
+        for leg in command.legs {
+            let amount = Money::new(leg.amount, currency)?;
+            amount.ensure_currency(accounts[&leg.account_id].currency)?;
+
+            legs.push(
+                LedgerEvent::new(
+                    leg.account_id,
+                    EventType::from_string(&leg.event_type)?,
+                    amount,
+                    leg.description.or_else(|| command.description.clone()),
+                )
+                .with_recorded_by(command.recorded_by.clone()),
+            );
+        }
+
+        let entry = JournalEntry::new(command.description, legs);
+
+        entry.validate()?;
+
+        let saved_entry = self.journal_entry_repository.save(&entry).await?;
+        metrics::record_journal_entry();
+        metrics::record_events_appended(&saved_entry.legs);
+
+        info!(
+            "Journal entry created successfully: id={:?}",
+            saved_entry.id
+        );
+
+        Ok(saved_entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::commands::JournalEntryLegCommand;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::repositories::journal_entry_repository::MockJournalEntryRepository;
+    use crate::domain::value_objects::AccountNumber;
+
+    /// Account 9 holds EUR, every other account holds USD
+    fn account_repo() -> MockAccountRepository {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(|id| {
+            let currency = if id == 9 {
+                Currency::EUR
+            } else {
+                Currency::USD
+            };
+            let mut account = Account::new(
+                AccountNumber::new(format!("ACC00{}", id)).unwrap(),
+                "Test Account".to_string(),
+                currency,
+            );
+            account.id = Some(id);
+            Ok(account)
+        });
+        mock_account_repo
+    }
+
+    #[tokio::test]
+    async fn test_create_balanced_entry() {
+        let mut mock_repo = MockJournalEntryRepository::new();
+        mock_repo.expect_save().once().returning(|entry| {
+            let mut saved = entry.clone();
+            saved.id = Some(1);
+            saved.legs = saved.linked_legs();
+            Ok(saved)
+        });
+
+        let handler = CreateJournalEntryHandler::new(Arc::new(account_repo()), Arc::new(mock_repo));
+        let command = CreateJournalEntryCommand::new(
+            Some("Card settlement".to_string()),
+            vec![
+                JournalEntryLegCommand::debit(1, 1000),
+                JournalEntryLegCommand::credit(2, 970),
+                JournalEntryLegCommand::credit(3, 30),
+            ],
+        );
+
+        let entry = handler.handle(command).await.unwrap();
+        assert_eq!(entry.id, Some(1));
+        assert_eq!(entry.legs.len(), 3);
+        assert!(entry.legs.iter().all(|leg| leg.journal_entry_id == Some(1)));
+        assert_eq!(
+            entry.legs[0].description.as_deref(),
+            Some("Card settlement")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_unbalanced_entry() {
+        let mut mock_repo = MockJournalEntryRepository::new();
+        mock_repo.expect_save().times(0);
+
+        let handler = CreateJournalEntryHandler::new(Arc::new(account_repo()), Arc::new(mock_repo));
+        let command = CreateJournalEntryCommand::new(
+            None,
+            vec![
+                JournalEntryLegCommand::debit(1, 1000),
+                JournalEntryLegCommand::credit(2, 999),
+            ],
+        );
+
+        let result = handler.handle(command).await;
+        assert!(matches!(
+            result,
+            Err(DomainError::UnbalancedJournalEntry {
+                debits: 1000,
+                credits: 999
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_entry_across_currencies() {
+        let mut mock_repo = MockJournalEntryRepository::new();
+        mock_repo.expect_save().times(0);
+
+        let handler = CreateJournalEntryHandler::new(Arc::new(account_repo()), Arc::new(mock_repo));
+        let command = CreateJournalEntryCommand::new(
+            None,
+            vec![
+                JournalEntryLegCommand::debit(1, 1000),
+                JournalEntryLegCommand::credit(9, 1000),
+            ],
+        );
+
+        let result = handler.handle(command).await;
+        assert!(matches!(
+            result,
+            Err(DomainError::CurrencyMismatch { expected, actual })
+                if expected == "EUR" && actual == "USD"
+        ));
+    }
+}
@@ -1,18 +1,26 @@
 use crate::application::commands::CreateLedgerEventCommand;
+use crate::application::metrics;
 use crate::domain::entities::{EventType, LedgerEvent};
-use crate::domain::errors::DomainResult;
-use crate::domain::repositories::LedgerEventRepository;
-use crate::domain::value_objects::Money;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{AccountRepository, LedgerEventRepository};
+use crate::domain::value_objects::{Currency, Money};
 use std::sync::Arc;
 use tracing::info;
 
 pub struct CreateLedgerEventHandler {
+    account_repository: Arc<dyn AccountRepository>,
     event_repository: Arc<dyn LedgerEventRepository>,
 }
 
 impl CreateLedgerEventHandler {
-    pub fn new(event_repository: Arc<dyn LedgerEventRepository>) -> Self {
-        Self { event_repository }
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        event_repository: Arc<dyn LedgerEventRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            event_repository,
+        }
     }
 
     pub async fn handle(&self, command: CreateLedgerEventCommand) -> DomainResult<LedgerEvent> {
@@ -22,23 +30,47 @@ impl CreateLedgerEventHandler {
         );
 
         let event_type = EventType::from_string(&command.event_type)?;
-        let amount = Money::new(command.amount)?;
+        if let Some(expected_version) = command.expected_version {
+            if expected_version < 0 {
+                return Err(DomainError::ValidationError(format!(
+                    "expected_version cannot be negative, got {}",
+                    expected_version
+                )));
+            }
+        }
 
-        let event = LedgerEvent::new(
-            command.account_id,
-            event_type,
-            amount,
-            command.description,
-        );
+        // Resolve the account first so unknown, frozen and closed accounts are
+        // reported as such; the repository re-checks under the account lock
+        let account = self
+            .account_repository
+            .find_by_id(command.account_id)
+            .await?;
+        account.ensure_accepts_events()?;
+
+        // The event is booked in the account's currency unless one is given
+        let currency = match command.currency.as_deref() {
+            Some(code) => Currency::from_code(code)?,
+            None => account.currency,
+        };
+        let amount = Money::new(command.amount, currency)?;
+        amount.ensure_currency(account.currency)?;
+
+        let event = LedgerEvent::new(command.account_id, event_type, amount, command.description)
+            .with_recorded_by(command.recorded_by);
 
         event.validate()?;
 
-        let saved_event = self.event_repository.save(&event).await?;
+        let saved_event = match command.expected_version {
+            Some(expected_version) => {
+                self.event_repository
+                    .save_at_version(&event, expected_version)
+                    .await?
+            }
+            None => self.event_repository.save(&event).await?,
+        };
+        metrics::record_events_appended([&saved_event]);
 
-        info!(
-            "Ledger event created successfully: id={:?}",
-            saved_event.id
-        );
+        info!("Ledger event created successfully: id={:?}", saved_event.id);
 
         Ok(saved_event)
     }
@@ -47,22 +79,47 @@ impl CreateLedgerEventHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::Account;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
     use crate::domain::repositories::ledger_event_repository::MockLedgerEventRepository;
+    use crate::domain::value_objects::{AccountNumber, AccountStatus};
+
+    fn account_repo(currency: Currency) -> MockAccountRepository {
+        account_repo_with_status(currency, AccountStatus::Active)
+    }
+
+    fn account_repo_with_status(
+        currency: Currency,
+        status: AccountStatus,
+    ) -> MockAccountRepository {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(move |id| {
+            let mut account = Account::new(
+                AccountNumber::new("ACC001".to_string()).unwrap(),
+                "Test Account".to_string(),
+                currency,
+            );
+            account.id = Some(id);
+            account.status = status;
+            Ok(account)
+        });
+        mock_account_repo
+    }
 
     #[tokio::test]
     async fn test_create_debit_event() {
         let mut mock_repo = MockLedgerEventRepository::new();
 
-        mock_repo
-            .expect_save()
-            .once()
-            .returning(|event| {
-                let mut saved = event.clone();
-                saved.id = Some(1);
-                Ok(saved)
-            });
+        mock_repo.expect_save().once().returning(|event| {
+            let mut saved = event.clone();
+            saved.id = Some(1);
+            Ok(saved)
+        });
 
-        let handler = CreateLedgerEventHandler::new(Arc::new(mock_repo));
+        let handler = CreateLedgerEventHandler::new(
+            Arc::new(account_repo(Currency::EUR)),
+            Arc::new(mock_repo),
+        );
         let command = CreateLedgerEventCommand::new_debit(1, 1000, Some("Test debit".to_string()));
 
         let result = handler.handle(command).await;
@@ -71,5 +128,97 @@ mod tests {
         let event = result.unwrap();
         assert_eq!(event.id, Some(1));
         assert_eq!(event.amount.value(), 1000);
+        assert_eq!(event.amount.currency(), Currency::EUR);
+    }
+
+    #[tokio::test]
+    async fn test_create_event_at_expected_version() {
+        let mut mock_repo = MockLedgerEventRepository::new();
+        mock_repo.expect_save().times(0);
+        mock_repo
+            .expect_save_at_version()
+            .withf(|_, expected_version| *expected_version == 4)
+            .once()
+            .returning(|event, expected_version| {
+                Err(DomainError::VersionConflict {
+                    account_id: event.account_id,
+                    expected: expected_version,
+                    actual: 5,
+                })
+            });
+
+        let handler = CreateLedgerEventHandler::new(
+            Arc::new(account_repo(Currency::USD)),
+            Arc::new(mock_repo),
+        );
+        let command = CreateLedgerEventCommand::new_credit(1, 1000, None).with_expected_version(4);
+
+        let result = handler.handle(command).await;
+        assert!(matches!(
+            result,
+            Err(DomainError::VersionConflict {
+                account_id: 1,
+                expected: 4,
+                actual: 5
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_event_currency_mismatch() {
+        let mut mock_repo = MockLedgerEventRepository::new();
+        mock_repo.expect_save().times(0);
+
+        let handler = CreateLedgerEventHandler::new(
+            Arc::new(account_repo(Currency::USD)),
+            Arc::new(mock_repo),
+        );
+        let command =
+            CreateLedgerEventCommand::new_credit(1, 1000, None).with_currency("EUR".to_string());
+
+        let result = handler.handle(command).await;
+        assert!(matches!(
+            result,
+            Err(DomainError::CurrencyMismatch { expected, actual })
+                if expected == "USD" && actual == "EUR"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_event_unknown_account() {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(|id| {
+            Err(DomainError::AccountNotFound(format!(
+                "Account with id {} not found",
+                id
+            )))
+        });
+        let mut mock_repo = MockLedgerEventRepository::new();
+        mock_repo.expect_save().times(0);
+
+        let handler =
+            CreateLedgerEventHandler::new(Arc::new(mock_account_repo), Arc::new(mock_repo));
+        let command = CreateLedgerEventCommand::new_credit(99, 1000, None);
+
+        let result = handler.handle(command).await;
+        assert!(matches!(result, Err(DomainError::AccountNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_event_on_frozen_account() {
+        let mut mock_repo = MockLedgerEventRepository::new();
+        mock_repo.expect_save().times(0);
+
+        let handler = CreateLedgerEventHandler::new(
+            Arc::new(account_repo_with_status(
+                Currency::USD,
+                AccountStatus::Frozen,
+            )),
+            Arc::new(mock_repo),
+        );
+        let command = CreateLedgerEventCommand::new_credit(1, 1000, None);
+
+        let result = handler.handle(command).await;
+        assert!(matches!(result, Err(DomainError::AccountFrozen(1))));
     }
 }
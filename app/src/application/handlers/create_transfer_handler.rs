@@ -0,0 +1,156 @@
+use crate::application::commands::CreateTransferCommand;
+use crate::application::metrics;
+use crate::domain::entities::Transfer;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::{AccountRepository, TransferRepository};
+use crate::domain::value_objects::{Currency, Money};
+use std::sync::Arc;
+use tracing::info;
+
+pub struct CreateTransferHandler {
+    account_repository: Arc<dyn AccountRepository>,
+    transfer_repository: Arc<dyn TransferRepository>,
+}
+
+impl CreateTransferHandler {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        transfer_repository: Arc<dyn TransferRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            transfer_repository,
+        }
+    }
+
+    pub async fn handle(&self, command: CreateTransferCommand) -> DomainResult<Transfer> {
+        info!(
+            "Creating transfer: from_account_id={}, to_account_id={}, amount={}",
+            command.from_account_id, command.to_account_id, command.amount
+        );
+
+        let from_account = self
+            .account_repository
+            .find_by_id(command.from_account_id)
+            .await?;
+        let to_account = self
+            .account_repository
+            .find_by_id(command.to_account_id)
+            .await?;
+        from_account.ensure_accepts_events()?;
+        to_account.ensure_accepts_events()?;
+
+        // Both legs are booked in one currency, which both accounts must hold
+        let currency = match command.currency.as_deref() {
+            Some(code) => Currency::from_code(code)?,
+            None => from_account.currency,
+        };
+        let amount = Money::new(command.amount, currency)?;
+        amount.ensure_currency(from_account.currency)?;
+        amount.ensure_currency(to_account.currency)?;
+
+        let transfer = Transfer::new(
+            command.from_account_id,
+            command.to_account_id,
+            amount,
+            command.description,
+        )
+        .with_recorded_by(command.recorded_by);
+
+        transfer.validate()?;
+
+        let saved_transfer = self.transfer_repository.save(&transfer).await?;
+        metrics::record_transfer();
+        metrics::record_events_appended(
+            saved_transfer
+                .debit_event
+                .iter()
+                .chain(saved_transfer.credit_event.iter()),
+        );
+
+        info!("Transfer created successfully: id={:?}", saved_transfer.id);
+
+        Ok(saved_transfer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Account;
+    use crate::domain::errors::DomainError;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::repositories::transfer_repository::MockTransferRepository;
+    use crate::domain::value_objects::AccountNumber;
+
+    /// Account 1 holds USD, every other account holds `other_currency`
+    fn account_repo(other_currency: Currency) -> MockAccountRepository {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(move |id| {
+            let currency = if id == 1 {
+                Currency::USD
+            } else {
+                other_currency
+            };
+            let mut account = Account::new(
+                AccountNumber::new(format!("ACC00{}", id)).unwrap(),
+                "Test Account".to_string(),
+                currency,
+            );
+            account.id = Some(id);
+            Ok(account)
+        });
+        mock_account_repo
+    }
+
+    #[tokio::test]
+    async fn test_create_transfer() {
+        let mut mock_repo = MockTransferRepository::new();
+
+        mock_repo.expect_save().once().returning(|transfer| {
+            let mut saved = transfer.clone();
+            saved.id = Some(1);
+            saved.debit_event = Some(saved.debit_leg());
+            saved.credit_event = Some(saved.credit_leg());
+            Ok(saved)
+        });
+
+        let handler =
+            CreateTransferHandler::new(Arc::new(account_repo(Currency::USD)), Arc::new(mock_repo));
+        let command = CreateTransferCommand::new(1, 2, 1000, Some("Rent".to_string()));
+
+        let result = handler.handle(command).await;
+        assert!(result.is_ok());
+
+        let transfer = result.unwrap();
+        assert_eq!(transfer.id, Some(1));
+        assert_eq!(transfer.debit_event.unwrap().transfer_id, Some(1));
+        assert_eq!(transfer.credit_event.unwrap().transfer_id, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_create_transfer_same_account() {
+        let mut mock_repo = MockTransferRepository::new();
+        mock_repo.expect_save().never();
+
+        let handler =
+            CreateTransferHandler::new(Arc::new(account_repo(Currency::USD)), Arc::new(mock_repo));
+        let command = CreateTransferCommand::new(1, 1, 1000, None);
+
+        let result = handler.handle(command).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_transfer_between_currencies() {
+        let mut mock_repo = MockTransferRepository::new();
+        mock_repo.expect_save().never();
+
+        let handler =
+            CreateTransferHandler::new(Arc::new(account_repo(Currency::EUR)), Arc::new(mock_repo));
+        let command = CreateTransferCommand::new(1, 2, 1000, None);
+
+        let result = handler.handle(command).await;
+        assert!(matches!(result, Err(DomainError::CurrencyMismatch { .. })));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::application::commands::CreateWebhookSubscriptionCommand;
+use crate::domain::entities::WebhookSubscription;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::{AccountRepository, WebhookRepository};
+use std::sync::Arc;
+use tracing::info;
+
+/// Registers a webhook endpoint, generating the secret its requests are signed with
+pub struct CreateWebhookSubscriptionHandler {
+    account_repository: Arc<dyn AccountRepository>,
+    webhook_repository: Arc<dyn WebhookRepository>,
+}
+
+impl CreateWebhookSubscriptionHandler {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        webhook_repository: Arc<dyn WebhookRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            webhook_repository,
+        }
+    }
+
+    pub async fn handle(
+        &self,
+        command: CreateWebhookSubscriptionCommand,
+    ) -> DomainResult<WebhookSubscription> {
+        info!(
+            "Creating webhook subscription: url={}, event_types={:?}",
+            command.url, command.event_types
+        );
+
+        let mut event_types = command.event_types;
+        event_types.sort();
+        event_types.dedup();
+
+        let subscription = WebhookSubscription::new(command.url, event_types, generate_secret())
+            .with_account(command.account_id)
+            .with_balance_threshold(command.balance_threshold);
+        subscription.validate()?;
+
+        if let Some(account_id) = subscription.account_id {
+            self.account_repository.find_by_id(account_id).await?;
+        }
+
+        let saved = self
+            .webhook_repository
+            .save_subscription(&subscription)
+            .await?;
+
+        info!("Webhook subscription created: id={:?}", saved.id);
+        Ok(saved)
+    }
+}
+
+/// 32 random bytes, hex encoded behind a `whsec_` prefix
+fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::random();
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("whsec_{}", hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::errors::DomainError;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::repositories::webhook_repository::MockWebhookRepository;
+
+    #[tokio::test]
+    async fn test_create_subscription_generates_secret() {
+        let mut mock_webhook_repo = MockWebhookRepository::new();
+        mock_webhook_repo
+            .expect_save_subscription()
+            .withf(|subscription| {
+                subscription.event_types == vec!["account.created", "ledger_event.created"]
+                    && subscription.secret.len() == 70
+                    && subscription.secret.starts_with("whsec_")
+            })
+            .once()
+            .returning(|subscription| {
+                let mut saved = subscription.clone();
+                saved.id = Some(1);
+                Ok(saved)
+            });
+
+        let handler = CreateWebhookSubscriptionHandler::new(
+            Arc::new(MockAccountRepository::new()),
+            Arc::new(mock_webhook_repo),
+        );
+        let command = CreateWebhookSubscriptionCommand::new(
+            "https://hooks.example.com/ledger".to_string(),
+            vec![
+                "ledger_event.created".to_string(),
+                "account.created".to_string(),
+                "ledger_event.created".to_string(),
+            ],
+        );
+
+        let saved = handler.handle(command).await.unwrap();
+        assert_eq!(saved.id, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_create_subscription_checks_account_and_threshold() {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo
+            .expect_find_by_id()
+            .returning(|id| Err(DomainError::AccountNotFound(id.to_string())));
+
+        let handler = CreateWebhookSubscriptionHandler::new(
+            Arc::new(mock_account_repo),
+            Arc::new(MockWebhookRepository::new()),
+        );
+        let without_threshold = CreateWebhookSubscriptionCommand::new(
+            "https://hooks.example.com/ledger".to_string(),
+            vec!["balance.below_threshold".to_string()],
+        );
+        let unknown_account = without_threshold
+            .clone()
+            .with_account(Some(99))
+            .with_balance_threshold(Some(1000));
+
+        assert!(matches!(
+            handler.handle(without_threshold).await,
+            Err(DomainError::ValidationError(_))
+        ));
+        assert!(matches!(
+            handler.handle(unknown_account).await,
+            Err(DomainError::AccountNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+use crate::application::commands::DeleteWebhookSubscriptionCommand;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::WebhookRepository;
+use std::sync::Arc;
+use tracing::info;
+
+pub struct DeleteWebhookSubscriptionHandler {
+    webhook_repository: Arc<dyn WebhookRepository>,
+}
+
+impl DeleteWebhookSubscriptionHandler {
+    pub fn new(webhook_repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn handle(&self, command: DeleteWebhookSubscriptionCommand) -> DomainResult<()> {
+        info!(
+            "Deleting webhook subscription: id={}",
+            command.subscription_id
+        );
+
+        self.webhook_repository
+            .delete_subscription(command.subscription_id)
+            .await
+    }
+}
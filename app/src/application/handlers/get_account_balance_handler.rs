@@ -1,22 +1,26 @@
 use crate::application::queries::GetAccountBalanceQuery;
 use crate::domain::entities::AccountBalance;
-use crate::domain::errors::{DomainError, DomainResult};
-use crate::domain::repositories::{AccountBalanceRepository, LedgerEventRepository};
-use crate::domain::value_objects::Money;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::{
+    AccountBalanceRepository, AccountRepository, LedgerEventRepository,
+};
 use std::sync::Arc;
 use tracing::info;
 
 pub struct GetAccountBalanceHandler {
+    account_repository: Arc<dyn AccountRepository>,
     event_repository: Arc<dyn LedgerEventRepository>,
     balance_repository: Arc<dyn AccountBalanceRepository>,
 }
 
 impl GetAccountBalanceHandler {
     pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
         event_repository: Arc<dyn LedgerEventRepository>,
         balance_repository: Arc<dyn AccountBalanceRepository>,
     ) -> Self {
         Self {
+            account_repository,
             event_repository,
             balance_repository,
         }
@@ -24,46 +28,93 @@ impl GetAccountBalanceHandler {
 
     pub async fn handle(&self, query: GetAccountBalanceQuery) -> DomainResult<AccountBalance> {
         info!(
-            "Getting account balance: account_id={}, use_snapshot={}",
-            query.account_id, query.use_snapshot
+            "Getting account balance: account_id={}, use_snapshot={}, as_of={:?}",
+            query.account_id, query.use_snapshot, query.as_of
         );
 
-        if query.use_snapshot {
-            // Try to get the latest snapshot
-            if let Some(snapshot) = self
-                .balance_repository
-                .find_latest_by_account_id(query.account_id)
-                .await?
-            {
-                info!("Using balance snapshot: balance={}", snapshot.balance.value());
-                return Ok(snapshot);
+        let account = self.account_repository.find_by_id(query.account_id).await?;
+
+        // A past balance can only start from a snapshot taken before that instant
+        let snapshot = match (query.use_snapshot, query.as_of) {
+            (false, _) => None,
+            (true, None) => {
+                self.balance_repository
+                    .find_latest_by_account_id(query.account_id)
+                    .await?
             }
-        }
+            (true, Some(as_of)) => {
+                self.balance_repository
+                    .find_latest_before(query.account_id, as_of)
+                    .await?
+            }
+        };
 
-        // Calculate from events
-        let balance_value = self
-            .event_repository
-            .calculate_balance(query.account_id)
-            .await?;
+        // Start from the latest snapshot, or replay every event when there is none
+        let base = match snapshot {
+            Some(snapshot) => {
+                info!(
+                    "Using balance snapshot: id={:?}, balance={}, last_event_id={:?}",
+                    snapshot.id,
+                    snapshot.balance.value(),
+                    snapshot.last_event_id
+                );
+                snapshot
+            }
+            None => AccountBalance::initial(query.account_id, account.currency),
+        };
 
-        info!("Calculated balance from events: balance={}", balance_value);
+        let after_event_id = base.last_event_id.unwrap_or(0);
+        let delta = match query.as_of {
+            Some(as_of) => {
+                self.event_repository
+                    .calculate_balance_before(query.account_id, after_event_id, as_of)
+                    .await?
+            }
+            None => {
+                self.event_repository
+                    .calculate_balance_since(query.account_id, after_event_id)
+                    .await?
+            }
+        };
 
         // Return a calculated balance (not persisted)
-        Ok(AccountBalance::new(
-            query.account_id,
-            Money::new(balance_value)
-                .map_err(|e| DomainError::ValidationError(format!("Invalid balance: {}", e)))?,
-        ))
+        let balance = base.apply(&delta)?;
+
+        info!(
+            "Calculated balance from {} events: balance={}",
+            delta.event_count,
+            balance.balance.value()
+        );
+
+        Ok(balance)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::Account;
     use crate::domain::repositories::account_balance_repository::MockAccountBalanceRepository;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
     use crate::domain::repositories::ledger_event_repository::MockLedgerEventRepository;
+    use crate::domain::value_objects::{AccountNumber, Balance, BalanceDelta, Currency};
+    use chrono::NaiveDate;
     use mockall::predicate::*;
 
+    fn account_repo(currency: Currency) -> Arc<MockAccountRepository> {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(move |id| {
+            let mut account = Account::new(
+                AccountNumber::new("ACC001".to_string()).unwrap(),
+                "Test Account".to_string(),
+                currency,
+            );
+            account.id = Some(id);
+            Ok(account)
+        });
+        Arc::new(mock_account_repo)
+    }
+
     #[tokio::test]
     async fn test_get_balance_from_events() {
         let mut mock_event_repo = MockLedgerEventRepository::new();
@@ -71,12 +122,13 @@ mod tests {
 
         // When use_snapshot is false, balance repo should not be called
         mock_event_repo
-            .expect_calculate_balance()
-            .with(eq(1))
+            .expect_calculate_balance_since()
+            .with(eq(1), eq(0))
             .once()
-            .returning(|_| Ok(3000));
+            .returning(|_, _| Ok(BalanceDelta::new(3000, Some(2), 2)));
 
         let handler = GetAccountBalanceHandler::new(
+            account_repo(Currency::JPY),
             Arc::new(mock_event_repo),
             Arc::new(mock_balance_repo),
         );
@@ -87,5 +139,79 @@ mod tests {
 
         let balance = result.unwrap();
         assert_eq!(balance.balance.value(), 3000);
+        assert_eq!(balance.currency, Currency::JPY);
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_from_snapshot_and_later_events() {
+        let mut mock_event_repo = MockLedgerEventRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        mock_balance_repo
+            .expect_find_latest_by_account_id()
+            .with(eq(1))
+            .once()
+            .returning(|_| {
+                let mut snapshot = AccountBalance::new(1, Balance::new(5000), Currency::USD);
+                snapshot.id = Some(7);
+                snapshot.last_event_id = Some(10);
+                Ok(Some(snapshot))
+            });
+
+        // Only events after the snapshot position are aggregated
+        mock_event_repo
+            .expect_calculate_balance_since()
+            .with(eq(1), eq(10))
+            .once()
+            .returning(|_, _| Ok(BalanceDelta::new(-1500, Some(12), 2)));
+
+        let handler = GetAccountBalanceHandler::new(
+            account_repo(Currency::USD),
+            Arc::new(mock_event_repo),
+            Arc::new(mock_balance_repo),
+        );
+        let query = GetAccountBalanceQuery::with_snapshot(1);
+
+        let balance = handler.handle(query).await.unwrap();
+        assert_eq!(balance.balance.value(), 3500);
+        assert_eq!(balance.last_event_id, Some(12));
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_as_of_replays_from_earlier_snapshot() {
+        let as_of = NaiveDate::from_ymd_opt(2026, 4, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let mut mock_event_repo = MockLedgerEventRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        // The latest snapshot may be newer than as_of, so only earlier ones are looked up
+        mock_balance_repo
+            .expect_find_latest_before()
+            .with(eq(1), eq(as_of))
+            .once()
+            .returning(|_, _| {
+                let mut snapshot = AccountBalance::new(1, Balance::new(5000), Currency::USD);
+                snapshot.id = Some(2);
+                snapshot.last_event_id = Some(4);
+                Ok(Some(snapshot))
+            });
+        mock_event_repo
+            .expect_calculate_balance_before()
+            .with(eq(1), eq(4), eq(as_of))
+            .once()
+            .returning(|_, _, _| Ok(BalanceDelta::new(700, Some(9), 3)));
+
+        let handler = GetAccountBalanceHandler::new(
+            account_repo(Currency::USD),
+            Arc::new(mock_event_repo),
+            Arc::new(mock_balance_repo),
+        );
+        let query = GetAccountBalanceQuery::with_snapshot(1).with_as_of(Some(as_of));
+
+        let balance = handler.handle(query).await.unwrap();
+        assert_eq!(balance.balance.value(), 5700);
+        assert_eq!(balance.last_event_id, Some(9));
     }
 }
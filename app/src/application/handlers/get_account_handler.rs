@@ -1,5 +1,7 @@
-use crate::application::queries::{GetAccountByNumberQuery, GetAccountQuery};
-use crate::domain::{Account, AccountNumber, AccountRepository, DomainResult};
+use crate::application::queries::{
+    GetAccountByNumberQuery, GetAccountQuery, GetAccountStatusHistoryQuery,
+};
+use crate::domain::{Account, AccountNumber, AccountRepository, AccountStatusChange, DomainResult};
 use std::sync::Arc;
 use tracing::info;
 
@@ -24,12 +26,24 @@ impl GetAccountHandler {
             .find_by_account_number(&account_number)
             .await
     }
+
+    pub async fn handle_status_history(
+        &self,
+        query: GetAccountStatusHistoryQuery,
+    ) -> DomainResult<Vec<AccountStatusChange>> {
+        info!("Getting status history of account: {}", query.account_id);
+        // Unknown accounts are a 404 rather than an empty history
+        self.account_repository.find_by_id(query.account_id).await?;
+        self.account_repository
+            .find_status_changes(query.account_id)
+            .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::Money;
+    use crate::domain::{AccountFilter, Currency, Page, PageRequest};
     use mockall::mock;
     use mockall::predicate::*;
 
@@ -42,7 +56,10 @@ mod tests {
             async fn find_by_id(&self, id: i32) -> DomainResult<Account>;
             async fn find_by_account_number(&self, account_number: &AccountNumber) -> DomainResult<Account>;
             async fn find_all(&self) -> DomainResult<Vec<Account>>;
+            async fn find_page(&self, filter: &AccountFilter, page: &PageRequest) -> DomainResult<Page<Account>>;
             async fn update(&self, account: &Account) -> DomainResult<Account>;
+            async fn change_status(&self, change: &AccountStatusChange) -> DomainResult<AccountStatusChange>;
+            async fn find_status_changes(&self, account_id: i32) -> DomainResult<Vec<AccountStatusChange>>;
             async fn exists_by_account_number(&self, account_number: &AccountNumber) -> DomainResult<bool>;
         }
     }
@@ -52,7 +69,7 @@ mod tests {
         let mut mock_repo = MockAccountRepo::new();
 
         let account_number = AccountNumber::new("ACC001".to_string()).unwrap();
-        let mut account = Account::new(account_number, "Test Account".to_string());
+        let mut account = Account::new(account_number, "Test Account".to_string(), Currency::USD);
         account.id = Some(1);
 
         let account_clone = account.clone();
@@ -0,0 +1,208 @@
+use crate::application::queries::{GetAccountSnapshotsQuery, VerifyAccountSnapshotsQuery};
+use crate::domain::entities::AccountBalance;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::{
+    AccountBalanceRepository, AccountRepository, LedgerEventRepository,
+};
+use crate::domain::value_objects::Balance;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// A snapshot next to the balance recomputed from the events it covers
+#[derive(Debug, Clone)]
+pub struct SnapshotVerification {
+    pub snapshot: AccountBalance,
+    /// None for snapshots taken before event positions were recorded
+    pub expected_balance: Option<Balance>,
+}
+
+impl SnapshotVerification {
+    /// Whether the snapshot matches its events, None when it cannot be checked
+    pub fn matches(&self) -> Option<bool> {
+        self.expected_balance
+            .map(|expected| expected == self.snapshot.balance)
+    }
+}
+
+pub struct GetAccountSnapshotsHandler {
+    account_repository: Arc<dyn AccountRepository>,
+    event_repository: Arc<dyn LedgerEventRepository>,
+    balance_repository: Arc<dyn AccountBalanceRepository>,
+}
+
+impl GetAccountSnapshotsHandler {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        event_repository: Arc<dyn LedgerEventRepository>,
+        balance_repository: Arc<dyn AccountBalanceRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            event_repository,
+            balance_repository,
+        }
+    }
+
+    pub async fn handle(
+        &self,
+        query: GetAccountSnapshotsQuery,
+    ) -> DomainResult<Vec<AccountBalance>> {
+        info!("Getting balance snapshots: account_id={}", query.account_id);
+
+        // Ensure the account exists
+        self.account_repository.find_by_id(query.account_id).await?;
+
+        self.balance_repository
+            .find_all_by_account_id(query.account_id)
+            .await
+    }
+
+    pub async fn handle_verify(
+        &self,
+        query: VerifyAccountSnapshotsQuery,
+    ) -> DomainResult<Vec<SnapshotVerification>> {
+        info!(
+            "Verifying balance snapshots: account_id={}",
+            query.account_id
+        );
+
+        self.account_repository.find_by_id(query.account_id).await?;
+
+        let snapshots = self
+            .balance_repository
+            .find_all_by_account_id(query.account_id)
+            .await?;
+
+        // Walk the snapshots in event order so each stretch of events is summed once
+        let mut positioned: Vec<&AccountBalance> = snapshots
+            .iter()
+            .filter(|snapshot| snapshot.last_event_id.is_some())
+            .collect();
+        positioned.sort_by_key(|snapshot| (snapshot.last_event_id, snapshot.id));
+
+        let mut expected = HashMap::new();
+        let mut running = Balance::zero();
+        let mut position = 0;
+        for snapshot in positioned {
+            let through = snapshot.last_event_id.unwrap_or(0);
+            let delta = self
+                .event_repository
+                .calculate_balance_between(query.account_id, position, through)
+                .await?;
+            running = running.adjust(delta.amount)?;
+            position = position.max(through);
+            expected.insert(snapshot.id, running);
+        }
+
+        // Report in the listing order, newest first
+        let verifications: Vec<_> = snapshots
+            .into_iter()
+            .map(|snapshot| SnapshotVerification {
+                expected_balance: expected.get(&snapshot.id).copied(),
+                snapshot,
+            })
+            .collect();
+
+        let mismatches = verifications
+            .iter()
+            .filter(|verification| verification.matches() == Some(false))
+            .count();
+        if mismatches > 0 {
+            warn!(
+                "{} of {} balance snapshots of account_id={} do not match their events",
+                mismatches,
+                verifications.len(),
+                query.account_id
+            );
+        }
+
+        Ok(verifications)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Account;
+    use crate::domain::repositories::account_balance_repository::MockAccountBalanceRepository;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::repositories::ledger_event_repository::MockLedgerEventRepository;
+    use crate::domain::value_objects::{AccountNumber, BalanceDelta, Currency};
+    use mockall::predicate::*;
+
+    fn account_repo() -> Arc<MockAccountRepository> {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(|id| {
+            let mut account = Account::new(
+                AccountNumber::new("ACC001".to_string()).unwrap(),
+                "Test Account".to_string(),
+                Currency::USD,
+            );
+            account.id = Some(id);
+            Ok(account)
+        });
+        Arc::new(mock_account_repo)
+    }
+
+    fn snapshot(id: i32, balance: i64, last_event_id: Option<i32>) -> AccountBalance {
+        let mut snapshot = AccountBalance::new(1, Balance::new(balance), Currency::USD);
+        snapshot.id = Some(id);
+        snapshot.last_event_id = last_event_id;
+        snapshot
+    }
+
+    #[tokio::test]
+    async fn test_verify_flags_mismatched_snapshots() {
+        let mut mock_event_repo = MockLedgerEventRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        mock_balance_repo
+            .expect_find_all_by_account_id()
+            .with(eq(1))
+            .returning(|_| {
+                Ok(vec![
+                    snapshot(3, 900, Some(8)),
+                    snapshot(2, 1000, Some(5)),
+                    snapshot(1, 400, None),
+                ])
+            });
+
+        // Events up to 5 sum to 1000 and events 6..=8 to -200, so snapshot 3 is off
+        mock_event_repo
+            .expect_calculate_balance_between()
+            .with(eq(1), eq(0), eq(5))
+            .once()
+            .returning(|_, _, _| Ok(BalanceDelta::new(1000, Some(5), 3)));
+        mock_event_repo
+            .expect_calculate_balance_between()
+            .with(eq(1), eq(5), eq(8))
+            .once()
+            .returning(|_, _, _| Ok(BalanceDelta::new(-200, Some(8), 2)));
+
+        let handler = GetAccountSnapshotsHandler::new(
+            account_repo(),
+            Arc::new(mock_event_repo),
+            Arc::new(mock_balance_repo),
+        );
+
+        let verifications = handler
+            .handle_verify(VerifyAccountSnapshotsQuery::new(1))
+            .await
+            .unwrap();
+
+        let results: Vec<_> = verifications
+            .iter()
+            .map(|verification| (verification.snapshot.id, verification.matches()))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                (Some(3), Some(false)),
+                (Some(2), Some(true)),
+                (Some(1), None)
+            ]
+        );
+        assert_eq!(verifications[0].expected_balance, Some(Balance::new(800)));
+    }
+}
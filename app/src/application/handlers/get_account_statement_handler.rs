@@ -0,0 +1,268 @@
+use crate::application::queries::GetAccountStatementQuery;
+use crate::domain::entities::{AccountBalance, AccountStatement};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{
+    AccountBalanceRepository, AccountRepository, LedgerEventFilter, LedgerEventRepository,
+};
+use crate::domain::value_objects::{PageRequest, MAX_PAGE_SIZE};
+use std::sync::Arc;
+use tracing::info;
+
+pub struct GetAccountStatementHandler {
+    account_repository: Arc<dyn AccountRepository>,
+    event_repository: Arc<dyn LedgerEventRepository>,
+    balance_repository: Arc<dyn AccountBalanceRepository>,
+}
+
+impl GetAccountStatementHandler {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        event_repository: Arc<dyn LedgerEventRepository>,
+        balance_repository: Arc<dyn AccountBalanceRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            event_repository,
+            balance_repository,
+        }
+    }
+
+    pub async fn handle(&self, query: GetAccountStatementQuery) -> DomainResult<AccountStatement> {
+        info!(
+            "Getting account statement: account_id={}, from={}, to={}",
+            query.account_id, query.from, query.to
+        );
+
+        if query.from > query.to {
+            return Err(DomainError::ValidationError(format!(
+                "from {} is after to {}",
+                query.from, query.to
+            )));
+        }
+
+        let account = self.account_repository.find_by_id(query.account_id).await?;
+
+        // Every event up to a snapshot's position was recorded before the snapshot was
+        // taken, so a snapshot from before `from` only misses events created after it
+        let base = self
+            .balance_repository
+            .find_latest_before(query.account_id, query.from)
+            .await?
+            .unwrap_or_else(|| AccountBalance::initial(query.account_id, account.currency));
+
+        let delta = self
+            .event_repository
+            .calculate_balance_before(
+                query.account_id,
+                base.last_event_id.unwrap_or(0),
+                query.from,
+            )
+            .await?;
+        let opening = base.apply(&delta)?;
+
+        let mut statement = AccountStatement::new(
+            query.account_id,
+            account.currency,
+            query.from,
+            query.to,
+            opening.balance,
+        );
+
+        let filter = LedgerEventFilter {
+            account_id: Some(query.account_id),
+            created_from: Some(query.from),
+            created_to: Some(query.to),
+            ..LedgerEventFilter::default()
+        };
+        let mut cursor = None;
+        loop {
+            let page_request = PageRequest::new(cursor, Some(MAX_PAGE_SIZE))?;
+            let page = self
+                .event_repository
+                .find_page(&filter, &page_request)
+                .await?;
+
+            for event in page.items {
+                statement.push(event)?;
+            }
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        info!(
+            "Built statement with {} events: opening={}, closing={}",
+            statement.lines.len(),
+            statement.opening_balance,
+            statement.closing_balance
+        );
+
+        Ok(statement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Account, EventType, LedgerEvent};
+    use crate::domain::repositories::account_balance_repository::MockAccountBalanceRepository;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::repositories::ledger_event_repository::MockLedgerEventRepository;
+    use crate::domain::value_objects::{
+        AccountNumber, Balance, BalanceDelta, Currency, Money, Page,
+    };
+    use chrono::{NaiveDate, NaiveDateTime};
+    use mockall::predicate::*;
+
+    fn at(month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn account_repo() -> Arc<MockAccountRepository> {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(|id| {
+            let mut account = Account::new(
+                AccountNumber::new("ACC001".to_string()).unwrap(),
+                "Test Account".to_string(),
+                Currency::USD,
+            );
+            account.id = Some(id);
+            Ok(account)
+        });
+        Arc::new(mock_account_repo)
+    }
+
+    fn event(id: i32, event_type: EventType, amount: i64) -> LedgerEvent {
+        LedgerEvent {
+            id: Some(id),
+            created_at: Some(at(3, 10)),
+            ..LedgerEvent::new(
+                1,
+                event_type,
+                Money::new(amount, Currency::USD).unwrap(),
+                None,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_statement_from_snapshot_and_events_in_range() {
+        let mut mock_event_repo = MockLedgerEventRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        mock_balance_repo
+            .expect_find_latest_before()
+            .with(eq(1), eq(at(3, 1)))
+            .once()
+            .returning(|_, _| {
+                let mut snapshot = AccountBalance::new(1, Balance::new(5000), Currency::USD);
+                snapshot.id = Some(4);
+                snapshot.last_event_id = Some(10);
+                snapshot.snapshot_at = Some(at(2, 15));
+                Ok(Some(snapshot))
+            });
+
+        // Events between the snapshot and the start of the period make up the opening balance
+        mock_event_repo
+            .expect_calculate_balance_before()
+            .with(eq(1), eq(10), eq(at(3, 1)))
+            .once()
+            .returning(|_, _, _| Ok(BalanceDelta::new(-1000, Some(12), 2)));
+
+        mock_event_repo
+            .expect_find_page()
+            .withf(|filter, page| {
+                filter.account_id == Some(1)
+                    && filter.created_from == Some(at(3, 1))
+                    && filter.created_to == Some(at(4, 1))
+                    && page.after.is_none()
+            })
+            .once()
+            .returning(|_, _| {
+                Ok(Page {
+                    items: vec![
+                        event(13, EventType::Credit, 2500),
+                        event(14, EventType::Debit, 700),
+                    ],
+                    next_cursor: Some(14),
+                })
+            });
+        mock_event_repo
+            .expect_find_page()
+            .withf(|_, page| page.after == Some(14))
+            .once()
+            .returning(|_, _| {
+                Ok(Page {
+                    items: vec![event(15, EventType::Debit, 300)],
+                    next_cursor: None,
+                })
+            });
+
+        let handler = GetAccountStatementHandler::new(
+            account_repo(),
+            Arc::new(mock_event_repo),
+            Arc::new(mock_balance_repo),
+        );
+        let query = GetAccountStatementQuery::new(1, at(3, 1), at(4, 1));
+
+        let statement = handler.handle(query).await.unwrap();
+        assert_eq!(statement.opening_balance.value(), 4000);
+        assert_eq!(statement.lines.len(), 3);
+        assert_eq!(statement.lines[0].running_balance.value(), 6500);
+        assert_eq!(statement.lines[2].running_balance.value(), 5500);
+        assert_eq!(statement.total_credits, 2500);
+        assert_eq!(statement.total_debits, 1000);
+        assert_eq!(statement.closing_balance.value(), 5500);
+    }
+
+    #[tokio::test]
+    async fn test_statement_without_snapshot_replays_from_first_event() {
+        let mut mock_event_repo = MockLedgerEventRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        mock_balance_repo
+            .expect_find_latest_before()
+            .returning(|_, _| Ok(None));
+        mock_event_repo
+            .expect_calculate_balance_before()
+            .with(eq(1), eq(0), eq(at(3, 1)))
+            .once()
+            .returning(|_, _, _| Ok(BalanceDelta::new(0, None, 0)));
+        mock_event_repo.expect_find_page().returning(|_, _| {
+            Ok(Page {
+                items: vec![],
+                next_cursor: None,
+            })
+        });
+
+        let handler = GetAccountStatementHandler::new(
+            account_repo(),
+            Arc::new(mock_event_repo),
+            Arc::new(mock_balance_repo),
+        );
+        let query = GetAccountStatementQuery::new(1, at(3, 1), at(4, 1));
+
+        let statement = handler.handle(query).await.unwrap();
+        assert_eq!(statement.opening_balance.value(), 0);
+        assert_eq!(statement.closing_balance.value(), 0);
+        assert!(statement.lines.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_statement_rejects_inverted_range() {
+        let handler = GetAccountStatementHandler::new(
+            Arc::new(MockAccountRepository::new()),
+            Arc::new(MockLedgerEventRepository::new()),
+            Arc::new(MockAccountBalanceRepository::new()),
+        );
+        let query = GetAccountStatementQuery::new(1, at(4, 1), at(3, 1));
+
+        let result = handler.handle(query).await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+}
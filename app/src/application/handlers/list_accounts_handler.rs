@@ -1,5 +1,7 @@
 use crate::application::queries::ListAccountsQuery;
-use crate::domain::{Account, AccountRepository, DomainResult};
+use crate::domain::{
+    Account, AccountFilter, AccountRepository, DomainError, DomainResult, Page, PageRequest,
+};
 use std::sync::Arc;
 use tracing::info;
 
@@ -12,8 +14,33 @@ impl ListAccountsHandler {
         Self { account_repository }
     }
 
-    pub async fn handle(&self, _query: ListAccountsQuery) -> DomainResult<Vec<Account>> {
-        info!("Listing all accounts");
-        self.account_repository.find_all().await
+    pub async fn handle(&self, query: ListAccountsQuery) -> DomainResult<Page<Account>> {
+        info!("Listing accounts: {:?}", query);
+
+        let balance_policy = match query.balance_policy.map(|p| p.to_uppercase()) {
+            Some(policy)
+                if !["NO_OVERDRAFT", "OVERDRAFT", "UNRESTRICTED"].contains(&policy.as_str()) =>
+            {
+                return Err(DomainError::ValidationError(format!(
+                    "Invalid balance policy: {}",
+                    policy
+                )))
+            }
+            policy => policy,
+        };
+
+        let filter = AccountFilter {
+            search: query.search,
+            balance_policy,
+            created_from: query.created_from,
+            created_to: query.created_to,
+        };
+        filter.validate()?;
+
+        let page_request = PageRequest::new(query.cursor, query.limit)?;
+
+        self.account_repository
+            .find_page(&filter, &page_request)
+            .await
     }
 }
@@ -1,7 +1,8 @@
 use crate::application::queries::ListLedgerEventsQuery;
-use crate::domain::entities::LedgerEvent;
+use crate::domain::entities::{EventType, LedgerEvent};
 use crate::domain::errors::DomainResult;
-use crate::domain::repositories::LedgerEventRepository;
+use crate::domain::repositories::{LedgerEventFilter, LedgerEventRepository};
+use crate::domain::value_objects::{Page, PageRequest};
 use std::sync::Arc;
 use tracing::info;
 
@@ -14,17 +15,121 @@ impl ListLedgerEventsHandler {
         Self { event_repository }
     }
 
-    pub async fn handle(&self, query: ListLedgerEventsQuery) -> DomainResult<Vec<LedgerEvent>> {
-        info!("Listing ledger events: account_id={:?}", query.account_id);
+    pub async fn handle(
+        &self,
+        query: ListLedgerEventsQuery,
+    ) -> DomainResult<Page<LedgerEvent, i64>> {
+        info!("Listing ledger events: {:?}", query);
 
-        let events = if let Some(account_id) = query.account_id {
-            self.event_repository.find_by_account_id(account_id).await?
-        } else {
-            self.event_repository.find_all().await?
+        let filter = LedgerEventFilter {
+            account_id: query.account_id,
+            event_type: query
+                .event_type
+                .as_deref()
+                .map(EventType::from_string)
+                .transpose()?,
+            min_amount: query.min_amount,
+            max_amount: query.max_amount,
+            created_from: query.created_from,
+            created_to: query.created_to,
+            description: query.description,
         };
+        filter.validate()?;
 
-        info!("Found {} ledger events", events.len());
+        let page_request = PageRequest::new(query.cursor, query.limit)?;
 
-        Ok(events)
+        let page = self
+            .event_repository
+            .find_page(&filter, &page_request)
+            .await?;
+
+        info!("Found {} ledger events", page.items.len());
+
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::ledger_event_repository::MockLedgerEventRepository;
+    use crate::domain::value_objects::{Currency, Money};
+    use mockall::predicate::*;
+
+    fn event(id: i32) -> LedgerEvent {
+        LedgerEvent {
+            id: Some(id),
+            ..LedgerEvent::new(
+                1,
+                EventType::Credit,
+                Money::new(100, Currency::USD).unwrap(),
+                None,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_events_with_filters() {
+        let mut mock_repo = MockLedgerEventRepository::new();
+
+        let expected_filter = LedgerEventFilter {
+            account_id: Some(1),
+            event_type: Some(EventType::Credit),
+            min_amount: Some(50),
+            ..LedgerEventFilter::default()
+        };
+
+        mock_repo
+            .expect_find_page()
+            .with(
+                eq(expected_filter),
+                eq(PageRequest::new(Some(10), Some(2)).unwrap()),
+            )
+            .times(1)
+            .returning(|_, _| {
+                Ok(Page {
+                    items: vec![event(11), event(12)],
+                    next_cursor: Some(12),
+                })
+            });
+
+        let handler = ListLedgerEventsHandler::new(Arc::new(mock_repo));
+        let query = ListLedgerEventsQuery {
+            event_type: Some("credit".to_string()),
+            min_amount: Some(50),
+            ..ListLedgerEventsQuery::for_account(1)
+        }
+        .with_page(Some(10), Some(2));
+
+        let page = handler.handle(query).await.unwrap();
+
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.next_cursor, Some(12));
+    }
+
+    #[tokio::test]
+    async fn test_list_events_rejects_invalid_amount_range() {
+        let mut mock_repo = MockLedgerEventRepository::new();
+        mock_repo.expect_find_page().times(0);
+
+        let handler = ListLedgerEventsHandler::new(Arc::new(mock_repo));
+        let query = ListLedgerEventsQuery {
+            min_amount: Some(500),
+            max_amount: Some(100),
+            ..ListLedgerEventsQuery::new()
+        };
+
+        assert!(handler.handle(query).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_events_rejects_oversized_page() {
+        let mut mock_repo = MockLedgerEventRepository::new();
+        mock_repo.expect_find_page().times(0);
+
+        let handler = ListLedgerEventsHandler::new(Arc::new(mock_repo));
+        let query = ListLedgerEventsQuery::new().with_page(None, Some(10_000));
+
+        assert!(handler.handle(query).await.is_err());
     }
 }
@@ -0,0 +1,82 @@
+use crate::application::queries::ListOutboxMessagesQuery;
+use crate::domain::entities::{OutboxMessage, OutboxStatus};
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::{OutboxFilter, OutboxRepository};
+use crate::domain::value_objects::{Page, PageRequest};
+use std::sync::Arc;
+use tracing::info;
+
+pub struct ListOutboxMessagesHandler {
+    outbox_repository: Arc<dyn OutboxRepository>,
+}
+
+impl ListOutboxMessagesHandler {
+    pub fn new(outbox_repository: Arc<dyn OutboxRepository>) -> Self {
+        Self { outbox_repository }
+    }
+
+    pub async fn handle(
+        &self,
+        query: ListOutboxMessagesQuery,
+    ) -> DomainResult<Page<OutboxMessage>> {
+        info!("Listing outbox messages: {:?}", query);
+
+        let filter = OutboxFilter {
+            status: query
+                .status
+                .as_deref()
+                .map(OutboxStatus::from_string)
+                .transpose()?,
+            topic: query.topic,
+        };
+        let page_request = PageRequest::new(query.cursor, query.limit)?;
+
+        self.outbox_repository
+            .find_page(&filter, &page_request)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::outbox_repository::MockOutboxRepository;
+    use mockall::predicate::*;
+
+    #[tokio::test]
+    async fn test_list_outbox_messages_by_status() {
+        let mut mock_repo = MockOutboxRepository::new();
+        let expected_filter = OutboxFilter {
+            status: Some(OutboxStatus::Retrying),
+            topic: None,
+        };
+        mock_repo
+            .expect_find_page()
+            .with(
+                eq(expected_filter),
+                eq(PageRequest::new(None, Some(5)).unwrap()),
+            )
+            .times(1)
+            .returning(|_, _| {
+                Ok(Page {
+                    items: vec![],
+                    next_cursor: None,
+                })
+            });
+
+        let handler = ListOutboxMessagesHandler::new(Arc::new(mock_repo));
+        let query = ListOutboxMessagesQuery {
+            status: Some("retrying".to_string()),
+            ..ListOutboxMessagesQuery::new()
+        }
+        .with_page(None, Some(5));
+
+        assert!(handler.handle(query).await.unwrap().items.is_empty());
+
+        let invalid = ListOutboxMessagesQuery {
+            status: Some("lost".to_string()),
+            ..ListOutboxMessagesQuery::new()
+        };
+        assert!(handler.handle(invalid).await.is_err());
+    }
+}
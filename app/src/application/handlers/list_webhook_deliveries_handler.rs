@@ -0,0 +1,78 @@
+use crate::application::queries::ListWebhookDeliveriesQuery;
+use crate::domain::entities::{WebhookDelivery, WebhookDeliveryStatus};
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::{WebhookDeliveryFilter, WebhookRepository};
+use crate::domain::value_objects::{Page, PageRequest};
+use std::sync::Arc;
+use tracing::info;
+
+pub struct ListWebhookDeliveriesHandler {
+    webhook_repository: Arc<dyn WebhookRepository>,
+}
+
+impl ListWebhookDeliveriesHandler {
+    pub fn new(webhook_repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn handle(
+        &self,
+        query: ListWebhookDeliveriesQuery,
+    ) -> DomainResult<Page<WebhookDelivery>> {
+        info!("Listing webhook deliveries: {:?}", query);
+
+        let filter = WebhookDeliveryFilter {
+            subscription_id: query.subscription_id,
+            status: query
+                .status
+                .as_deref()
+                .map(WebhookDeliveryStatus::from_string)
+                .transpose()?,
+        };
+        let page_request = PageRequest::new(query.cursor, query.limit)?;
+
+        self.webhook_repository
+            .find_deliveries(&filter, &page_request)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::webhook_repository::MockWebhookRepository;
+    use mockall::predicate::*;
+
+    #[tokio::test]
+    async fn test_list_dead_letters() {
+        let mut mock_repo = MockWebhookRepository::new();
+        let expected_filter = WebhookDeliveryFilter {
+            subscription_id: Some(4),
+            status: Some(WebhookDeliveryStatus::DeadLettered),
+        };
+        mock_repo
+            .expect_find_deliveries()
+            .with(
+                eq(expected_filter),
+                eq(PageRequest::new(Some(20), Some(5)).unwrap()),
+            )
+            .times(1)
+            .returning(|_, _| {
+                Ok(Page {
+                    items: vec![],
+                    next_cursor: None,
+                })
+            });
+
+        let handler = ListWebhookDeliveriesHandler::new(Arc::new(mock_repo));
+        let query = ListWebhookDeliveriesQuery::dead_letters(Some(4)).with_page(Some(20), Some(5));
+
+        assert!(handler.handle(query).await.unwrap().items.is_empty());
+
+        let invalid = ListWebhookDeliveriesQuery {
+            status: Some("lost".to_string()),
+            ..ListWebhookDeliveriesQuery::new()
+        };
+        assert!(handler.handle(invalid).await.is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use crate::application::queries::ListWebhookSubscriptionsQuery;
+use crate::domain::entities::WebhookSubscription;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::WebhookRepository;
+use crate::domain::value_objects::{Page, PageRequest};
+use std::sync::Arc;
+use tracing::info;
+
+pub struct ListWebhookSubscriptionsHandler {
+    webhook_repository: Arc<dyn WebhookRepository>,
+}
+
+impl ListWebhookSubscriptionsHandler {
+    pub fn new(webhook_repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn handle(
+        &self,
+        query: ListWebhookSubscriptionsQuery,
+    ) -> DomainResult<Page<WebhookSubscription>> {
+        info!("Listing webhook subscriptions: {:?}", query);
+
+        let page_request = PageRequest::new(query.cursor, query.limit)?;
+        self.webhook_repository
+            .find_subscriptions(&page_request)
+            .await
+    }
+}
@@ -1,19 +1,43 @@
+pub mod change_account_status_handler;
 pub mod create_account_handler;
 pub mod create_balance_snapshot_handler;
+pub mod create_journal_entry_handler;
 pub mod create_ledger_event_handler;
-// pub mod create_transaction_handler; // Deprecated in favor of event-sourcing
+pub mod create_transfer_handler;
+pub mod create_webhook_subscription_handler;
+pub mod delete_webhook_subscription_handler;
 pub mod get_account_balance_handler;
 pub mod get_account_handler;
+pub mod get_account_snapshots_handler;
+pub mod get_account_statement_handler;
 pub mod list_accounts_handler;
 pub mod list_ledger_events_handler;
-// pub mod list_transactions_handler; // Deprecated in favor of ledger events
+pub mod list_outbox_messages_handler;
+pub mod list_webhook_deliveries_handler;
+pub mod list_webhook_subscriptions_handler;
+pub mod prune_balance_snapshots_handler;
+pub mod replay_webhook_delivery_handler;
+pub mod reverse_ledger_event_handler;
+pub mod snapshot_accounts_handler;
 
+pub use change_account_status_handler::ChangeAccountStatusHandler;
 pub use create_account_handler::CreateAccountHandler;
 pub use create_balance_snapshot_handler::CreateBalanceSnapshotHandler;
+pub use create_journal_entry_handler::CreateJournalEntryHandler;
 pub use create_ledger_event_handler::CreateLedgerEventHandler;
-// pub use create_transaction_handler::CreateTransactionHandler;
+pub use create_transfer_handler::CreateTransferHandler;
+pub use create_webhook_subscription_handler::CreateWebhookSubscriptionHandler;
+pub use delete_webhook_subscription_handler::DeleteWebhookSubscriptionHandler;
 pub use get_account_balance_handler::GetAccountBalanceHandler;
 pub use get_account_handler::GetAccountHandler;
+pub use get_account_snapshots_handler::{GetAccountSnapshotsHandler, SnapshotVerification};
+pub use get_account_statement_handler::GetAccountStatementHandler;
 pub use list_accounts_handler::ListAccountsHandler;
 pub use list_ledger_events_handler::ListLedgerEventsHandler;
-// pub use list_transactions_handler::ListTransactionsHandler;
+pub use list_outbox_messages_handler::ListOutboxMessagesHandler;
+pub use list_webhook_deliveries_handler::ListWebhookDeliveriesHandler;
+pub use list_webhook_subscriptions_handler::ListWebhookSubscriptionsHandler;
+pub use prune_balance_snapshots_handler::{PruneBalanceSnapshotsHandler, SnapshotPrune};
+pub use replay_webhook_delivery_handler::ReplayWebhookDeliveryHandler;
+pub use reverse_ledger_event_handler::ReverseLedgerEventHandler;
+pub use snapshot_accounts_handler::{SnapshotAccountsHandler, SnapshotRun};
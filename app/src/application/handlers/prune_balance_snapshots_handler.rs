@@ -0,0 +1,236 @@
+use crate::application::commands::PruneBalanceSnapshotsCommand;
+use crate::domain::entities::AccountBalance;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{AccountBalanceRepository, AccountFilter, AccountRepository};
+use crate::domain::value_objects::{PageRequest, MAX_PAGE_SIZE};
+use chrono::{Datelike, Duration, NaiveDateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::info;
+
+/// Outcome of pruning balance snapshots
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotPrune {
+    pub accounts_checked: usize,
+    pub deleted: usize,
+}
+
+pub struct PruneBalanceSnapshotsHandler {
+    account_repository: Arc<dyn AccountRepository>,
+    balance_repository: Arc<dyn AccountBalanceRepository>,
+}
+
+impl PruneBalanceSnapshotsHandler {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        balance_repository: Arc<dyn AccountBalanceRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            balance_repository,
+        }
+    }
+
+    pub async fn handle(
+        &self,
+        command: PruneBalanceSnapshotsCommand,
+    ) -> DomainResult<SnapshotPrune> {
+        info!("Pruning balance snapshots: {:?}", command);
+
+        if command.retain_days < 0 {
+            return Err(DomainError::ValidationError(format!(
+                "retain_days cannot be negative, got {}",
+                command.retain_days
+            )));
+        }
+        let cutoff = Utc::now().naive_utc() - Duration::days(command.retain_days);
+
+        let account_ids = match command.account_id {
+            Some(account_id) => {
+                self.account_repository.find_by_id(account_id).await?;
+                vec![account_id]
+            }
+            None => self.all_account_ids().await?,
+        };
+
+        let mut prune = SnapshotPrune::default();
+        for account_id in account_ids {
+            let snapshots = self
+                .balance_repository
+                .find_all_by_account_id(account_id)
+                .await?;
+            let ids = prunable_snapshots(&snapshots, cutoff);
+            if !ids.is_empty() {
+                prune.deleted += self.balance_repository.delete_by_ids(&ids).await?;
+            }
+            prune.accounts_checked += 1;
+        }
+
+        info!(
+            "Pruned {} balance snapshots of {} accounts taken before {}",
+            prune.deleted, prune.accounts_checked, cutoff
+        );
+
+        Ok(prune)
+    }
+
+    async fn all_account_ids(&self) -> DomainResult<Vec<i32>> {
+        let filter = AccountFilter::default();
+        let mut ids = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page_request = PageRequest::new(cursor, Some(MAX_PAGE_SIZE))?;
+            let page = self
+                .account_repository
+                .find_page(&filter, &page_request)
+                .await?;
+            ids.extend(page.items.iter().filter_map(|account| account.id));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(ids),
+            }
+        }
+    }
+}
+
+/// Ids of one account's snapshots that the retention policy drops: those taken
+/// before `cutoff`, except the last snapshot of each calendar month and the latest
+/// positioned snapshot, which current balances start from
+fn prunable_snapshots(snapshots: &[AccountBalance], cutoff: NaiveDateTime) -> Vec<i32> {
+    let mut keep = HashSet::new();
+
+    if let Some(latest) = snapshots
+        .iter()
+        .filter(|snapshot| snapshot.last_event_id.is_some())
+        .max_by_key(|snapshot| (snapshot.last_event_id, snapshot.id))
+    {
+        keep.insert(latest.id);
+    }
+
+    let mut month_ends: HashMap<(i32, u32), &AccountBalance> = HashMap::new();
+    for snapshot in snapshots {
+        let Some(taken) = snapshot.snapshot_at else {
+            continue;
+        };
+        month_ends
+            .entry((taken.year(), taken.month()))
+            .and_modify(|last| {
+                if (snapshot.snapshot_at, snapshot.id) > (last.snapshot_at, last.id) {
+                    *last = snapshot;
+                }
+            })
+            .or_insert(snapshot);
+    }
+    keep.extend(month_ends.values().map(|snapshot| snapshot.id));
+
+    snapshots
+        .iter()
+        .filter(|snapshot| snapshot.snapshot_at.is_some_and(|taken| taken < cutoff))
+        .filter(|snapshot| !keep.contains(&snapshot.id))
+        .filter_map(|snapshot| snapshot.id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Account;
+    use crate::domain::repositories::account_balance_repository::MockAccountBalanceRepository;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::value_objects::{AccountNumber, Balance, Currency, Page};
+    use chrono::NaiveDate;
+
+    fn at(month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, month, day)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    fn snapshot(id: i32, taken: NaiveDateTime) -> AccountBalance {
+        let mut snapshot = AccountBalance::new(1, Balance::new(100), Currency::USD);
+        snapshot.id = Some(id);
+        snapshot.last_event_id = Some(id * 10);
+        snapshot.snapshot_at = Some(taken);
+        snapshot
+    }
+
+    #[test]
+    fn test_prunable_snapshots_keep_month_ends() {
+        let snapshots = vec![
+            snapshot(1, at(1, 5)),
+            snapshot(2, at(1, 20)),
+            snapshot(3, at(1, 31)),
+            snapshot(4, at(2, 10)),
+            snapshot(5, at(3, 1)),
+            snapshot(6, at(3, 15)),
+        ];
+
+        // Everything before March 10 is old; March's last snapshot is newer than that
+        let mut pruned = prunable_snapshots(&snapshots, at(3, 10));
+        pruned.sort();
+
+        assert_eq!(pruned, vec![1, 2, 5]);
+    }
+
+    #[test]
+    fn test_prunable_snapshots_keep_latest_positioned() {
+        let mut legacy = snapshot(2, at(1, 20));
+        legacy.last_event_id = None;
+        let snapshots = vec![snapshot(1, at(1, 5)), legacy];
+
+        // Snapshot 2 closes January but 1 is still the latest that balances start from
+        assert!(prunable_snapshots(&snapshots, at(6, 1)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prune_deletes_old_snapshots() {
+        let mut mock_account_repo = MockAccountRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        mock_account_repo.expect_find_by_id().returning(|id| {
+            Err(DomainError::AccountNotFound(format!(
+                "Account with id {} not found",
+                id
+            )))
+        });
+        mock_account_repo.expect_find_page().returning(|_, _| {
+            let mut account = Account::new(
+                AccountNumber::new("ACC001".to_string()).unwrap(),
+                "Test Account".to_string(),
+                Currency::USD,
+            );
+            account.id = Some(1);
+            Ok(Page {
+                items: vec![account],
+                next_cursor: None,
+            })
+        });
+        mock_balance_repo
+            .expect_find_all_by_account_id()
+            .returning(|_| Ok(vec![snapshot(2, at(1, 20)), snapshot(1, at(1, 5))]));
+        mock_balance_repo
+            .expect_delete_by_ids()
+            .withf(|ids: &[i32]| ids == [1])
+            .once()
+            .returning(|ids| Ok(ids.len()));
+
+        let handler = PruneBalanceSnapshotsHandler::new(
+            Arc::new(mock_account_repo),
+            Arc::new(mock_balance_repo),
+        );
+
+        let prune = handler
+            .handle(PruneBalanceSnapshotsCommand::new(30))
+            .await
+            .unwrap();
+        assert_eq!(prune.accounts_checked, 1);
+        assert_eq!(prune.deleted, 1);
+
+        let result = handler
+            .handle(PruneBalanceSnapshotsCommand::for_account(9, 30))
+            .await;
+        assert!(matches!(result, Err(DomainError::AccountNotFound(_))));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::application::commands::ReplayWebhookDeliveryCommand;
+use crate::domain::entities::WebhookDelivery;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::WebhookRepository;
+use std::sync::Arc;
+use tracing::info;
+
+/// Sends a dead-lettered or delivered webhook again, under the same delivery id
+pub struct ReplayWebhookDeliveryHandler {
+    webhook_repository: Arc<dyn WebhookRepository>,
+}
+
+impl ReplayWebhookDeliveryHandler {
+    pub fn new(webhook_repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn handle(
+        &self,
+        command: ReplayWebhookDeliveryCommand,
+    ) -> DomainResult<WebhookDelivery> {
+        info!("Replaying webhook delivery: id={}", command.delivery_id);
+
+        let delivery = self
+            .webhook_repository
+            .find_delivery(command.delivery_id)
+            .await?;
+        delivery.ensure_replayable()?;
+
+        // The repository re-checks the status, so a replay cannot reset a delivery
+        // the dispatcher has picked up again meanwhile
+        self.webhook_repository
+            .replay_delivery(command.delivery_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::errors::DomainError;
+    use crate::domain::repositories::webhook_repository::MockWebhookRepository;
+    use mockall::predicate::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_replay_only_finished_deliveries() {
+        let mut mock_repo = MockWebhookRepository::new();
+        mock_repo.expect_find_delivery().returning(|id| {
+            let mut delivery = WebhookDelivery::new(1, 10, "account.created", json!({}));
+            delivery.id = Some(id);
+            delivery.attempts = 8;
+            if id == 1 {
+                delivery.dead_lettered_at = Some(chrono::Utc::now().naive_utc());
+            }
+            Ok(delivery)
+        });
+        mock_repo
+            .expect_replay_delivery()
+            .with(eq(1))
+            .once()
+            .returning(|id| {
+                let mut delivery = WebhookDelivery::new(1, 10, "account.created", json!({}));
+                delivery.id = Some(id);
+                Ok(delivery)
+            });
+
+        let handler = ReplayWebhookDeliveryHandler::new(Arc::new(mock_repo));
+
+        let replayed = handler
+            .handle(ReplayWebhookDeliveryCommand::new(1))
+            .await
+            .unwrap();
+        assert_eq!(replayed.attempts, 0);
+
+        assert!(matches!(
+            handler.handle(ReplayWebhookDeliveryCommand::new(2)).await,
+            Err(DomainError::DeliveryNotReplayable { delivery_id: 2, .. })
+        ));
+    }
+}
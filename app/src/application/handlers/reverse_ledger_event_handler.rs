@@ -0,0 +1,136 @@
+use crate::application::commands::ReverseLedgerEventCommand;
+use crate::application::metrics;
+use crate::domain::entities::LedgerEvent;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::{AccountRepository, LedgerEventRepository};
+use std::sync::Arc;
+use tracing::info;
+
+/// Cancels a ledger event by appending a compensating event that references it,
+/// leaving the original in place for the audit trail
+pub struct ReverseLedgerEventHandler {
+    account_repository: Arc<dyn AccountRepository>,
+    event_repository: Arc<dyn LedgerEventRepository>,
+}
+
+impl ReverseLedgerEventHandler {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        event_repository: Arc<dyn LedgerEventRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            event_repository,
+        }
+    }
+
+    pub async fn handle(&self, command: ReverseLedgerEventCommand) -> DomainResult<LedgerEvent> {
+        info!("Reversing ledger event: id={}", command.event_id);
+
+        let original = self.event_repository.find_by_id(command.event_id).await?;
+        let reversal = original
+            .reversal(command.description)?
+            .with_recorded_by(command.recorded_by);
+
+        // The repository re-checks the account status and that the event has not
+        // been reversed in the meantime under the account lock
+        let account = self
+            .account_repository
+            .find_by_id(original.account_id)
+            .await?;
+        account.ensure_accepts_events()?;
+
+        let saved_reversal = self.event_repository.save(&reversal).await?;
+        metrics::record_reversal();
+        metrics::record_events_appended([&saved_reversal]);
+
+        info!(
+            "Ledger event {} reversed by event {:?}",
+            command.event_id, saved_reversal.id
+        );
+
+        Ok(saved_reversal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Account, EventType};
+    use crate::domain::errors::DomainError;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::repositories::ledger_event_repository::MockLedgerEventRepository;
+    use crate::domain::value_objects::{AccountNumber, Currency, Money};
+    use mockall::predicate::*;
+
+    fn account_repo() -> MockAccountRepository {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo.expect_find_by_id().returning(|id| {
+            let mut account = Account::new(
+                AccountNumber::new("ACC001".to_string()).unwrap(),
+                "Test Account".to_string(),
+                Currency::USD,
+            );
+            account.id = Some(id);
+            Ok(account)
+        });
+        mock_account_repo
+    }
+
+    fn credit(id: i32) -> LedgerEvent {
+        LedgerEvent {
+            id: Some(id),
+            ..LedgerEvent::new_credit(1, Money::new(1000, Currency::USD).unwrap(), None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reverse_event() {
+        let mut mock_repo = MockLedgerEventRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(5))
+            .returning(|id| Ok(credit(id)));
+        mock_repo.expect_save().once().returning(|event| {
+            let mut saved = event.clone();
+            saved.id = Some(6);
+            Ok(saved)
+        });
+
+        let handler = ReverseLedgerEventHandler::new(Arc::new(account_repo()), Arc::new(mock_repo));
+
+        let reversal = handler
+            .handle(ReverseLedgerEventCommand::new(5, None))
+            .await
+            .unwrap();
+        assert_eq!(reversal.id, Some(6));
+        assert_eq!(reversal.event_type, EventType::Debit);
+        assert_eq!(reversal.amount.value(), 1000);
+        assert_eq!(reversal.reverses_event_id, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_reverse_event_twice() {
+        let mut mock_repo = MockLedgerEventRepository::new();
+        mock_repo.expect_find_by_id().returning(|id| {
+            Ok(LedgerEvent {
+                reversed_by_event_id: Some(6),
+                ..credit(id)
+            })
+        });
+        mock_repo.expect_save().times(0);
+
+        let handler = ReverseLedgerEventHandler::new(Arc::new(account_repo()), Arc::new(mock_repo));
+
+        let result = handler
+            .handle(ReverseLedgerEventCommand::new(5, None))
+            .await;
+        assert!(matches!(
+            result,
+            Err(DomainError::EventAlreadyReversed {
+                event_id: 5,
+                reversal_event_id: 6
+            })
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+use crate::application::commands::SnapshotAccountsCommand;
+use crate::application::metrics;
+use crate::domain::entities::{Account, AccountBalance};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{
+    AccountBalanceRepository, AccountFilter, AccountRepository, LedgerEventRepository,
+};
+use crate::domain::value_objects::{PageRequest, MAX_PAGE_SIZE};
+use chrono::{Duration, NaiveDateTime, Utc};
+use std::sync::Arc;
+use tracing::info;
+
+/// Outcome of a snapshot run over all accounts
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotRun {
+    pub snapshots: Vec<AccountBalance>,
+    pub accounts_checked: usize,
+    /// Accounts left with events after their latest snapshot
+    pub lagging_accounts: usize,
+    /// Most events any account has after its latest snapshot once the run is done
+    pub max_lag: i64,
+}
+
+pub struct SnapshotAccountsHandler {
+    account_repository: Arc<dyn AccountRepository>,
+    event_repository: Arc<dyn LedgerEventRepository>,
+    balance_repository: Arc<dyn AccountBalanceRepository>,
+}
+
+impl SnapshotAccountsHandler {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepository>,
+        event_repository: Arc<dyn LedgerEventRepository>,
+        balance_repository: Arc<dyn AccountBalanceRepository>,
+    ) -> Self {
+        Self {
+            account_repository,
+            event_repository,
+            balance_repository,
+        }
+    }
+
+    pub async fn handle(&self, command: SnapshotAccountsCommand) -> DomainResult<SnapshotRun> {
+        info!("Snapshotting accounts: {:?}", command);
+
+        if command.event_threshold < 1 {
+            return Err(DomainError::ValidationError(format!(
+                "event_threshold must be at least 1, got {}",
+                command.event_threshold
+            )));
+        }
+        let stale_before = command
+            .max_age_seconds
+            .map(|seconds| Utc::now().naive_utc() - Duration::seconds(seconds));
+
+        let mut run = SnapshotRun::default();
+        let filter = AccountFilter::default();
+        let mut cursor = None;
+        loop {
+            let page_request = PageRequest::new(cursor, Some(MAX_PAGE_SIZE))?;
+            let page = self
+                .account_repository
+                .find_page(&filter, &page_request)
+                .await?;
+
+            for account in &page.items {
+                self.snapshot_if_due(account, &command, stale_before, &mut run)
+                    .await?;
+            }
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        metrics::record_snapshots_created(run.snapshots.len());
+        metrics::record_snapshot_lag(run.max_lag, run.lagging_accounts);
+
+        info!(
+            "Snapshot run done: {} of {} accounts snapshotted, {} still lagging (max {} events)",
+            run.snapshots.len(),
+            run.accounts_checked,
+            run.lagging_accounts,
+            run.max_lag
+        );
+
+        Ok(run)
+    }
+
+    async fn snapshot_if_due(
+        &self,
+        account: &Account,
+        command: &SnapshotAccountsCommand,
+        stale_before: Option<NaiveDateTime>,
+        run: &mut SnapshotRun,
+    ) -> DomainResult<()> {
+        let account_id = account
+            .id
+            .ok_or_else(|| DomainError::RepositoryError("Listed account has no id".to_string()))?;
+        run.accounts_checked += 1;
+
+        let latest = self
+            .balance_repository
+            .find_latest_by_account_id(account_id)
+            .await?;
+        let snapshot_at = latest.as_ref().and_then(|snapshot| snapshot.snapshot_at);
+        let base = latest.unwrap_or_else(|| AccountBalance::initial(account_id, account.currency));
+
+        let delta = self
+            .event_repository
+            .calculate_balance_since(account_id, base.last_event_id.unwrap_or(0))
+            .await?;
+        if delta.event_count == 0 {
+            return Ok(());
+        }
+
+        let stale = stale_before.is_some_and(|stale_before| {
+            snapshot_at.is_none_or(|snapshot_at| snapshot_at < stale_before)
+        });
+        if delta.event_count < command.event_threshold && !stale {
+            run.lagging_accounts += 1;
+            run.max_lag = run.max_lag.max(delta.event_count);
+            return Ok(());
+        }
+
+        let snapshot = self.balance_repository.save(&base.apply(&delta)?).await?;
+        info!(
+            "Snapshotted account_id={} after {} new events: balance={}, last_event_id={:?}",
+            account_id,
+            delta.event_count,
+            snapshot.balance.value(),
+            snapshot.last_event_id
+        );
+        run.snapshots.push(snapshot);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::account_balance_repository::MockAccountBalanceRepository;
+    use crate::domain::repositories::account_repository::MockAccountRepository;
+    use crate::domain::repositories::ledger_event_repository::MockLedgerEventRepository;
+    use crate::domain::value_objects::{AccountNumber, Balance, BalanceDelta, Currency, Page};
+    use mockall::predicate::*;
+
+    fn account_repo(ids: Vec<i32>) -> Arc<MockAccountRepository> {
+        let mut mock_account_repo = MockAccountRepository::new();
+        mock_account_repo
+            .expect_find_page()
+            .once()
+            .returning(move |_, _| {
+                let items = ids
+                    .iter()
+                    .map(|&id| {
+                        let mut account = Account::new(
+                            AccountNumber::new(format!("ACC{:03}", id)).unwrap(),
+                            "Test Account".to_string(),
+                            Currency::USD,
+                        );
+                        account.id = Some(id);
+                        account
+                    })
+                    .collect();
+                Ok(Page {
+                    items,
+                    next_cursor: None,
+                })
+            });
+        Arc::new(mock_account_repo)
+    }
+
+    fn snapshot(account_id: i32, last_event_id: i32, age: Duration) -> AccountBalance {
+        let mut snapshot = AccountBalance::new(account_id, Balance::new(1000), Currency::USD);
+        snapshot.id = Some(account_id);
+        snapshot.last_event_id = Some(last_event_id);
+        snapshot.snapshot_at = Some(Utc::now().naive_utc() - age);
+        snapshot
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_accounts_past_threshold_or_max_age() {
+        let mut mock_event_repo = MockLedgerEventRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        // 1: many new events, 2: few events but an old snapshot,
+        // 3: few events and a recent snapshot, 4: nothing new
+        mock_balance_repo
+            .expect_find_latest_by_account_id()
+            .returning(|account_id| {
+                let age = match account_id {
+                    2 => Duration::days(3),
+                    _ => Duration::minutes(5),
+                };
+                Ok(Some(snapshot(account_id, 10, age)))
+            });
+        mock_event_repo
+            .expect_calculate_balance_since()
+            .returning(|account_id, _| {
+                Ok(match account_id {
+                    1 => BalanceDelta::new(500, Some(200), 150),
+                    2 | 3 => BalanceDelta::new(-200, Some(20), 4),
+                    _ => BalanceDelta::new(0, None, 0),
+                })
+            });
+        mock_balance_repo
+            .expect_save()
+            .times(2)
+            .returning(|snapshot| Ok(snapshot.clone()));
+
+        let handler = SnapshotAccountsHandler::new(
+            account_repo(vec![1, 2, 3, 4]),
+            Arc::new(mock_event_repo),
+            Arc::new(mock_balance_repo),
+        );
+
+        let run = handler
+            .handle(SnapshotAccountsCommand::due(100, 24 * 60 * 60))
+            .await
+            .unwrap();
+
+        let snapshotted: Vec<_> = run.snapshots.iter().map(|s| s.account_id).collect();
+        assert_eq!(snapshotted, vec![1, 2]);
+        assert_eq!(run.snapshots[0].balance.value(), 1500);
+        assert_eq!(run.snapshots[0].last_event_id, Some(200));
+        assert_eq!(run.accounts_checked, 4);
+        assert_eq!(run.lagging_accounts, 1);
+        assert_eq!(run.max_lag, 4);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_all_covers_accounts_without_snapshot() {
+        let mut mock_event_repo = MockLedgerEventRepository::new();
+        let mut mock_balance_repo = MockAccountBalanceRepository::new();
+
+        mock_balance_repo
+            .expect_find_latest_by_account_id()
+            .returning(|_| Ok(None));
+        mock_event_repo
+            .expect_calculate_balance_since()
+            .with(eq(7), eq(0))
+            .returning(|_, _| Ok(BalanceDelta::new(-300, Some(3), 1)));
+        mock_balance_repo
+            .expect_save()
+            .once()
+            .returning(|snapshot| Ok(snapshot.clone()));
+
+        let handler = SnapshotAccountsHandler::new(
+            account_repo(vec![7]),
+            Arc::new(mock_event_repo),
+            Arc::new(mock_balance_repo),
+        );
+
+        let run = handler
+            .handle(SnapshotAccountsCommand::all())
+            .await
+            .unwrap();
+
+        assert_eq!(run.snapshots.len(), 1);
+        assert_eq!(run.snapshots[0].balance.value(), -300);
+        assert_eq!(run.lagging_accounts, 0);
+        assert_eq!(run.max_lag, 0);
+    }
+}
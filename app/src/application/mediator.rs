@@ -1,29 +1,57 @@
 use crate::application::commands::{
-    CreateAccountCommand, CreateBalanceSnapshotCommand, CreateLedgerEventCommand,
+    ChangeAccountStatusCommand, CreateAccountCommand, CreateBalanceSnapshotCommand,
+    CreateJournalEntryCommand, CreateLedgerEventCommand, CreateTransferCommand,
+    CreateWebhookSubscriptionCommand, DeleteWebhookSubscriptionCommand,
+    PruneBalanceSnapshotsCommand, ReplayWebhookDeliveryCommand, ReverseLedgerEventCommand,
+    SnapshotAccountsCommand,
 };
 use crate::application::handlers::{
-    CreateAccountHandler, CreateBalanceSnapshotHandler, CreateLedgerEventHandler,
-    GetAccountBalanceHandler, GetAccountHandler, ListAccountsHandler, ListLedgerEventsHandler,
+    ChangeAccountStatusHandler, CreateAccountHandler, CreateBalanceSnapshotHandler,
+    CreateJournalEntryHandler, CreateLedgerEventHandler, CreateTransferHandler,
+    CreateWebhookSubscriptionHandler, DeleteWebhookSubscriptionHandler, GetAccountBalanceHandler,
+    GetAccountHandler, GetAccountSnapshotsHandler, GetAccountStatementHandler, ListAccountsHandler,
+    ListLedgerEventsHandler, ListOutboxMessagesHandler, ListWebhookDeliveriesHandler,
+    ListWebhookSubscriptionsHandler, PruneBalanceSnapshotsHandler, ReplayWebhookDeliveryHandler,
+    ReverseLedgerEventHandler, SnapshotAccountsHandler, SnapshotPrune, SnapshotRun,
+    SnapshotVerification,
 };
 use crate::application::queries::{
-    GetAccountBalanceQuery, GetAccountByNumberQuery, GetAccountQuery, ListAccountsQuery,
-    ListLedgerEventsQuery,
+    GetAccountBalanceQuery, GetAccountByNumberQuery, GetAccountQuery, GetAccountSnapshotsQuery,
+    GetAccountStatementQuery, GetAccountStatusHistoryQuery, ListAccountsQuery,
+    ListLedgerEventsQuery, ListOutboxMessagesQuery, ListWebhookDeliveriesQuery,
+    ListWebhookSubscriptionsQuery, VerifyAccountSnapshotsQuery,
 };
 use crate::domain::{
-    Account, AccountBalance, AccountBalanceRepository, AccountRepository, DomainResult,
-    LedgerEvent, LedgerEventRepository,
+    Account, AccountBalance, AccountBalanceRepository, AccountRepository, AccountStatement,
+    AccountStatusChange, DomainResult, JournalEntry, JournalEntryRepository, LedgerEvent,
+    LedgerEventRepository, OutboxMessage, OutboxRepository, Page, Transfer, TransferRepository,
+    WebhookDelivery, WebhookRepository, WebhookSubscription,
 };
 use std::sync::Arc;
 
 /// Mediator pattern implementation for command and query dispatching
 pub struct Mediator {
     create_account_handler: CreateAccountHandler,
+    change_account_status_handler: ChangeAccountStatusHandler,
     create_ledger_event_handler: CreateLedgerEventHandler,
+    reverse_ledger_event_handler: ReverseLedgerEventHandler,
+    create_transfer_handler: CreateTransferHandler,
+    create_journal_entry_handler: CreateJournalEntryHandler,
     create_balance_snapshot_handler: CreateBalanceSnapshotHandler,
+    snapshot_accounts_handler: SnapshotAccountsHandler,
+    prune_balance_snapshots_handler: PruneBalanceSnapshotsHandler,
     get_account_handler: GetAccountHandler,
     get_account_balance_handler: GetAccountBalanceHandler,
+    get_account_statement_handler: GetAccountStatementHandler,
+    get_account_snapshots_handler: GetAccountSnapshotsHandler,
     list_accounts_handler: ListAccountsHandler,
     list_ledger_events_handler: ListLedgerEventsHandler,
+    list_outbox_messages_handler: ListOutboxMessagesHandler,
+    create_webhook_subscription_handler: CreateWebhookSubscriptionHandler,
+    delete_webhook_subscription_handler: DeleteWebhookSubscriptionHandler,
+    replay_webhook_delivery_handler: ReplayWebhookDeliveryHandler,
+    list_webhook_subscriptions_handler: ListWebhookSubscriptionsHandler,
+    list_webhook_deliveries_handler: ListWebhookDeliveriesHandler,
 }
 
 impl Mediator {
@@ -31,21 +59,79 @@ impl Mediator {
         account_repository: Arc<dyn AccountRepository>,
         event_repository: Arc<dyn LedgerEventRepository>,
         balance_repository: Arc<dyn AccountBalanceRepository>,
+        transfer_repository: Arc<dyn TransferRepository>,
+        journal_entry_repository: Arc<dyn JournalEntryRepository>,
+        outbox_repository: Arc<dyn OutboxRepository>,
+        webhook_repository: Arc<dyn WebhookRepository>,
     ) -> Self {
         Self {
             create_account_handler: CreateAccountHandler::new(account_repository.clone()),
-            create_ledger_event_handler: CreateLedgerEventHandler::new(event_repository.clone()),
+            change_account_status_handler: ChangeAccountStatusHandler::new(
+                account_repository.clone(),
+            ),
+            create_ledger_event_handler: CreateLedgerEventHandler::new(
+                account_repository.clone(),
+                event_repository.clone(),
+            ),
+            reverse_ledger_event_handler: ReverseLedgerEventHandler::new(
+                account_repository.clone(),
+                event_repository.clone(),
+            ),
+            create_transfer_handler: CreateTransferHandler::new(
+                account_repository.clone(),
+                transfer_repository,
+            ),
+            create_journal_entry_handler: CreateJournalEntryHandler::new(
+                account_repository.clone(),
+                journal_entry_repository,
+            ),
             create_balance_snapshot_handler: CreateBalanceSnapshotHandler::new(
+                account_repository.clone(),
+                event_repository.clone(),
+                balance_repository.clone(),
+            ),
+            snapshot_accounts_handler: SnapshotAccountsHandler::new(
+                account_repository.clone(),
                 event_repository.clone(),
                 balance_repository.clone(),
             ),
+            prune_balance_snapshots_handler: PruneBalanceSnapshotsHandler::new(
+                account_repository.clone(),
+                balance_repository.clone(),
+            ),
             get_account_handler: GetAccountHandler::new(account_repository.clone()),
             get_account_balance_handler: GetAccountBalanceHandler::new(
+                account_repository.clone(),
+                event_repository.clone(),
+                balance_repository.clone(),
+            ),
+            get_account_statement_handler: GetAccountStatementHandler::new(
+                account_repository.clone(),
+                event_repository.clone(),
+                balance_repository.clone(),
+            ),
+            get_account_snapshots_handler: GetAccountSnapshotsHandler::new(
+                account_repository.clone(),
                 event_repository.clone(),
                 balance_repository,
             ),
+            create_webhook_subscription_handler: CreateWebhookSubscriptionHandler::new(
+                account_repository.clone(),
+                webhook_repository.clone(),
+            ),
             list_accounts_handler: ListAccountsHandler::new(account_repository),
             list_ledger_events_handler: ListLedgerEventsHandler::new(event_repository),
+            list_outbox_messages_handler: ListOutboxMessagesHandler::new(outbox_repository),
+            delete_webhook_subscription_handler: DeleteWebhookSubscriptionHandler::new(
+                webhook_repository.clone(),
+            ),
+            replay_webhook_delivery_handler: ReplayWebhookDeliveryHandler::new(
+                webhook_repository.clone(),
+            ),
+            list_webhook_subscriptions_handler: ListWebhookSubscriptionsHandler::new(
+                webhook_repository.clone(),
+            ),
+            list_webhook_deliveries_handler: ListWebhookDeliveriesHandler::new(webhook_repository),
         }
     }
 
@@ -57,6 +143,13 @@ impl Mediator {
         self.create_account_handler.handle(command).await
     }
 
+    pub async fn send_change_account_status(
+        &self,
+        command: ChangeAccountStatusCommand,
+    ) -> DomainResult<AccountStatusChange> {
+        self.change_account_status_handler.handle(command).await
+    }
+
     pub async fn send_create_ledger_event(
         &self,
         command: CreateLedgerEventCommand,
@@ -64,6 +157,27 @@ impl Mediator {
         self.create_ledger_event_handler.handle(command).await
     }
 
+    pub async fn send_reverse_ledger_event(
+        &self,
+        command: ReverseLedgerEventCommand,
+    ) -> DomainResult<LedgerEvent> {
+        self.reverse_ledger_event_handler.handle(command).await
+    }
+
+    pub async fn send_create_transfer(
+        &self,
+        command: CreateTransferCommand,
+    ) -> DomainResult<Transfer> {
+        self.create_transfer_handler.handle(command).await
+    }
+
+    pub async fn send_create_journal_entry(
+        &self,
+        command: CreateJournalEntryCommand,
+    ) -> DomainResult<JournalEntry> {
+        self.create_journal_entry_handler.handle(command).await
+    }
+
     pub async fn send_create_balance_snapshot(
         &self,
         command: CreateBalanceSnapshotCommand,
@@ -71,6 +185,45 @@ impl Mediator {
         self.create_balance_snapshot_handler.handle(command).await
     }
 
+    pub async fn send_snapshot_accounts(
+        &self,
+        command: SnapshotAccountsCommand,
+    ) -> DomainResult<SnapshotRun> {
+        self.snapshot_accounts_handler.handle(command).await
+    }
+
+    pub async fn send_prune_balance_snapshots(
+        &self,
+        command: PruneBalanceSnapshotsCommand,
+    ) -> DomainResult<SnapshotPrune> {
+        self.prune_balance_snapshots_handler.handle(command).await
+    }
+
+    pub async fn send_create_webhook_subscription(
+        &self,
+        command: CreateWebhookSubscriptionCommand,
+    ) -> DomainResult<WebhookSubscription> {
+        self.create_webhook_subscription_handler
+            .handle(command)
+            .await
+    }
+
+    pub async fn send_delete_webhook_subscription(
+        &self,
+        command: DeleteWebhookSubscriptionCommand,
+    ) -> DomainResult<()> {
+        self.delete_webhook_subscription_handler
+            .handle(command)
+            .await
+    }
+
+    pub async fn send_replay_webhook_delivery(
+        &self,
+        command: ReplayWebhookDeliveryCommand,
+    ) -> DomainResult<WebhookDelivery> {
+        self.replay_webhook_delivery_handler.handle(command).await
+    }
+
     // Query handlers
     pub async fn send_get_account(&self, query: GetAccountQuery) -> DomainResult<Account> {
         self.get_account_handler.handle(query).await
@@ -83,6 +236,13 @@ impl Mediator {
         self.get_account_handler.handle_by_number(query).await
     }
 
+    pub async fn send_get_account_status_history(
+        &self,
+        query: GetAccountStatusHistoryQuery,
+    ) -> DomainResult<Vec<AccountStatusChange>> {
+        self.get_account_handler.handle_status_history(query).await
+    }
+
     pub async fn send_get_account_balance(
         &self,
         query: GetAccountBalanceQuery,
@@ -90,14 +250,61 @@ impl Mediator {
         self.get_account_balance_handler.handle(query).await
     }
 
-    pub async fn send_list_accounts(&self, query: ListAccountsQuery) -> DomainResult<Vec<Account>> {
+    pub async fn send_get_account_statement(
+        &self,
+        query: GetAccountStatementQuery,
+    ) -> DomainResult<AccountStatement> {
+        self.get_account_statement_handler.handle(query).await
+    }
+
+    pub async fn send_get_account_snapshots(
+        &self,
+        query: GetAccountSnapshotsQuery,
+    ) -> DomainResult<Vec<AccountBalance>> {
+        self.get_account_snapshots_handler.handle(query).await
+    }
+
+    pub async fn send_verify_account_snapshots(
+        &self,
+        query: VerifyAccountSnapshotsQuery,
+    ) -> DomainResult<Vec<SnapshotVerification>> {
+        self.get_account_snapshots_handler
+            .handle_verify(query)
+            .await
+    }
+
+    pub async fn send_list_accounts(
+        &self,
+        query: ListAccountsQuery,
+    ) -> DomainResult<Page<Account>> {
         self.list_accounts_handler.handle(query).await
     }
 
     pub async fn send_list_ledger_events(
         &self,
         query: ListLedgerEventsQuery,
-    ) -> DomainResult<Vec<LedgerEvent>> {
+    ) -> DomainResult<Page<LedgerEvent, i64>> {
         self.list_ledger_events_handler.handle(query).await
     }
+
+    pub async fn send_list_outbox_messages(
+        &self,
+        query: ListOutboxMessagesQuery,
+    ) -> DomainResult<Page<OutboxMessage>> {
+        self.list_outbox_messages_handler.handle(query).await
+    }
+
+    pub async fn send_list_webhook_subscriptions(
+        &self,
+        query: ListWebhookSubscriptionsQuery,
+    ) -> DomainResult<Page<WebhookSubscription>> {
+        self.list_webhook_subscriptions_handler.handle(query).await
+    }
+
+    pub async fn send_list_webhook_deliveries(
+        &self,
+        query: ListWebhookDeliveriesQuery,
+    ) -> DomainResult<Page<WebhookDelivery>> {
+        self.list_webhook_deliveries_handler.handle(query).await
+    }
 }
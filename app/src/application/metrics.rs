@@ -0,0 +1,67 @@
+use crate::domain::entities::LedgerEvent;
+use crate::domain::value_objects::AccountStatus;
+use metrics::{counter, gauge};
+
+// Business counters exported on /metrics. Without an installed recorder (tests,
+// metrics disabled) the macros are no-ops.
+
+pub(crate) fn record_account_created() {
+    counter!("ledger_accounts_created_total").increment(1);
+}
+
+pub(crate) fn record_account_status_changed(to_status: AccountStatus) {
+    counter!("ledger_account_status_changes_total", "to_status" => to_status.as_str().to_string())
+        .increment(1);
+}
+
+pub(crate) fn record_events_appended<'a>(events: impl IntoIterator<Item = &'a LedgerEvent>) {
+    for event in events {
+        counter!("ledger_events_appended_total", "event_type" => event.event_type.as_str().to_string())
+            .increment(1);
+    }
+}
+
+pub(crate) fn record_transfer() {
+    counter!("ledger_transfers_total").increment(1);
+}
+
+pub(crate) fn record_journal_entry() {
+    counter!("ledger_journal_entries_total").increment(1);
+}
+
+pub(crate) fn record_reversal() {
+    counter!("ledger_reversals_total").increment(1);
+}
+
+pub(crate) fn record_snapshots_created(count: usize) {
+    counter!("ledger_balance_snapshots_total").increment(count as u64);
+}
+
+/// Events not yet covered by a snapshot, as measured by the latest snapshot run
+pub(crate) fn record_snapshot_lag(max_lag: i64, lagging_accounts: usize) {
+    gauge!("ledger_snapshot_max_lag_events").set(max_lag as f64);
+    gauge!("ledger_snapshot_lagging_accounts").set(lagging_accounts as f64);
+}
+
+pub(crate) fn record_outbox_run(sink: &str, published: usize, failed: usize) {
+    counter!("ledger_outbox_published_total", "sink" => sink.to_string())
+        .increment(published as u64);
+    counter!("ledger_outbox_publish_failures_total", "sink" => sink.to_string())
+        .increment(failed as u64);
+}
+
+/// Outbox messages not published yet, as counted after the latest relay run
+pub(crate) fn record_outbox_backlog(unpublished: i64) {
+    gauge!("ledger_outbox_unpublished_messages").set(unpublished as f64);
+}
+
+/// Webhook delivery attempts of a dispatcher run, by outcome
+pub(crate) fn record_webhook_run(delivered: usize, failed: usize, dead_lettered: usize) {
+    for (outcome, count) in [
+        ("delivered", delivered),
+        ("failed", failed),
+        ("dead_lettered", dead_lettered),
+    ] {
+        counter!("ledger_webhook_deliveries_total", "outcome" => outcome).increment(count as u64);
+    }
+}
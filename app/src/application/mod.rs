@@ -1,6 +1,18 @@
 pub mod commands;
 pub mod handlers;
 pub mod mediator;
+mod metrics;
+pub mod outbox_relay;
 pub mod queries;
+pub mod snapshot_scheduler;
+pub mod webhook_dispatcher;
 
 pub use mediator::Mediator;
+pub use outbox_relay::{
+    spawn_outbox_relay, MessageSink, OutboxRelay, OutboxRelaySettings, RelayRun, SinkError,
+};
+pub use snapshot_scheduler::spawn_snapshot_scheduler;
+pub use webhook_dispatcher::{
+    spawn_webhook_dispatcher, DispatchRun, WebhookDispatcher, WebhookDispatcherSettings,
+    WebhookRequest, WebhookSender,
+};
@@ -0,0 +1,227 @@
+use crate::application::metrics;
+use crate::domain::{DomainResult, OutboxMessage, OutboxRepository, RetryPolicy};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Failure to hand a message to its destination; the relay retries it later
+#[derive(Error, Debug)]
+#[error("{0}")]
+pub struct SinkError(pub String);
+
+/// Destination the outbox relay publishes messages to. The infrastructure layer
+/// provides webhook, NATS and file sinks; other brokers, Kafka for instance, plug
+/// in by implementing this trait.
+#[async_trait]
+pub trait MessageSink: Send + Sync {
+    /// Short name used in logs and metrics
+    fn name(&self) -> &str;
+    /// Delivers one message. Ok means the destination has accepted it; the message
+    /// is not offered again unless the relay fails to record that.
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), SinkError>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OutboxRelaySettings {
+    /// Messages claimed per run
+    pub batch_size: i64,
+    /// How long claimed messages stay hidden from other relays
+    pub lease: chrono::Duration,
+    pub retry: RetryPolicy,
+}
+
+/// Outcome of one relay run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayRun {
+    pub claimed: usize,
+    pub published: usize,
+    pub failed: usize,
+}
+
+/// Publishes outbox messages to a sink, at least once each.
+///
+/// A message is marked published only after the sink accepted it, so a crash in
+/// between delivers it again once its lease runs out. Messages are offered in id
+/// order, but a failed one is retried after later ones have gone out; consumers
+/// order each account's events by their `version`.
+pub struct OutboxRelay {
+    repository: Arc<dyn OutboxRepository>,
+    sink: Arc<dyn MessageSink>,
+    settings: OutboxRelaySettings,
+}
+
+impl OutboxRelay {
+    pub fn new(
+        repository: Arc<dyn OutboxRepository>,
+        sink: Arc<dyn MessageSink>,
+        settings: OutboxRelaySettings,
+    ) -> Self {
+        Self {
+            repository,
+            sink,
+            settings,
+        }
+    }
+
+    /// Claims one batch of due messages and publishes them
+    pub async fn run_once(&self) -> DomainResult<RelayRun> {
+        let messages = self
+            .repository
+            .claim_due(self.settings.lease, self.settings.batch_size)
+            .await?;
+
+        let mut run = RelayRun {
+            claimed: messages.len(),
+            ..RelayRun::default()
+        };
+        for message in &messages {
+            let Some(id) = message.id else {
+                continue;
+            };
+
+            match self.sink.publish(message).await {
+                Ok(()) => {
+                    self.repository.mark_published(id).await?;
+                    run.published += 1;
+                }
+                Err(e) => {
+                    let retry_in = self.settings.retry.delay(message.attempts + 1);
+                    warn!(
+                        "Publishing outbox message {} to {} failed (attempt {}), retrying in {}s: {}",
+                        id,
+                        self.sink.name(),
+                        message.attempts + 1,
+                        retry_in.num_seconds(),
+                        e
+                    );
+                    self.repository
+                        .mark_failed(id, &e.to_string(), retry_in)
+                        .await?;
+                    run.failed += 1;
+                }
+            }
+        }
+
+        metrics::record_outbox_run(self.sink.name(), run.published, run.failed);
+        metrics::record_outbox_backlog(self.repository.count_unpublished().await?);
+
+        Ok(run)
+    }
+}
+
+/// Periodically publishes due outbox messages, draining full batches back to back
+pub fn spawn_outbox_relay(relay: OutboxRelay, period: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        info!("Outbox relay publishing to {}", relay.sink.name());
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            loop {
+                match relay.run_once().await {
+                    Ok(run) if run.claimed as i64 >= relay.settings.batch_size => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        error!("Outbox relay run failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::outbox_repository::MockOutboxRepository;
+    use chrono::Duration as ChronoDuration;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Sink rejecting messages with `rejected_key` and recording the ids of the others
+    struct RecordingSink {
+        rejected_key: &'static str,
+        published: Mutex<Vec<i32>>,
+    }
+
+    #[async_trait]
+    impl MessageSink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn publish(&self, message: &OutboxMessage) -> Result<(), SinkError> {
+            if message.key == self.rejected_key {
+                return Err(SinkError("connection refused".to_string()));
+            }
+            self.published.lock().unwrap().extend(message.id);
+            Ok(())
+        }
+    }
+
+    fn message(id: i32, key: &str, attempts: i32) -> OutboxMessage {
+        let mut message = OutboxMessage::new("test", key.to_string(), json!({}));
+        message.id = Some(id);
+        message.attempts = attempts;
+        message
+    }
+
+    fn settings() -> OutboxRelaySettings {
+        OutboxRelaySettings {
+            batch_size: 10,
+            lease: ChronoDuration::seconds(30),
+            retry: RetryPolicy::new(ChronoDuration::seconds(1), ChronoDuration::seconds(60)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_publishes_and_schedules_retries() {
+        let mut mock_repo = MockOutboxRepository::new();
+        mock_repo
+            .expect_claim_due()
+            .withf(|lease, limit| *lease == ChronoDuration::seconds(30) && *limit == 10)
+            .returning(|_, _| {
+                Ok(vec![
+                    message(1, "a", 0),
+                    message(2, "b", 3),
+                    message(3, "a", 0),
+                ])
+            });
+        mock_repo
+            .expect_mark_published()
+            .withf(|id| *id == 1 || *id == 3)
+            .times(2)
+            .returning(|_| Ok(()));
+        // Fourth attempt failed: 1s doubled three times
+        mock_repo
+            .expect_mark_failed()
+            .withf(|id, error, retry_in| {
+                *id == 2 && error == "connection refused" && *retry_in == ChronoDuration::seconds(8)
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+        mock_repo.expect_count_unpublished().returning(|| Ok(1));
+
+        let sink = Arc::new(RecordingSink {
+            rejected_key: "b",
+            published: Mutex::new(Vec::new()),
+        });
+        let relay = OutboxRelay::new(Arc::new(mock_repo), sink.clone(), settings());
+
+        let run = relay.run_once().await.unwrap();
+
+        assert_eq!(
+            run,
+            RelayRun {
+                claimed: 3,
+                published: 2,
+                failed: 1
+            }
+        );
+        assert_eq!(*sink.published.lock().unwrap(), vec![1, 3]);
+    }
+}
@@ -1,9 +1,11 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetAccountBalanceQuery {
     pub account_id: i32,
     pub use_snapshot: bool, // If true, use latest snapshot; if false, calculate from events
+    pub as_of: Option<NaiveDateTime>, // Balance from the events created before this instant
 }
 
 impl GetAccountBalanceQuery {
@@ -11,6 +13,7 @@ impl GetAccountBalanceQuery {
         Self {
             account_id,
             use_snapshot: false,
+            as_of: None,
         }
     }
 
@@ -18,6 +21,12 @@ impl GetAccountBalanceQuery {
         Self {
             account_id,
             use_snapshot: true,
+            as_of: None,
         }
     }
+
+    pub fn with_as_of(mut self, as_of: Option<NaiveDateTime>) -> Self {
+        self.as_of = as_of;
+        self
+    }
 }
@@ -21,3 +21,14 @@ impl GetAccountByNumberQuery {
         Self { account_number }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetAccountStatusHistoryQuery {
+    pub account_id: i32,
+}
+
+impl GetAccountStatusHistoryQuery {
+    pub fn new(account_id: i32) -> Self {
+        Self { account_id }
+    }
+}
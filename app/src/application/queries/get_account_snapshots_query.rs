@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetAccountSnapshotsQuery {
+    pub account_id: i32,
+}
+
+impl GetAccountSnapshotsQuery {
+    pub fn new(account_id: i32) -> Self {
+        Self { account_id }
+    }
+}
+
+/// Recomputes every snapshot of the account from its events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyAccountSnapshotsQuery {
+    pub account_id: i32,
+}
+
+impl VerifyAccountSnapshotsQuery {
+    pub fn new(account_id: i32) -> Self {
+        Self { account_id }
+    }
+}
@@ -0,0 +1,19 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetAccountStatementQuery {
+    pub account_id: i32,
+    pub from: NaiveDateTime, // Inclusive
+    pub to: NaiveDateTime,   // Exclusive
+}
+
+impl GetAccountStatementQuery {
+    pub fn new(account_id: i32, from: NaiveDateTime, to: NaiveDateTime) -> Self {
+        Self {
+            account_id,
+            from,
+            to,
+        }
+    }
+}
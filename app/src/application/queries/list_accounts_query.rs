@@ -1,16 +1,24 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ListAccountsQuery;
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListAccountsQuery {
+    pub search: Option<String>,         // Matches account number or name
+    pub balance_policy: Option<String>, // "NO_OVERDRAFT", "OVERDRAFT" or "UNRESTRICTED"
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub cursor: Option<i32>, // Id of the last account of the previous page
+    pub limit: Option<i64>,
+}
 
 impl ListAccountsQuery {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
-}
 
-impl Default for ListAccountsQuery {
-    fn default() -> Self {
-        Self::new()
+    pub fn with_page(mut self, cursor: Option<i32>, limit: Option<i64>) -> Self {
+        self.cursor = cursor;
+        self.limit = limit;
+        self
     }
 }
@@ -1,24 +1,34 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ListLedgerEventsQuery {
     pub account_id: Option<i32>,
+    pub event_type: Option<String>, // "DEBIT" or "CREDIT"
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub description: Option<String>,
+    pub cursor: Option<i64>, // Sequence of the last event of the previous page
+    pub limit: Option<i64>,
 }
 
 impl ListLedgerEventsQuery {
     pub fn new() -> Self {
-        Self { account_id: None }
+        Self::default()
     }
 
     pub fn for_account(account_id: i32) -> Self {
         Self {
             account_id: Some(account_id),
+            ..Self::default()
         }
     }
-}
 
-impl Default for ListLedgerEventsQuery {
-    fn default() -> Self {
-        Self::new()
+    pub fn with_page(mut self, cursor: Option<i64>, limit: Option<i64>) -> Self {
+        self.cursor = cursor;
+        self.limit = limit;
+        self
     }
 }
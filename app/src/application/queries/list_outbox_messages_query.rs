@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListOutboxMessagesQuery {
+    pub status: Option<String>, // "PENDING", "RETRYING" or "PUBLISHED"
+    pub topic: Option<String>,
+    pub cursor: Option<i32>, // Id of the last message of the previous page
+    pub limit: Option<i64>,
+}
+
+impl ListOutboxMessagesQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_page(mut self, cursor: Option<i32>, limit: Option<i64>) -> Self {
+        self.cursor = cursor;
+        self.limit = limit;
+        self
+    }
+}
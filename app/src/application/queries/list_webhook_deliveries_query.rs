@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListWebhookDeliveriesQuery {
+    pub subscription_id: Option<i32>,
+    pub status: Option<String>, // "PENDING", "RETRYING", "DELIVERED" or "DEAD_LETTERED"
+    pub cursor: Option<i32>,    // Id of the last delivery of the previous page
+    pub limit: Option<i64>,
+}
+
+impl ListWebhookDeliveriesQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deliveries given up on after their last allowed attempt
+    pub fn dead_letters(subscription_id: Option<i32>) -> Self {
+        Self {
+            subscription_id,
+            status: Some("DEAD_LETTERED".to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_page(mut self, cursor: Option<i32>, limit: Option<i64>) -> Self {
+        self.cursor = cursor;
+        self.limit = limit;
+        self
+    }
+}
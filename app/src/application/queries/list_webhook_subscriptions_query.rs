@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListWebhookSubscriptionsQuery {
+    pub cursor: Option<i32>, // Id of the last subscription of the previous page
+    pub limit: Option<i64>,
+}
+
+impl ListWebhookSubscriptionsQuery {
+    pub fn new(cursor: Option<i32>, limit: Option<i64>) -> Self {
+        Self { cursor, limit }
+    }
+}
@@ -1,11 +1,21 @@
 pub mod get_account_balance_query;
 pub mod get_account_query;
+pub mod get_account_snapshots_query;
+pub mod get_account_statement_query;
 pub mod list_accounts_query;
 pub mod list_ledger_events_query;
-// pub mod list_transactions_query; // Deprecated in favor of ledger events
+pub mod list_outbox_messages_query;
+pub mod list_webhook_deliveries_query;
+pub mod list_webhook_subscriptions_query;
 
 pub use get_account_balance_query::GetAccountBalanceQuery;
-pub use get_account_query::{GetAccountByNumberQuery, GetAccountQuery};
+pub use get_account_query::{
+    GetAccountByNumberQuery, GetAccountQuery, GetAccountStatusHistoryQuery,
+};
+pub use get_account_snapshots_query::{GetAccountSnapshotsQuery, VerifyAccountSnapshotsQuery};
+pub use get_account_statement_query::GetAccountStatementQuery;
 pub use list_accounts_query::ListAccountsQuery;
 pub use list_ledger_events_query::ListLedgerEventsQuery;
-// pub use list_transactions_query::{GetTransactionsByAccountQuery, ListTransactionsQuery};
+pub use list_outbox_messages_query::ListOutboxMessagesQuery;
+pub use list_webhook_deliveries_query::ListWebhookDeliveriesQuery;
+pub use list_webhook_subscriptions_query::ListWebhookSubscriptionsQuery;
@@ -0,0 +1,25 @@
+use crate::application::commands::SnapshotAccountsCommand;
+use crate::application::Mediator;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::error;
+
+/// Periodically snapshots the accounts that `command` considers due
+pub fn spawn_snapshot_scheduler(
+    mediator: Arc<Mediator>,
+    period: Duration,
+    command: SnapshotAccountsCommand,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        // A run slower than the period is followed by the next one, not by a burst
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = mediator.send_snapshot_accounts(command.clone()).await {
+                error!("Scheduled balance snapshot run failed: {}", e);
+            }
+        }
+    })
+}
@@ -0,0 +1,297 @@
+use crate::application::metrics;
+use crate::application::SinkError;
+use crate::domain::{
+    DomainError, DomainResult, RetryPolicy, WebhookRepository, WebhookSignature,
+    WebhookSubscription,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Signed webhook request, ready to be POSTed to a subscriber
+#[derive(Debug, Clone)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub delivery_id: i32,
+    pub event_type: String,
+    pub signature: WebhookSignature,
+    pub body: String,
+}
+
+/// Sends webhook requests; the infrastructure layer provides one over HTTP
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// Ok means the subscriber answered with a 2xx status
+    async fn send(&self, request: &WebhookRequest) -> Result<(), SinkError>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct WebhookDispatcherSettings {
+    /// Deliveries claimed per run
+    pub batch_size: i64,
+    /// How long claimed deliveries stay hidden from other dispatchers
+    pub lease: chrono::Duration,
+    pub retry: RetryPolicy,
+    /// Attempts after which a failing delivery is dead-lettered
+    pub max_attempts: i32,
+}
+
+/// Outcome of one dispatcher run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DispatchRun {
+    pub claimed: usize,
+    pub delivered: usize,
+    pub failed: usize,
+    pub dead_lettered: usize,
+}
+
+/// Sends due webhook deliveries to their subscribers, signing each request with
+/// the subscription's secret.
+///
+/// Like the outbox relay, delivery is at least once: a delivery is marked
+/// delivered only after the subscriber accepted it. A failed delivery is retried
+/// with exponential backoff until `max_attempts`, then dead-lettered until replayed.
+pub struct WebhookDispatcher {
+    repository: Arc<dyn WebhookRepository>,
+    sender: Arc<dyn WebhookSender>,
+    settings: WebhookDispatcherSettings,
+}
+
+impl WebhookDispatcher {
+    pub fn new(
+        repository: Arc<dyn WebhookRepository>,
+        sender: Arc<dyn WebhookSender>,
+        settings: WebhookDispatcherSettings,
+    ) -> Self {
+        Self {
+            repository,
+            sender,
+            settings,
+        }
+    }
+
+    /// Claims one batch of due deliveries and sends them
+    pub async fn run_once(&self) -> DomainResult<DispatchRun> {
+        let deliveries = self
+            .repository
+            .claim_due_deliveries(self.settings.lease, self.settings.batch_size)
+            .await?;
+
+        let mut run = DispatchRun {
+            claimed: deliveries.len(),
+            ..DispatchRun::default()
+        };
+        let mut subscriptions: HashMap<i32, WebhookSubscription> = HashMap::new();
+        for delivery in &deliveries {
+            let Some(id) = delivery.id else {
+                continue;
+            };
+
+            let subscription = match subscriptions.entry(delivery.subscription_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    match self
+                        .repository
+                        .find_subscription(delivery.subscription_id)
+                        .await
+                    {
+                        Ok(subscription) => entry.insert(subscription),
+                        // Deleted since the claim, taking its deliveries with it
+                        Err(DomainError::WebhookSubscriptionNotFound(_)) => continue,
+                        Err(e) => return Err(e),
+                    }
+                }
+            };
+
+            let body = delivery.body().to_string();
+            let request = WebhookRequest {
+                url: subscription.url.clone(),
+                delivery_id: id,
+                event_type: delivery.event_type.clone(),
+                signature: WebhookSignature::sign(
+                    &subscription.secret,
+                    Utc::now().timestamp(),
+                    &body,
+                ),
+                body,
+            };
+
+            let attempt = delivery.attempts + 1;
+            match self.sender.send(&request).await {
+                Ok(()) => {
+                    self.repository.mark_delivered(id).await?;
+                    run.delivered += 1;
+                }
+                Err(e) if attempt >= self.settings.max_attempts => {
+                    warn!(
+                        "Webhook delivery {} to {} failed (attempt {}), dead-lettered: {}",
+                        id, request.url, attempt, e
+                    );
+                    self.repository
+                        .mark_dead_lettered(id, &e.to_string())
+                        .await?;
+                    run.dead_lettered += 1;
+                }
+                Err(e) => {
+                    let retry_in = self.settings.retry.delay(attempt);
+                    warn!(
+                        "Webhook delivery {} to {} failed (attempt {}), retrying in {}s: {}",
+                        id,
+                        request.url,
+                        attempt,
+                        retry_in.num_seconds(),
+                        e
+                    );
+                    self.repository
+                        .mark_failed(id, &e.to_string(), retry_in)
+                        .await?;
+                    run.failed += 1;
+                }
+            }
+        }
+
+        metrics::record_webhook_run(run.delivered, run.failed, run.dead_lettered);
+        Ok(run)
+    }
+}
+
+/// Periodically sends due webhook deliveries, draining full batches back to back
+pub fn spawn_webhook_dispatcher(dispatcher: WebhookDispatcher, period: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        info!("Webhook dispatcher started");
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            loop {
+                match dispatcher.run_once().await {
+                    Ok(run) if run.claimed as i64 >= dispatcher.settings.batch_size => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        error!("Webhook dispatcher run failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::webhook_repository::MockWebhookRepository;
+    use crate::domain::WebhookDelivery;
+    use chrono::Duration as ChronoDuration;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Sender failing requests to `failing_url` and recording the others
+    struct RecordingSender {
+        failing_url: &'static str,
+        sent: Mutex<Vec<WebhookRequest>>,
+    }
+
+    #[async_trait]
+    impl WebhookSender for RecordingSender {
+        async fn send(&self, request: &WebhookRequest) -> Result<(), SinkError> {
+            if request.url == self.failing_url {
+                return Err(SinkError("answered 500".to_string()));
+            }
+            self.sent.lock().unwrap().push(request.clone());
+            Ok(())
+        }
+    }
+
+    fn delivery(id: i32, subscription_id: i32, attempts: i32) -> WebhookDelivery {
+        let mut delivery = WebhookDelivery::new(
+            subscription_id,
+            100 + id,
+            "account.created",
+            json!({"id": id}),
+        );
+        delivery.id = Some(id);
+        delivery.attempts = attempts;
+        delivery
+    }
+
+    fn subscription(id: i32) -> WebhookSubscription {
+        let mut subscription = WebhookSubscription::new(
+            format!("https://hooks.example.com/{}", id),
+            vec!["account.created".to_string()],
+            format!("whsec_{}", id),
+        );
+        subscription.id = Some(id);
+        subscription
+    }
+
+    #[tokio::test]
+    async fn test_run_delivers_retries_and_dead_letters() {
+        let mut mock_repo = MockWebhookRepository::new();
+        mock_repo.expect_claim_due_deliveries().returning(|_, _| {
+            Ok(vec![
+                delivery(1, 1, 0),
+                delivery(2, 2, 1),
+                delivery(3, 2, 4),
+            ])
+        });
+        mock_repo
+            .expect_find_subscription()
+            .times(2)
+            .returning(|id| Ok(subscription(id)));
+        mock_repo
+            .expect_mark_delivered()
+            .withf(|id| *id == 1)
+            .once()
+            .returning(|_| Ok(()));
+        // Second attempt failed: base delay doubled once
+        mock_repo
+            .expect_mark_failed()
+            .withf(|id, error, retry_in| {
+                *id == 2 && error == "answered 500" && *retry_in == ChronoDuration::seconds(20)
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+        mock_repo
+            .expect_mark_dead_lettered()
+            .withf(|id, _| *id == 3)
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let sender = Arc::new(RecordingSender {
+            failing_url: "https://hooks.example.com/2",
+            sent: Mutex::new(Vec::new()),
+        });
+        let dispatcher = WebhookDispatcher::new(
+            Arc::new(mock_repo),
+            sender.clone(),
+            WebhookDispatcherSettings {
+                batch_size: 10,
+                lease: ChronoDuration::seconds(30),
+                retry: RetryPolicy::new(ChronoDuration::seconds(10), ChronoDuration::seconds(600)),
+                max_attempts: 5,
+            },
+        );
+
+        let run = dispatcher.run_once().await.unwrap();
+
+        assert_eq!(
+            run,
+            DispatchRun {
+                claimed: 3,
+                delivered: 1,
+                failed: 1,
+                dead_lettered: 1
+            }
+        );
+        let sent = sender.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].delivery_id, 1);
+        assert!(sent[0].signature.verify("whsec_1", &sent[0].body));
+    }
+}
@@ -0,0 +1,1167 @@
+use crate::api::auth::Scope;
+use crate::api::idempotency::DEFAULT_IDEMPOTENCY_TTL_SECONDS;
+use crate::domain::value_objects::MAX_PAGE_SIZE;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error;
+use tracing_subscriber::EnvFilter;
+
+/// Config file read when `CONFIG_FILE` is not set; it is optional
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("cannot read config file {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("invalid config file {}: {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+
+    #[error("invalid value {value:?} for {name}: {message}")]
+    Env {
+        name: &'static str,
+        value: String,
+        message: String,
+    },
+
+    #[error("invalid configuration: {0}")]
+    Invalid(String),
+}
+
+/// Application settings. Values come from the built-in defaults, then the TOML
+/// config file, then environment variables, each layer overriding the previous one.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub storage: StorageConfig,
+    pub database: DatabaseConfig,
+    pub logging: LoggingConfig,
+    pub idempotency: IdempotencyConfig,
+    pub snapshots: SnapshotConfig,
+    pub outbox: OutboxConfig,
+    pub webhooks: WebhookConfig,
+    pub auth: AuthConfig,
+    pub features: FeatureConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind_address: SocketAddr,
+    /// Requests running longer than this are answered with 408
+    pub request_timeout_seconds: u64,
+    /// How long in-flight requests may keep running once shutdown is requested
+    pub shutdown_timeout_seconds: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: SocketAddr::from(([0, 0, 0, 0], 3000)),
+            request_timeout_seconds: 30,
+            shutdown_timeout_seconds: 30,
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_seconds)
+    }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_seconds)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Postgres,
+    /// Data lives in process memory and is lost on shutdown; for demos and tests
+    Memory,
+}
+
+impl FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "postgres" => Ok(StorageBackend::Postgres),
+            "memory" => Ok(StorageBackend::Memory),
+            _ => Err("expected \"postgres\" or \"memory\"".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub max_connections: u32,
+    /// Idle connections kept open; defaults to `max_connections`
+    pub min_idle: Option<u32>,
+    /// How long to wait for a free connection before failing
+    pub connection_timeout_seconds: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            max_connections: 10,
+            min_idle: None,
+            connection_timeout_seconds: 30,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    pub fn connection_timeout(&self) -> Duration {
+        Duration::from_secs(self.connection_timeout_seconds)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err("expected \"text\" or \"json\"".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// Level or `EnvFilter` directives, e.g. "info" or "info,tower_http=debug"
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            format: LogFormat::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdempotencyConfig {
+    pub key_ttl_seconds: i64,
+    pub purge_interval_seconds: u64,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            key_ttl_seconds: DEFAULT_IDEMPOTENCY_TTL_SECONDS,
+            purge_interval_seconds: 60 * 60,
+        }
+    }
+}
+
+impl IdempotencyConfig {
+    pub fn key_ttl(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.key_ttl_seconds)
+    }
+
+    pub fn purge_interval(&self) -> Duration {
+        Duration::from_secs(self.purge_interval_seconds)
+    }
+}
+
+/// Background balance snapshotting. An account is snapshotted once `event_threshold`
+/// events were appended after its latest snapshot, or once it has any new event and
+/// the snapshot is older than `max_age_seconds`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnapshotConfig {
+    /// How often accounts are checked
+    pub interval_seconds: u64,
+    pub event_threshold: i64,
+    pub max_age_seconds: i64,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 60,
+            event_threshold: 1000,
+            max_age_seconds: 24 * 60 * 60,
+        }
+    }
+}
+
+impl SnapshotConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_seconds)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutboxSink {
+    /// Messages are recorded but not published
+    #[default]
+    None,
+    Webhook,
+    Nats,
+    /// JSON lines appended to a local file, for development and tests
+    File,
+}
+
+impl FromStr for OutboxSink {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(OutboxSink::None),
+            "webhook" => Ok(OutboxSink::Webhook),
+            "nats" => Ok(OutboxSink::Nats),
+            "file" => Ok(OutboxSink::File),
+            _ => Err("expected \"none\", \"webhook\", \"nats\" or \"file\"".to_string()),
+        }
+    }
+}
+
+/// Publishing of the transactional outbox. A message is recorded with every ledger
+/// event regardless; the relay delivers them to `sink`, retrying failed deliveries
+/// after `retry_base_seconds`, doubling up to `retry_max_seconds`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutboxConfig {
+    pub sink: OutboxSink,
+    /// Endpoint the webhook sink POSTs to
+    pub webhook_url: Option<String>,
+    /// `host:port` of the NATS server, optionally prefixed with `nats://`
+    pub nats_address: Option<String>,
+    /// File the file sink appends to
+    pub file_path: Option<PathBuf>,
+    /// How often the relay looks for due messages
+    pub poll_interval_ms: u64,
+    pub batch_size: i64,
+    /// How long a claimed message stays hidden from other relays
+    pub lease_seconds: i64,
+    pub retry_base_seconds: i64,
+    pub retry_max_seconds: i64,
+    /// Deadline for a single delivery
+    pub publish_timeout_seconds: u64,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            sink: OutboxSink::None,
+            webhook_url: None,
+            nats_address: None,
+            file_path: None,
+            poll_interval_ms: 1000,
+            batch_size: 100,
+            lease_seconds: 60,
+            retry_base_seconds: 1,
+            retry_max_seconds: 15 * 60,
+            publish_timeout_seconds: 10,
+        }
+    }
+}
+
+impl OutboxConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    pub fn lease(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.lease_seconds)
+    }
+
+    pub fn retry_base(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.retry_base_seconds)
+    }
+
+    pub fn retry_max(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.retry_max_seconds)
+    }
+
+    pub fn publish_timeout(&self) -> Duration {
+        Duration::from_secs(self.publish_timeout_seconds)
+    }
+}
+
+/// Delivery of webhook subscriptions. A failed delivery is retried after
+/// `retry_base_seconds`, doubling up to `retry_max_seconds`, and dead-lettered once
+/// `max_attempts` attempts have failed.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// How often the dispatcher looks for due deliveries
+    pub poll_interval_ms: u64,
+    pub batch_size: i64,
+    /// How long a claimed delivery stays hidden from other dispatchers
+    pub lease_seconds: i64,
+    pub retry_base_seconds: i64,
+    pub retry_max_seconds: i64,
+    pub max_attempts: i32,
+    /// Deadline for a single request
+    pub timeout_seconds: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: 1000,
+            batch_size: 100,
+            lease_seconds: 60,
+            retry_base_seconds: 10,
+            retry_max_seconds: 60 * 60,
+            max_attempts: 10,
+            timeout_seconds: 10,
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    pub fn lease(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.lease_seconds)
+    }
+
+    pub fn retry_base(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.retry_base_seconds)
+    }
+
+    pub fn retry_max(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.retry_max_seconds)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds)
+    }
+}
+
+/// Caller authentication. When enabled every endpoint except the health probes and
+/// `/metrics` needs an API key (`X-API-Key`) or a JWT (`Authorization: Bearer`).
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    pub enabled: bool,
+    pub api_keys: Vec<ApiKeyConfig>,
+    pub jwt: Option<JwtConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// Identity of the caller, recorded on the ledger events it creates
+    pub client_id: String,
+    /// Hex-encoded SHA-256 of the key, so the key itself is never stored
+    pub key_sha256: String,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum JwtAlgorithm {
+    #[default]
+    HS256,
+    RS256,
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "HS256" => Ok(JwtAlgorithm::HS256),
+            "RS256" => Ok(JwtAlgorithm::RS256),
+            _ => Err("expected \"HS256\" or \"RS256\"".to_string()),
+        }
+    }
+}
+
+/// Tokens are verified with a locally configured key; the client id is taken from
+/// the `sub` claim and the scopes from the space-separated `scope` claim.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JwtConfig {
+    pub algorithm: JwtAlgorithm,
+    /// Shared secret, for HS256
+    pub secret: Option<String>,
+    /// PEM file holding the RSA public key, for RS256
+    pub public_key_file: Option<PathBuf>,
+    /// Required `iss` claim, if set
+    pub issuer: Option<String>,
+    /// Required `aud` claim, if set
+    pub audience: Option<String>,
+}
+
+/// Switches for optional behaviour
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureConfig {
+    /// Honour `Idempotency-Key` headers on write requests
+    pub idempotency: bool,
+    /// Periodically delete expired idempotency keys
+    pub idempotency_key_purge: bool,
+    /// Expose Prometheus metrics at `/metrics`
+    pub metrics: bool,
+    /// Periodically snapshot account balances, see `SnapshotConfig`
+    pub snapshot_scheduler: bool,
+    /// Publish outbox messages to the configured sink, see `OutboxConfig`
+    pub outbox_relay: bool,
+    /// Send webhook deliveries to subscribers, see `WebhookConfig`
+    pub webhook_dispatcher: bool,
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self {
+            idempotency: true,
+            idempotency_key_purge: true,
+            metrics: true,
+            snapshot_scheduler: true,
+            outbox_relay: true,
+            webhook_dispatcher: true,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Loads the configuration from `CONFIG_FILE` (or `config.toml` if present) and
+    /// the process environment, and validates it.
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = match std::env::var("CONFIG_FILE") {
+            Ok(path) => Self::from_file(Path::new(&path))?,
+            Err(_) if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                Self::from_file(Path::new(DEFAULT_CONFIG_FILE))?
+            }
+            Err(_) => Self::default(),
+        };
+
+        config.apply_env(|name| std::env::var(name).ok())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_toml(&contents).map_err(|message| ConfigError::Parse {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.message().to_string())
+    }
+
+    /// Overrides settings with the environment variables that are set, as returned
+    /// by `var`
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        override_from(&var, "STORAGE_BACKEND", &mut self.storage.backend)?;
+        if let Some(url) = var("DATABASE_URL") {
+            self.database.url = url;
+        }
+        override_from(&var, "BIND_ADDRESS", &mut self.server.bind_address)?;
+        override_from(
+            &var,
+            "REQUEST_TIMEOUT_SECONDS",
+            &mut self.server.request_timeout_seconds,
+        )?;
+        override_from(
+            &var,
+            "SHUTDOWN_TIMEOUT_SECONDS",
+            &mut self.server.shutdown_timeout_seconds,
+        )?;
+        override_from(
+            &var,
+            "DATABASE_MAX_CONNECTIONS",
+            &mut self.database.max_connections,
+        )?;
+        if let Some(value) = var("DATABASE_MIN_IDLE") {
+            self.database.min_idle = Some(parse_env("DATABASE_MIN_IDLE", value)?);
+        }
+        override_from(
+            &var,
+            "DATABASE_CONNECTION_TIMEOUT_SECONDS",
+            &mut self.database.connection_timeout_seconds,
+        )?;
+        if let Some(level) = var("LOG_LEVEL") {
+            self.logging.level = level;
+        }
+        override_from(&var, "LOG_FORMAT", &mut self.logging.format)?;
+        override_from(
+            &var,
+            "IDEMPOTENCY_KEY_TTL_SECONDS",
+            &mut self.idempotency.key_ttl_seconds,
+        )?;
+        override_from(
+            &var,
+            "IDEMPOTENCY_PURGE_INTERVAL_SECONDS",
+            &mut self.idempotency.purge_interval_seconds,
+        )?;
+        override_from(&var, "FEATURE_IDEMPOTENCY", &mut self.features.idempotency)?;
+        override_from(
+            &var,
+            "FEATURE_IDEMPOTENCY_KEY_PURGE",
+            &mut self.features.idempotency_key_purge,
+        )?;
+        override_from(
+            &var,
+            "SNAPSHOT_INTERVAL_SECONDS",
+            &mut self.snapshots.interval_seconds,
+        )?;
+        override_from(
+            &var,
+            "SNAPSHOT_EVENT_THRESHOLD",
+            &mut self.snapshots.event_threshold,
+        )?;
+        override_from(
+            &var,
+            "SNAPSHOT_MAX_AGE_SECONDS",
+            &mut self.snapshots.max_age_seconds,
+        )?;
+        override_from(
+            &var,
+            "FEATURE_SNAPSHOT_SCHEDULER",
+            &mut self.features.snapshot_scheduler,
+        )?;
+        override_from(&var, "OUTBOX_SINK", &mut self.outbox.sink)?;
+        if let Some(url) = var("OUTBOX_WEBHOOK_URL") {
+            self.outbox.webhook_url = Some(url);
+        }
+        if let Some(address) = var("OUTBOX_NATS_ADDRESS") {
+            self.outbox.nats_address = Some(address);
+        }
+        if let Some(path) = var("OUTBOX_FILE_PATH") {
+            self.outbox.file_path = Some(PathBuf::from(path));
+        }
+        override_from(
+            &var,
+            "OUTBOX_POLL_INTERVAL_MS",
+            &mut self.outbox.poll_interval_ms,
+        )?;
+        override_from(&var, "OUTBOX_BATCH_SIZE", &mut self.outbox.batch_size)?;
+        override_from(&var, "OUTBOX_LEASE_SECONDS", &mut self.outbox.lease_seconds)?;
+        override_from(
+            &var,
+            "OUTBOX_RETRY_BASE_SECONDS",
+            &mut self.outbox.retry_base_seconds,
+        )?;
+        override_from(
+            &var,
+            "OUTBOX_RETRY_MAX_SECONDS",
+            &mut self.outbox.retry_max_seconds,
+        )?;
+        override_from(
+            &var,
+            "OUTBOX_PUBLISH_TIMEOUT_SECONDS",
+            &mut self.outbox.publish_timeout_seconds,
+        )?;
+        override_from(
+            &var,
+            "FEATURE_OUTBOX_RELAY",
+            &mut self.features.outbox_relay,
+        )?;
+        override_from(
+            &var,
+            "WEBHOOK_POLL_INTERVAL_MS",
+            &mut self.webhooks.poll_interval_ms,
+        )?;
+        override_from(&var, "WEBHOOK_BATCH_SIZE", &mut self.webhooks.batch_size)?;
+        override_from(
+            &var,
+            "WEBHOOK_LEASE_SECONDS",
+            &mut self.webhooks.lease_seconds,
+        )?;
+        override_from(
+            &var,
+            "WEBHOOK_RETRY_BASE_SECONDS",
+            &mut self.webhooks.retry_base_seconds,
+        )?;
+        override_from(
+            &var,
+            "WEBHOOK_RETRY_MAX_SECONDS",
+            &mut self.webhooks.retry_max_seconds,
+        )?;
+        override_from(
+            &var,
+            "WEBHOOK_MAX_ATTEMPTS",
+            &mut self.webhooks.max_attempts,
+        )?;
+        override_from(
+            &var,
+            "WEBHOOK_TIMEOUT_SECONDS",
+            &mut self.webhooks.timeout_seconds,
+        )?;
+        override_from(
+            &var,
+            "FEATURE_WEBHOOK_DISPATCHER",
+            &mut self.features.webhook_dispatcher,
+        )?;
+        override_from(&var, "AUTH_ENABLED", &mut self.auth.enabled)?;
+        self.apply_jwt_env(&var)?;
+        override_from(&var, "FEATURE_METRICS", &mut self.features.metrics)?;
+        Ok(())
+    }
+
+    /// Any `AUTH_JWT_*` variable enables JWT authentication with the other settings
+    /// taken from the file or their defaults
+    fn apply_jwt_env(&mut self, var: &impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        const NAMES: [&str; 5] = [
+            "AUTH_JWT_ALGORITHM",
+            "AUTH_JWT_SECRET",
+            "AUTH_JWT_PUBLIC_KEY_FILE",
+            "AUTH_JWT_ISSUER",
+            "AUTH_JWT_AUDIENCE",
+        ];
+        if NAMES.iter().all(|name| var(name).is_none()) {
+            return Ok(());
+        }
+
+        let jwt = self.auth.jwt.get_or_insert_with(JwtConfig::default);
+        override_from(var, "AUTH_JWT_ALGORITHM", &mut jwt.algorithm)?;
+        if let Some(secret) = var("AUTH_JWT_SECRET") {
+            jwt.secret = Some(secret);
+        }
+        if let Some(path) = var("AUTH_JWT_PUBLIC_KEY_FILE") {
+            jwt.public_key_file = Some(PathBuf::from(path));
+        }
+        if let Some(issuer) = var("AUTH_JWT_ISSUER") {
+            jwt.issuer = Some(issuer);
+        }
+        if let Some(audience) = var("AUTH_JWT_AUDIENCE") {
+            jwt.audience = Some(audience);
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.storage.backend == StorageBackend::Postgres && self.database.url.trim().is_empty() {
+            return Err(ConfigError::Invalid(
+                "database.url (DATABASE_URL) must be set".to_string(),
+            ));
+        }
+        if self.database.max_connections == 0 {
+            return Err(ConfigError::Invalid(
+                "database.max_connections must be at least 1".to_string(),
+            ));
+        }
+        if let Some(min_idle) = self.database.min_idle {
+            if min_idle > self.database.max_connections {
+                return Err(ConfigError::Invalid(format!(
+                    "database.min_idle ({}) cannot exceed database.max_connections ({})",
+                    min_idle, self.database.max_connections
+                )));
+            }
+        }
+        if self.database.connection_timeout_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "database.connection_timeout_seconds must be positive".to_string(),
+            ));
+        }
+        if self.server.request_timeout_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "server.request_timeout_seconds must be positive".to_string(),
+            ));
+        }
+        if let Err(e) = EnvFilter::try_new(&self.logging.level) {
+            return Err(ConfigError::Invalid(format!(
+                "logging.level {:?} is not a valid filter: {}",
+                self.logging.level, e
+            )));
+        }
+        if self.idempotency.key_ttl_seconds <= 0 {
+            return Err(ConfigError::Invalid(
+                "idempotency.key_ttl_seconds must be positive".to_string(),
+            ));
+        }
+        if self.idempotency.purge_interval_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "idempotency.purge_interval_seconds must be positive".to_string(),
+            ));
+        }
+        if self.snapshots.interval_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "snapshots.interval_seconds must be positive".to_string(),
+            ));
+        }
+        if self.snapshots.event_threshold <= 0 {
+            return Err(ConfigError::Invalid(
+                "snapshots.event_threshold must be positive".to_string(),
+            ));
+        }
+        if self.snapshots.max_age_seconds <= 0 {
+            return Err(ConfigError::Invalid(
+                "snapshots.max_age_seconds must be positive".to_string(),
+            ));
+        }
+        self.validate_outbox()?;
+        self.validate_webhooks()?;
+        self.validate_auth()
+    }
+
+    fn validate_outbox(&self) -> Result<(), ConfigError> {
+        let outbox = &self.outbox;
+        match outbox.sink {
+            OutboxSink::Webhook => {
+                let url = outbox.webhook_url.as_deref().unwrap_or("");
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(ConfigError::Invalid(
+                        "outbox.webhook_url (OUTBOX_WEBHOOK_URL) must be an http(s) URL for the webhook sink"
+                            .to_string(),
+                    ));
+                }
+            }
+            OutboxSink::Nats if outbox.nats_address.as_deref().unwrap_or("").is_empty() => {
+                return Err(ConfigError::Invalid(
+                    "outbox.nats_address (OUTBOX_NATS_ADDRESS) must be set for the nats sink"
+                        .to_string(),
+                ));
+            }
+            OutboxSink::File if outbox.file_path.is_none() => {
+                return Err(ConfigError::Invalid(
+                    "outbox.file_path (OUTBOX_FILE_PATH) must be set for the file sink".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        if outbox.poll_interval_ms == 0 {
+            return Err(ConfigError::Invalid(
+                "outbox.poll_interval_ms must be positive".to_string(),
+            ));
+        }
+        if !(1..=MAX_PAGE_SIZE).contains(&outbox.batch_size) {
+            return Err(ConfigError::Invalid(format!(
+                "outbox.batch_size must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+        if outbox.lease_seconds <= 0 {
+            return Err(ConfigError::Invalid(
+                "outbox.lease_seconds must be positive".to_string(),
+            ));
+        }
+        if outbox.retry_base_seconds <= 0 || outbox.retry_max_seconds < outbox.retry_base_seconds {
+            return Err(ConfigError::Invalid(
+                "outbox.retry_base_seconds must be positive and at most outbox.retry_max_seconds"
+                    .to_string(),
+            ));
+        }
+        if outbox.publish_timeout_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "outbox.publish_timeout_seconds must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_webhooks(&self) -> Result<(), ConfigError> {
+        let webhooks = &self.webhooks;
+        if webhooks.poll_interval_ms == 0 {
+            return Err(ConfigError::Invalid(
+                "webhooks.poll_interval_ms must be positive".to_string(),
+            ));
+        }
+        if !(1..=MAX_PAGE_SIZE).contains(&webhooks.batch_size) {
+            return Err(ConfigError::Invalid(format!(
+                "webhooks.batch_size must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+        if webhooks.lease_seconds <= 0 {
+            return Err(ConfigError::Invalid(
+                "webhooks.lease_seconds must be positive".to_string(),
+            ));
+        }
+        if webhooks.retry_base_seconds <= 0
+            || webhooks.retry_max_seconds < webhooks.retry_base_seconds
+        {
+            return Err(ConfigError::Invalid(
+                "webhooks.retry_base_seconds must be positive and at most webhooks.retry_max_seconds"
+                    .to_string(),
+            ));
+        }
+        if webhooks.max_attempts < 1 {
+            return Err(ConfigError::Invalid(
+                "webhooks.max_attempts must be at least 1".to_string(),
+            ));
+        }
+        if webhooks.timeout_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "webhooks.timeout_seconds must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_auth(&self) -> Result<(), ConfigError> {
+        let auth = &self.auth;
+        if auth.enabled && auth.api_keys.is_empty() && auth.jwt.is_none() {
+            return Err(ConfigError::Invalid(
+                "auth.enabled requires auth.api_keys or auth.jwt".to_string(),
+            ));
+        }
+
+        let mut key_hashes = HashSet::new();
+        for key in &auth.api_keys {
+            if key.client_id.trim().is_empty() {
+                return Err(ConfigError::Invalid(
+                    "auth.api_keys client_id cannot be empty".to_string(),
+                ));
+            }
+            let hash = &key.key_sha256;
+            if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ConfigError::Invalid(format!(
+                    "auth.api_keys key_sha256 for {:?} must be 64 hex characters",
+                    key.client_id
+                )));
+            }
+            if !key_hashes.insert(hash.to_lowercase()) {
+                return Err(ConfigError::Invalid(format!(
+                    "auth.api_keys key_sha256 for {:?} is used by another client",
+                    key.client_id
+                )));
+            }
+            for scope in &key.scopes {
+                scope.parse::<Scope>().map_err(|e| {
+                    ConfigError::Invalid(format!(
+                        "auth.api_keys scopes for {:?}: {}",
+                        key.client_id, e
+                    ))
+                })?;
+            }
+        }
+
+        if let Some(jwt) = &auth.jwt {
+            match jwt.algorithm {
+                JwtAlgorithm::HS256 if jwt.secret.as_deref().unwrap_or("").is_empty() => {
+                    return Err(ConfigError::Invalid(
+                        "auth.jwt.secret (AUTH_JWT_SECRET) must be set for HS256".to_string(),
+                    ));
+                }
+                JwtAlgorithm::RS256 if jwt.public_key_file.is_none() => {
+                    return Err(ConfigError::Invalid(
+                        "auth.jwt.public_key_file (AUTH_JWT_PUBLIC_KEY_FILE) must be set for RS256"
+                            .to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_env<T>(name: &'static str, value: String) -> Result<T, ConfigError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.trim().parse().map_err(|e: T::Err| ConfigError::Env {
+        name,
+        message: e.to_string(),
+        value,
+    })
+}
+
+fn override_from<T>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &'static str,
+    target: &mut T,
+) -> Result<(), ConfigError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    if let Some(value) = var(name) {
+        *target = parse_env(name, value)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_defaults_require_database_url() {
+        let config = AppConfig::default();
+
+        assert_eq!(config.server.bind_address.port(), 3000);
+        assert_eq!(config.database.max_connections, 10);
+        assert!(config.features.idempotency);
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let config = AppConfig::from_toml(
+            r#"
+            [server]
+            bind_address = "127.0.0.1:8080"
+
+            [database]
+            url = "postgres://localhost/ledger"
+            max_connections = 4
+
+            [logging]
+            format = "json"
+
+            [features]
+            idempotency = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.server.bind_address.port(), 8080);
+        assert_eq!(config.server.request_timeout_seconds, 30);
+        assert_eq!(config.database.max_connections, 4);
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert!(!config.features.idempotency);
+        assert!(config.features.idempotency_key_purge);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_memory_storage_needs_no_database_url() {
+        let mut config = AppConfig::default();
+
+        config
+            .apply_env(env(&[("STORAGE_BACKEND", "memory")]))
+            .unwrap();
+
+        assert_eq!(config.storage.backend, StorageBackend::Memory);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_from_toml_rejects_unknown_keys() {
+        assert!(AppConfig::from_toml("[server]\nport = 3000\n").is_err());
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let mut config = AppConfig::from_toml(
+            "[database]\nurl = \"postgres://file/ledger\"\nmax_connections = 4\n",
+        )
+        .unwrap();
+
+        config
+            .apply_env(env(&[
+                ("DATABASE_URL", "postgres://env/ledger"),
+                ("BIND_ADDRESS", "127.0.0.1:9000"),
+                ("LOG_FORMAT", "JSON"),
+                ("FEATURE_IDEMPOTENCY", "false"),
+            ]))
+            .unwrap();
+
+        assert_eq!(config.database.url, "postgres://env/ledger");
+        assert_eq!(config.database.max_connections, 4);
+        assert_eq!(config.server.bind_address.port(), 9000);
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert!(!config.features.idempotency);
+    }
+
+    #[test]
+    fn test_invalid_env_value() {
+        let mut config = AppConfig::default();
+
+        let result = config.apply_env(env(&[("DATABASE_MAX_CONNECTIONS", "ten")]));
+
+        assert!(matches!(
+            result,
+            Err(ConfigError::Env {
+                name: "DATABASE_MAX_CONNECTIONS",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut config = AppConfig::default();
+        config.database.url = "postgres://localhost/ledger".to_string();
+        assert!(config.validate().is_ok());
+
+        let mut too_many_idle = config.clone();
+        too_many_idle.database.min_idle = Some(20);
+        assert!(too_many_idle.validate().is_err());
+
+        let mut bad_level = config.clone();
+        bad_level.logging.level = "info,[".to_string();
+        assert!(bad_level.validate().is_err());
+
+        let mut no_ttl = config.clone();
+        no_ttl.idempotency.key_ttl_seconds = 0;
+        assert!(no_ttl.validate().is_err());
+
+        let mut no_threshold = config;
+        no_threshold
+            .apply_env(env(&[("SNAPSHOT_EVENT_THRESHOLD", "0")]))
+            .unwrap();
+        assert!(no_threshold.validate().is_err());
+    }
+
+    #[test]
+    fn test_outbox() {
+        let mut config = AppConfig::from_toml(
+            r#"
+            [database]
+            url = "postgres://localhost/ledger"
+
+            [outbox]
+            sink = "webhook"
+            webhook_url = "https://events.example.com/ledger"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.outbox.sink, OutboxSink::Webhook);
+        assert_eq!(config.outbox.batch_size, 100);
+        assert!(config.validate().is_ok());
+
+        config
+            .apply_env(env(&[
+                ("OUTBOX_SINK", "nats"),
+                ("OUTBOX_RETRY_MAX_SECONDS", "0"),
+            ]))
+            .unwrap();
+        assert_eq!(config.outbox.sink, OutboxSink::Nats);
+        assert!(config.validate().is_err());
+
+        config
+            .apply_env(env(&[
+                ("OUTBOX_NATS_ADDRESS", "nats://localhost:4222"),
+                ("OUTBOX_RETRY_MAX_SECONDS", "300"),
+            ]))
+            .unwrap();
+        assert!(config.validate().is_ok());
+
+        let mut no_file = config;
+        no_file.outbox.sink = OutboxSink::File;
+        assert!(no_file.validate().is_err());
+        assert!("kafka".parse::<OutboxSink>().is_err());
+    }
+
+    #[test]
+    fn test_webhooks() {
+        let mut config = AppConfig::from_toml(
+            r#"
+            [database]
+            url = "postgres://localhost/ledger"
+
+            [webhooks]
+            max_attempts = 5
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.webhooks.max_attempts, 5);
+        assert_eq!(config.webhooks.retry_base_seconds, 10);
+        assert!(config.features.webhook_dispatcher);
+        assert!(config.validate().is_ok());
+
+        config
+            .apply_env(env(&[
+                ("WEBHOOK_MAX_ATTEMPTS", "0"),
+                ("FEATURE_WEBHOOK_DISPATCHER", "false"),
+            ]))
+            .unwrap();
+        assert!(!config.features.webhook_dispatcher);
+        assert!(config.validate().is_err());
+
+        config
+            .apply_env(env(&[
+                ("WEBHOOK_MAX_ATTEMPTS", "3"),
+                ("WEBHOOK_RETRY_BASE_SECONDS", "7200"),
+            ]))
+            .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_auth() {
+        let config = AppConfig::from_toml(
+            r#"
+            [database]
+            url = "postgres://localhost/ledger"
+
+            [auth]
+            enabled = true
+
+            [[auth.api_keys]]
+            client_id = "billing"
+            key_sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+            scopes = ["events:write", "balances:read"]
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let mut unknown_scope = config.clone();
+        unknown_scope.auth.api_keys[0].scopes = vec!["events:delete".to_string()];
+        assert!(unknown_scope.validate().is_err());
+
+        let mut no_credentials = config.clone();
+        no_credentials.auth.api_keys.clear();
+        assert!(no_credentials.validate().is_err());
+
+        // A JWT secret from the environment is enough on its own
+        no_credentials
+            .apply_env(env(&[("AUTH_JWT_SECRET", "s3cret")]))
+            .unwrap();
+        assert_eq!(
+            no_credentials.auth.jwt.as_ref().unwrap().algorithm,
+            JwtAlgorithm::HS256
+        );
+        assert!(no_credentials.validate().is_ok());
+
+        let mut rs256_without_key = no_credentials;
+        rs256_without_key
+            .apply_env(env(&[("AUTH_JWT_ALGORITHM", "RS256")]))
+            .unwrap();
+        assert!(rs256_without_key.validate().is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::domain::errors::{DomainError, DomainResult};
-use crate::domain::value_objects::AccountNumber;
+use crate::domain::value_objects::{AccountNumber, AccountStatus, BalancePolicy, Currency};
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
@@ -8,27 +8,47 @@ pub struct Account {
     pub id: Option<i32>,
     pub account_number: AccountNumber,
     pub account_name: String,
+    pub currency: Currency,
+    pub balance_policy: BalancePolicy,
+    pub status: AccountStatus,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
 }
 
 impl Account {
-    pub fn new(account_number: AccountNumber, account_name: String) -> Self {
+    pub fn new(account_number: AccountNumber, account_name: String, currency: Currency) -> Self {
         Self {
             id: None,
             account_number,
             account_name,
+            currency,
+            balance_policy: BalancePolicy::default(),
+            status: AccountStatus::default(),
             created_at: None,
             updated_at: None,
         }
     }
 
+    pub fn with_balance_policy(mut self, balance_policy: BalancePolicy) -> Self {
+        self.balance_policy = balance_policy;
+        self
+    }
+
+    /// Fails unless ledger events may be appended to this account
+    pub fn ensure_accepts_events(&self) -> DomainResult<()> {
+        self.status
+            .ensure_accepts_events(self.id.unwrap_or_default())
+    }
+
     pub fn validate(&self) -> DomainResult<()> {
         if self.account_name.is_empty() {
             return Err(DomainError::ValidationError(
                 "Account name cannot be empty".to_string(),
             ));
         }
+        if let BalancePolicy::Overdraft { limit } = &self.balance_policy {
+            limit.ensure_currency(self.currency)?;
+        }
         Ok(())
     }
 }
@@ -36,24 +56,39 @@ impl Account {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::value_objects::Money;
 
     #[test]
     fn test_create_account() {
         let account_number = AccountNumber::new("ACC001".to_string()).unwrap();
-        let account = Account::new(account_number, "Test Account".to_string());
+        let account = Account::new(account_number, "Test Account".to_string(), Currency::EUR);
 
         assert_eq!(account.account_name, "Test Account");
+        assert_eq!(account.currency, Currency::EUR);
+        assert_eq!(account.status, AccountStatus::Active);
+        assert_eq!(account.balance_policy, BalancePolicy::NoOverdraft);
         assert!(account.id.is_none());
     }
 
     #[test]
     fn test_validate() {
         let account_number = AccountNumber::new("ACC001".to_string()).unwrap();
-        let account = Account::new(account_number.clone(), "Test Account".to_string());
+        let account = Account::new(
+            account_number.clone(),
+            "Test Account".to_string(),
+            Currency::USD,
+        );
 
         assert!(account.validate().is_ok());
 
-        let invalid_account = Account::new(account_number, "".to_string());
+        let invalid_account = Account::new(account_number.clone(), "".to_string(), Currency::USD);
         assert!(invalid_account.validate().is_err());
+
+        let mismatched_limit =
+            Account::new(account_number, "Test Account".to_string(), Currency::USD)
+                .with_balance_policy(BalancePolicy::Overdraft {
+                    limit: Money::new(500, Currency::EUR).unwrap(),
+                });
+        assert!(mismatched_limit.validate().is_err());
     }
 }
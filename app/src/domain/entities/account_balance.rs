@@ -1,4 +1,5 @@
-use crate::domain::value_objects::Money;
+use crate::domain::errors::DomainResult;
+use crate::domain::value_objects::{Balance, BalanceDelta, Currency};
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
@@ -6,19 +7,46 @@ use serde::{Deserialize, Serialize};
 pub struct AccountBalance {
     pub id: Option<i32>,
     pub account_id: i32,
-    pub balance: Money,
+    pub balance: Balance,
+    pub currency: Currency,
+    /// Id of the newest ledger event included in the balance (0 before any event).
+    /// None for snapshots taken before positions were recorded.
+    pub last_event_id: Option<i32>,
     pub snapshot_at: Option<NaiveDateTime>,
 }
 
 impl AccountBalance {
-    pub fn new(account_id: i32, balance: Money) -> Self {
+    pub fn new(account_id: i32, balance: Balance, currency: Currency) -> Self {
         Self {
             id: None,
             account_id,
             balance,
+            currency,
+            last_event_id: None,
             snapshot_at: None,
         }
     }
+
+    /// Zero balance positioned before the account's first event
+    pub fn initial(account_id: i32, currency: Currency) -> Self {
+        Self {
+            last_event_id: Some(0),
+            ..Self::new(account_id, Balance::zero(), currency)
+        }
+    }
+
+    /// Balance after applying the events summarized by `delta` on top of this one.
+    /// The result is not persisted.
+    pub fn apply(&self, delta: &BalanceDelta) -> DomainResult<AccountBalance> {
+        Ok(Self {
+            last_event_id: delta.last_event_id.or(self.last_event_id),
+            ..Self::new(
+                self.account_id,
+                self.balance.adjust(delta.amount)?,
+                self.currency,
+            )
+        })
+    }
 }
 
 #[cfg(test)]
@@ -27,12 +55,47 @@ mod tests {
 
     #[test]
     fn test_create_account_balance() {
-        let balance = Money::new(5000).unwrap();
-        let account_balance = AccountBalance::new(1, balance);
+        let balance = Balance::new(5000);
+        let account_balance = AccountBalance::new(1, balance, Currency::USD);
 
         assert_eq!(account_balance.account_id, 1);
         assert_eq!(account_balance.balance.value(), 5000);
         assert!(account_balance.id.is_none());
         assert!(account_balance.snapshot_at.is_none());
     }
+
+    #[test]
+    fn test_apply_delta() {
+        let mut snapshot = AccountBalance::new(1, Balance::new(5000), Currency::USD);
+        snapshot.id = Some(3);
+        snapshot.last_event_id = Some(10);
+
+        let balance = snapshot
+            .apply(&BalanceDelta::new(-1500, Some(14), 3))
+            .unwrap();
+
+        assert_eq!(balance.balance.value(), 3500);
+        assert_eq!(balance.last_event_id, Some(14));
+        assert!(balance.id.is_none());
+    }
+
+    #[test]
+    fn test_apply_empty_delta_keeps_position() {
+        let balance = AccountBalance::initial(1, Currency::USD)
+            .apply(&BalanceDelta::default())
+            .unwrap();
+
+        assert_eq!(balance.balance.value(), 0);
+        assert_eq!(balance.last_event_id, Some(0));
+    }
+
+    #[test]
+    fn test_apply_delta_to_negative_balance() {
+        let balance = AccountBalance::initial(1, Currency::USD)
+            .apply(&BalanceDelta::new(-2500, Some(3), 2))
+            .unwrap();
+
+        assert_eq!(balance.balance.value(), -2500);
+        assert!(balance.balance.is_negative());
+    }
 }
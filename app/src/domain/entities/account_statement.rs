@@ -0,0 +1,142 @@
+use crate::domain::entities::{EventType, LedgerEvent};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::{Balance, Currency};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Activity of an account over `[from, to)`, from the balance just before `from`
+/// to the balance just before `to`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountStatement {
+    pub account_id: i32,
+    pub currency: Currency,
+    pub from: NaiveDateTime,
+    pub to: NaiveDateTime,
+    pub opening_balance: Balance,
+    pub lines: Vec<StatementLine>,
+    pub total_debits: i64,
+    pub total_credits: i64,
+    pub closing_balance: Balance,
+}
+
+/// Event of the statement with the account balance right after it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatementLine {
+    pub event: LedgerEvent,
+    pub running_balance: Balance,
+}
+
+impl AccountStatement {
+    pub fn new(
+        account_id: i32,
+        currency: Currency,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        opening_balance: Balance,
+    ) -> Self {
+        Self {
+            account_id,
+            currency,
+            from,
+            to,
+            opening_balance,
+            lines: Vec::new(),
+            total_debits: 0,
+            total_credits: 0,
+            closing_balance: opening_balance,
+        }
+    }
+
+    /// Appends the next event of the period; events must be pushed in ledger order
+    pub fn push(&mut self, event: LedgerEvent) -> DomainResult<()> {
+        if event.account_id != self.account_id {
+            return Err(DomainError::ValidationError(format!(
+                "Event {:?} belongs to account {}, not {}",
+                event.id, event.account_id, self.account_id
+            )));
+        }
+
+        let overflow = || DomainError::ValidationError("Statement total overflow".to_string());
+        let amount = event.amount.value();
+        let running_balance = match event.event_type {
+            EventType::Credit => {
+                self.total_credits = self
+                    .total_credits
+                    .checked_add(amount)
+                    .ok_or_else(overflow)?;
+                self.closing_balance.credit(&event.amount)?
+            }
+            EventType::Debit => {
+                self.total_debits = self.total_debits.checked_add(amount).ok_or_else(overflow)?;
+                self.closing_balance.debit(&event.amount)?
+            }
+        };
+
+        self.closing_balance = running_balance;
+        self.lines.push(StatementLine {
+            event,
+            running_balance,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Money;
+    use chrono::NaiveDate;
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 3, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn event(account_id: i32, event_type: EventType, amount: i64) -> LedgerEvent {
+        LedgerEvent::new(
+            account_id,
+            event_type,
+            Money::new(amount, Currency::USD).unwrap(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_running_balance_and_totals() {
+        let mut statement =
+            AccountStatement::new(1, Currency::USD, at(1), at(31), Balance::new(1000));
+
+        statement.push(event(1, EventType::Credit, 500)).unwrap();
+        statement.push(event(1, EventType::Debit, 2000)).unwrap();
+        statement.push(event(1, EventType::Credit, 300)).unwrap();
+
+        let running: Vec<_> = statement
+            .lines
+            .iter()
+            .map(|line| line.running_balance.value())
+            .collect();
+        assert_eq!(running, vec![1500, -500, -200]);
+        assert_eq!(statement.total_credits, 800);
+        assert_eq!(statement.total_debits, 2000);
+        assert_eq!(statement.opening_balance.value(), 1000);
+        assert_eq!(statement.closing_balance.value(), -200);
+    }
+
+    #[test]
+    fn test_empty_statement_closes_at_opening_balance() {
+        let statement = AccountStatement::new(1, Currency::USD, at(1), at(31), Balance::new(750));
+
+        assert!(statement.lines.is_empty());
+        assert_eq!(statement.closing_balance.value(), 750);
+    }
+
+    #[test]
+    fn test_rejects_event_of_another_account() {
+        let mut statement = AccountStatement::new(1, Currency::USD, at(1), at(31), Balance::zero());
+
+        assert!(statement.push(event(2, EventType::Credit, 100)).is_err());
+        assert!(statement.lines.is_empty());
+    }
+}
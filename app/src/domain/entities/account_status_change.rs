@@ -0,0 +1,85 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::AccountStatus;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Audit record of an account moving from one status to another, with who made
+/// the change and why. Records are append-only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountStatusChange {
+    pub id: Option<i32>,
+    pub account_id: i32,
+    pub from_status: AccountStatus,
+    pub to_status: AccountStatus,
+    pub actor: String,
+    pub reason: String,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl AccountStatusChange {
+    pub fn new(
+        account_id: i32,
+        from_status: AccountStatus,
+        to_status: AccountStatus,
+        actor: String,
+        reason: String,
+    ) -> Self {
+        Self {
+            id: None,
+            account_id,
+            from_status,
+            to_status,
+            actor,
+            reason,
+            created_at: None,
+        }
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.actor.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Actor cannot be empty".to_string(),
+            ));
+        }
+        if self.reason.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Reason cannot be empty".to_string(),
+            ));
+        }
+        self.from_status
+            .ensure_can_transition_to(self.to_status, self.account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let change = AccountStatusChange::new(
+            1,
+            AccountStatus::Active,
+            AccountStatus::Frozen,
+            "ops@example.com".to_string(),
+            "Suspected fraud".to_string(),
+        );
+        assert!(change.validate().is_ok());
+
+        let mut missing_actor = change.clone();
+        missing_actor.actor = " ".to_string();
+        assert!(missing_actor.validate().is_err());
+
+        let mut missing_reason = change.clone();
+        missing_reason.reason = String::new();
+        assert!(missing_reason.validate().is_err());
+
+        let mut reopen = change;
+        reopen.from_status = AccountStatus::Closed;
+        reopen.to_status = AccountStatus::Active;
+        assert!(matches!(
+            reopen.validate(),
+            Err(DomainError::InvalidStatusTransition { .. })
+        ));
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Stored outcome of a write request sent with an `Idempotency-Key` header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    pub key: String,
+    /// Hash of the request method, path and body the key was first used with
+    pub request_fingerprint: String,
+    /// None while the original request is still being processed
+    pub response_status: Option<u16>,
+    pub response_body: Option<String>,
+    /// Content-Type the response was sent with; None for responses stored without one
+    pub response_content_type: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+impl IdempotencyRecord {
+    pub fn is_completed(&self) -> bool {
+        self.response_status.is_some()
+    }
+
+    pub fn matches(&self, request_fingerprint: &str) -> bool {
+        self.request_fingerprint == request_fingerprint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(response_status: Option<u16>) -> IdempotencyRecord {
+        let now = chrono::Utc::now().naive_utc();
+        IdempotencyRecord {
+            key: "key-1".to_string(),
+            request_fingerprint: "abc".to_string(),
+            response_status,
+            response_body: response_status.map(|_| "{}".to_string()),
+            response_content_type: response_status.map(|_| "application/json".to_string()),
+            created_at: now,
+            expires_at: now + chrono::Duration::hours(24),
+        }
+    }
+
+    #[test]
+    fn test_is_completed() {
+        assert!(!record(None).is_completed());
+        assert!(record(Some(200)).is_completed());
+    }
+
+    #[test]
+    fn test_matches() {
+        let record = record(Some(200));
+
+        assert!(record.matches("abc"));
+        assert!(!record.matches("def"));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::domain::entities::{EventType, LedgerEvent};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::Currency;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Double-entry posting grouping several ledger event legs that are booked
+/// together. The DEBIT legs must add up to the CREDIT legs in a single currency,
+/// so the entry as a whole sums to zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: Option<i32>,
+    pub description: Option<String>,
+    pub legs: Vec<LedgerEvent>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl JournalEntry {
+    pub fn new(description: Option<String>, legs: Vec<LedgerEvent>) -> Self {
+        Self {
+            id: None,
+            description,
+            legs,
+            created_at: None,
+        }
+    }
+
+    /// Currency every leg is booked in, taken from the first leg
+    pub fn currency(&self) -> Option<Currency> {
+        self.legs.first().map(|leg| leg.amount.currency())
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.legs.len() < 2 {
+            return Err(DomainError::ValidationError(
+                "A journal entry needs at least two legs".to_string(),
+            ));
+        }
+
+        let currency = self.legs[0].amount.currency();
+        let mut debits: i64 = 0;
+        let mut credits: i64 = 0;
+        for leg in &self.legs {
+            leg.validate()?;
+            leg.amount.ensure_currency(currency)?;
+
+            let total = match leg.event_type {
+                EventType::Debit => &mut debits,
+                EventType::Credit => &mut credits,
+            };
+            *total = total
+                .checked_add(leg.amount.value())
+                .ok_or_else(|| DomainError::ValidationError("Amount overflow".to_string()))?;
+        }
+
+        if debits != credits {
+            return Err(DomainError::UnbalancedJournalEntry { debits, credits });
+        }
+        Ok(())
+    }
+
+    /// Legs linked to this entry's id, ready to be appended to the ledger
+    pub fn linked_legs(&self) -> Vec<LedgerEvent> {
+        self.legs
+            .iter()
+            .map(|leg| {
+                let mut leg = leg.clone();
+                leg.journal_entry_id = self.id;
+                leg
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Money;
+
+    fn leg(account_id: i32, event_type: EventType, amount: i64) -> LedgerEvent {
+        LedgerEvent::new(
+            account_id,
+            event_type,
+            Money::new(amount, Currency::USD).unwrap(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_balanced_entry() {
+        // Customer pays 1000, merchant receives 970, fee account 30
+        let entry = JournalEntry::new(
+            Some("Card settlement".to_string()),
+            vec![
+                leg(1, EventType::Debit, 1000),
+                leg(2, EventType::Credit, 970),
+                leg(3, EventType::Credit, 30),
+            ],
+        );
+
+        assert!(entry.validate().is_ok());
+        assert_eq!(entry.currency(), Some(Currency::USD));
+    }
+
+    #[test]
+    fn test_unbalanced_entry() {
+        let entry = JournalEntry::new(
+            None,
+            vec![
+                leg(1, EventType::Debit, 1000),
+                leg(2, EventType::Credit, 900),
+            ],
+        );
+
+        assert!(matches!(
+            entry.validate(),
+            Err(DomainError::UnbalancedJournalEntry {
+                debits: 1000,
+                credits: 900
+            })
+        ));
+    }
+
+    #[test]
+    fn test_requires_two_legs_in_one_currency() {
+        let single = JournalEntry::new(None, vec![leg(1, EventType::Debit, 1000)]);
+        assert!(single.validate().is_err());
+
+        let mut eur_leg = leg(2, EventType::Credit, 1000);
+        eur_leg.amount = Money::new(1000, Currency::EUR).unwrap();
+        let mixed = JournalEntry::new(None, vec![leg(1, EventType::Debit, 1000), eur_leg]);
+        assert!(matches!(
+            mixed.validate(),
+            Err(DomainError::CurrencyMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_linked_legs() {
+        let mut entry = JournalEntry::new(
+            None,
+            vec![
+                leg(1, EventType::Debit, 500),
+                leg(2, EventType::Credit, 500),
+            ],
+        );
+        entry.id = Some(7);
+
+        assert!(entry
+            .linked_legs()
+            .iter()
+            .all(|leg| leg.journal_entry_id == Some(7)));
+    }
+}
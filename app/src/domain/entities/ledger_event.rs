@@ -21,12 +21,25 @@ impl EventType {
         }
     }
 
-    pub fn to_string(&self) -> String {
+    pub fn as_str(&self) -> &str {
         match self {
-            EventType::Debit => "DEBIT".to_string(),
-            EventType::Credit => "CREDIT".to_string(),
+            EventType::Debit => "DEBIT",
+            EventType::Credit => "CREDIT",
         }
     }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            EventType::Debit => EventType::Credit,
+            EventType::Credit => EventType::Debit,
+        }
+    }
+}
+
+impl std::fmt::Display for EventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +49,18 @@ pub struct LedgerEvent {
     pub event_type: EventType,
     pub amount: Money,
     pub description: Option<String>,
+    pub transfer_id: Option<i32>,
+    pub journal_entry_id: Option<i32>,
+    /// Event this one compensates, for reversal events
+    pub reverses_event_id: Option<i32>,
+    /// Reversal event compensating this one, filled in when the event is read
+    pub reversed_by_event_id: Option<i32>,
+    /// Authenticated client that recorded the event, kept for audit
+    pub recorded_by: Option<String>,
+    /// Position of the event in its account's stream, starting at 1
+    pub version: Option<i64>,
+    /// Position of the event across all accounts, unique and increasing
+    pub sequence: Option<i64>,
     pub created_at: Option<NaiveDateTime>,
 }
 
@@ -52,10 +77,22 @@ impl LedgerEvent {
             event_type,
             amount,
             description,
+            transfer_id: None,
+            journal_entry_id: None,
+            reverses_event_id: None,
+            reversed_by_event_id: None,
+            recorded_by: None,
+            version: None,
+            sequence: None,
             created_at: None,
         }
     }
 
+    pub fn with_recorded_by(mut self, recorded_by: Option<String>) -> Self {
+        self.recorded_by = recorded_by;
+        self
+    }
+
     pub fn new_debit(account_id: i32, amount: Money, description: Option<String>) -> Self {
         Self::new(account_id, EventType::Debit, amount, description)
     }
@@ -72,15 +109,61 @@ impl LedgerEvent {
         }
         Ok(())
     }
+
+    /// Compensating event cancelling this one: same account and amount, opposite type.
+    ///
+    /// Reversals cannot themselves be reversed, and legs of transfers and journal
+    /// entries cannot be reversed on their own since that would unbalance them.
+    pub fn reversal(&self, description: Option<String>) -> DomainResult<LedgerEvent> {
+        let event_id = self.id.ok_or_else(|| {
+            DomainError::ValidationError("Cannot reverse an unsaved event".to_string())
+        })?;
+
+        if let Some(reversal_event_id) = self.reversed_by_event_id {
+            return Err(DomainError::EventAlreadyReversed {
+                event_id,
+                reversal_event_id,
+            });
+        }
+        if self.reverses_event_id.is_some() {
+            return Err(DomainError::ValidationError(format!(
+                "Event {} is a reversal and cannot be reversed",
+                event_id
+            )));
+        }
+        if let Some(transfer_id) = self.transfer_id {
+            return Err(DomainError::ValidationError(format!(
+                "Event {} is a leg of transfer {} and cannot be reversed on its own",
+                event_id, transfer_id
+            )));
+        }
+        if let Some(journal_entry_id) = self.journal_entry_id {
+            return Err(DomainError::ValidationError(format!(
+                "Event {} is a leg of journal entry {} and cannot be reversed on its own",
+                event_id, journal_entry_id
+            )));
+        }
+
+        let description = description.or_else(|| Some(format!("Reversal of event {}", event_id)));
+        let mut reversal = LedgerEvent::new(
+            self.account_id,
+            self.event_type.opposite(),
+            self.amount,
+            description,
+        );
+        reversal.reverses_event_id = Some(event_id);
+        Ok(reversal)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::value_objects::Currency;
 
     #[test]
     fn test_create_debit_event() {
-        let amount = Money::new(1000).unwrap();
+        let amount = Money::new(1000, Currency::USD).unwrap();
         let event = LedgerEvent::new_debit(1, amount, Some("Test debit".to_string()));
 
         assert_eq!(event.account_id, 1);
@@ -90,7 +173,7 @@ mod tests {
 
     #[test]
     fn test_create_credit_event() {
-        let amount = Money::new(500).unwrap();
+        let amount = Money::new(500, Currency::USD).unwrap();
         let event = LedgerEvent::new_credit(2, amount, Some("Test credit".to_string()));
 
         assert_eq!(event.account_id, 2);
@@ -100,14 +183,8 @@ mod tests {
 
     #[test]
     fn test_event_type_from_string() {
-        assert_eq!(
-            EventType::from_string("DEBIT").unwrap(),
-            EventType::Debit
-        );
-        assert_eq!(
-            EventType::from_string("CREDIT").unwrap(),
-            EventType::Credit
-        );
+        assert_eq!(EventType::from_string("DEBIT").unwrap(), EventType::Debit);
+        assert_eq!(EventType::from_string("CREDIT").unwrap(), EventType::Credit);
         assert!(EventType::from_string("INVALID").is_err());
     }
 
@@ -119,8 +196,47 @@ mod tests {
 
     #[test]
     fn test_validate() {
-        let amount = Money::new(1000).unwrap();
+        let amount = Money::new(1000, Currency::USD).unwrap();
         let event = LedgerEvent::new_debit(1, amount, None);
         assert!(event.validate().is_ok());
     }
+
+    #[test]
+    fn test_reversal() {
+        let amount = Money::new(1000, Currency::USD).unwrap();
+        let mut event = LedgerEvent::new_credit(1, amount, Some("Deposit".to_string()));
+        event.id = Some(5);
+
+        let reversal = event.reversal(None).unwrap();
+        assert_eq!(reversal.account_id, 1);
+        assert_eq!(reversal.event_type, EventType::Debit);
+        assert_eq!(reversal.amount, amount);
+        assert_eq!(reversal.reverses_event_id, Some(5));
+        assert_eq!(reversal.description.as_deref(), Some("Reversal of event 5"));
+    }
+
+    #[test]
+    fn test_reversal_rejected() {
+        let amount = Money::new(1000, Currency::USD).unwrap();
+        let mut event = LedgerEvent::new_credit(1, amount, None);
+        assert!(event.reversal(None).is_err());
+
+        event.id = Some(5);
+        event.reversed_by_event_id = Some(6);
+        assert!(matches!(
+            event.reversal(None),
+            Err(DomainError::EventAlreadyReversed {
+                event_id: 5,
+                reversal_event_id: 6
+            })
+        ));
+
+        event.reversed_by_event_id = None;
+        event.reverses_event_id = Some(4);
+        assert!(event.reversal(None).is_err());
+
+        event.reverses_event_id = None;
+        event.transfer_id = Some(2);
+        assert!(event.reversal(None).is_err());
+    }
 }
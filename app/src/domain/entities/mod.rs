@@ -1,9 +1,23 @@
 pub mod account;
 pub mod account_balance;
+pub mod account_statement;
+pub mod account_status_change;
+pub mod idempotency_record;
+pub mod journal_entry;
 pub mod ledger_event;
-pub mod transaction;
+pub mod outbox_message;
+pub mod transfer;
+pub mod webhook_delivery;
+pub mod webhook_subscription;
 
 pub use account::Account;
 pub use account_balance::AccountBalance;
+pub use account_statement::{AccountStatement, StatementLine};
+pub use account_status_change::AccountStatusChange;
+pub use idempotency_record::IdempotencyRecord;
+pub use journal_entry::JournalEntry;
 pub use ledger_event::{EventType, LedgerEvent};
-pub use transaction::Transaction;
+pub use outbox_message::{OutboxMessage, OutboxStatus, ACCOUNT_CREATED, LEDGER_EVENT_CREATED};
+pub use transfer::Transfer;
+pub use webhook_delivery::{WebhookDelivery, WebhookDeliveryStatus};
+pub use webhook_subscription::{WebhookSubscription, BALANCE_BELOW_THRESHOLD, WEBHOOK_EVENT_TYPES};
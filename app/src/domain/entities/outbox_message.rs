@@ -0,0 +1,208 @@
+use crate::domain::entities::{Account, LedgerEvent};
+use crate::domain::errors::{DomainError, DomainResult};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Topic of the message written for every appended ledger event
+pub const LEDGER_EVENT_CREATED: &str = "ledger_event.created";
+
+/// Topic of the message written for every opened account
+pub const ACCOUNT_CREATED: &str = "account.created";
+
+/// Delivery state of an outbox message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutboxStatus {
+    /// Not published yet and never attempted
+    Pending,
+    /// Not published yet, with at least one failed attempt
+    Retrying,
+    Published,
+}
+
+impl OutboxStatus {
+    pub fn from_string(s: &str) -> DomainResult<Self> {
+        match s.to_uppercase().as_str() {
+            "PENDING" => Ok(OutboxStatus::Pending),
+            "RETRYING" => Ok(OutboxStatus::Retrying),
+            "PUBLISHED" => Ok(OutboxStatus::Published),
+            _ => Err(DomainError::ValidationError(format!(
+                "Invalid outbox status: {}",
+                s
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            OutboxStatus::Pending => "PENDING",
+            OutboxStatus::Retrying => "RETRYING",
+            OutboxStatus::Published => "PUBLISHED",
+        }
+    }
+}
+
+/// Message recorded in the same transaction as the change it describes and
+/// published to downstream consumers afterwards, at least once.
+///
+/// Consumers may see a message more than once and should deduplicate on its id.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutboxMessage {
+    pub id: Option<i32>,
+    pub topic: String,
+    /// Partitioning key; messages with the same key describe the same aggregate
+    pub key: String,
+    pub payload: serde_json::Value,
+    /// Delivery attempts made so far, successful or not
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// Earliest time the relay picks the message up (again)
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub published_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl OutboxMessage {
+    pub fn new(topic: &str, key: String, payload: serde_json::Value) -> Self {
+        Self {
+            id: None,
+            topic: topic.to_string(),
+            key,
+            payload,
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: None,
+            published_at: None,
+            created_at: None,
+        }
+    }
+
+    /// Message announcing a saved ledger event, keyed by its account so that
+    /// consumers can keep each account's events in version order
+    pub fn ledger_event_created(event: &LedgerEvent) -> Self {
+        Self::new(
+            LEDGER_EVENT_CREATED,
+            event.account_id.to_string(),
+            json!({
+                "id": event.id,
+                "account_id": event.account_id,
+                "event_type": event.event_type.to_string(),
+                "amount": event.amount.value(),
+                "currency": event.amount.currency().code(),
+                "description": event.description,
+                "transfer_id": event.transfer_id,
+                "journal_entry_id": event.journal_entry_id,
+                "reverses_event_id": event.reverses_event_id,
+                "recorded_by": event.recorded_by,
+                "version": event.version,
+                "sequence": event.sequence,
+                "created_at": event.created_at
+            }),
+        )
+    }
+
+    /// Message announcing a saved account, keyed by its id
+    pub fn account_created(account: &Account) -> Self {
+        let id = account.id.unwrap_or_default();
+        Self::new(
+            ACCOUNT_CREATED,
+            id.to_string(),
+            json!({
+                "id": account.id,
+                "account_number": account.account_number.value(),
+                "account_name": account.account_name,
+                "currency": account.currency.code(),
+                "status": account.status.as_str(),
+                "balance_policy": account.balance_policy.as_str(),
+                "overdraft_limit": account.balance_policy.overdraft_limit(),
+                "created_at": account.created_at
+            }),
+        )
+    }
+
+    /// Account the message is about; every topic so far is keyed by account id
+    pub fn account_id(&self) -> Option<i32> {
+        self.key.parse().ok()
+    }
+
+    pub fn status(&self) -> OutboxStatus {
+        match (self.published_at, self.attempts) {
+            (Some(_), _) => OutboxStatus::Published,
+            (None, 0) => OutboxStatus::Pending,
+            (None, _) => OutboxStatus::Retrying,
+        }
+    }
+
+    /// What sinks deliver: the payload wrapped with the message id, topic and key
+    pub fn envelope(&self) -> serde_json::Value {
+        json!({
+            "id": self.id,
+            "topic": self.topic,
+            "key": self.key,
+            "payload": self.payload,
+            "created_at": self.created_at
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{AccountNumber, Currency, Money};
+
+    #[test]
+    fn test_ledger_event_created_message() {
+        let mut event = LedgerEvent::new_credit(
+            7,
+            Money::new(2500, Currency::EUR).unwrap(),
+            Some("Deposit".to_string()),
+        );
+        event.id = Some(42);
+        event.version = Some(3);
+
+        let message = OutboxMessage::ledger_event_created(&event);
+
+        assert_eq!(message.topic, LEDGER_EVENT_CREATED);
+        assert_eq!(message.key, "7");
+        assert_eq!(message.payload["id"], 42);
+        assert_eq!(message.payload["event_type"], "CREDIT");
+        assert_eq!(message.payload["amount"], 2500);
+        assert_eq!(message.payload["currency"], "EUR");
+        assert_eq!(message.payload["version"], 3);
+        assert_eq!(message.account_id(), Some(7));
+        assert_eq!(message.status(), OutboxStatus::Pending);
+    }
+
+    #[test]
+    fn test_account_created_message() {
+        let mut account = Account::new(
+            AccountNumber::new("ACC001".to_string()).unwrap(),
+            "Savings".to_string(),
+            Currency::EUR,
+        );
+        account.id = Some(9);
+
+        let message = OutboxMessage::account_created(&account);
+
+        assert_eq!(message.topic, ACCOUNT_CREATED);
+        assert_eq!(message.account_id(), Some(9));
+        assert_eq!(message.payload["account_number"], "ACC001");
+        assert_eq!(message.payload["currency"], "EUR");
+        assert_eq!(message.payload["status"], "ACTIVE");
+    }
+
+    #[test]
+    fn test_status_follows_attempts() {
+        let mut message = OutboxMessage::new("test", "1".to_string(), json!({}));
+        message.attempts = 2;
+        assert_eq!(message.status(), OutboxStatus::Retrying);
+
+        message.published_at = Some(chrono::Utc::now().naive_utc());
+        assert_eq!(message.status(), OutboxStatus::Published);
+        assert_eq!(
+            OutboxStatus::from_string("retrying").unwrap(),
+            OutboxStatus::Retrying
+        );
+        assert!(OutboxStatus::from_string("lost").is_err());
+    }
+}
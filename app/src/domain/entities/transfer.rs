@@ -0,0 +1,135 @@
+use crate::domain::entities::{EventType, LedgerEvent};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::Money;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Movement of funds between two accounts, recorded as a DEBIT on the source
+/// account and a CREDIT on the destination account sharing the transfer id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transfer {
+    pub id: Option<i32>,
+    pub from_account_id: i32,
+    pub to_account_id: i32,
+    pub amount: Money,
+    pub description: Option<String>,
+    pub debit_event: Option<LedgerEvent>,
+    pub credit_event: Option<LedgerEvent>,
+    /// Authenticated client that requested the transfer, recorded on both legs
+    pub recorded_by: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl Transfer {
+    pub fn new(
+        from_account_id: i32,
+        to_account_id: i32,
+        amount: Money,
+        description: Option<String>,
+    ) -> Self {
+        Self {
+            id: None,
+            from_account_id,
+            to_account_id,
+            amount,
+            description,
+            debit_event: None,
+            credit_event: None,
+            recorded_by: None,
+            created_at: None,
+        }
+    }
+
+    pub fn with_recorded_by(mut self, recorded_by: Option<String>) -> Self {
+        self.recorded_by = recorded_by;
+        self
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.amount.value() <= 0 {
+            return Err(DomainError::ValidationError(
+                "Amount must be positive".to_string(),
+            ));
+        }
+        if self.from_account_id == self.to_account_id {
+            return Err(DomainError::ValidationError(
+                "Cannot transfer to the same account".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// DEBIT leg on the source account
+    pub fn debit_leg(&self) -> LedgerEvent {
+        self.leg(self.from_account_id, EventType::Debit)
+    }
+
+    /// CREDIT leg on the destination account
+    pub fn credit_leg(&self) -> LedgerEvent {
+        self.leg(self.to_account_id, EventType::Credit)
+    }
+
+    fn leg(&self, account_id: i32, event_type: EventType) -> LedgerEvent {
+        let mut event = LedgerEvent::new(
+            account_id,
+            event_type,
+            self.amount,
+            self.description.clone(),
+        );
+        event.transfer_id = self.id;
+        event.recorded_by = self.recorded_by.clone();
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Currency;
+
+    #[test]
+    fn test_create_transfer() {
+        let amount = Money::new(750, Currency::USD).unwrap();
+        let transfer = Transfer::new(1, 2, amount, Some("Payment".to_string()));
+
+        assert_eq!(transfer.from_account_id, 1);
+        assert_eq!(transfer.to_account_id, 2);
+        assert_eq!(transfer.amount.value(), 750);
+        assert!(transfer.id.is_none());
+        assert!(transfer.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_same_account() {
+        let amount = Money::new(750, Currency::USD).unwrap();
+        let transfer = Transfer::new(1, 1, amount, None);
+
+        assert!(transfer.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_zero_amount() {
+        let transfer = Transfer::new(1, 2, Money::zero(Currency::USD), None);
+
+        assert!(transfer.validate().is_err());
+    }
+
+    #[test]
+    fn test_legs() {
+        let amount = Money::new(750, Currency::USD).unwrap();
+        let mut transfer = Transfer::new(1, 2, amount, Some("Payment".to_string()));
+        transfer.id = Some(10);
+
+        let debit = transfer.debit_leg();
+        assert_eq!(debit.account_id, 1);
+        assert_eq!(debit.event_type, EventType::Debit);
+        assert_eq!(debit.amount.value(), 750);
+        assert_eq!(debit.transfer_id, Some(10));
+
+        let credit = transfer.credit_leg();
+        assert_eq!(credit.account_id, 2);
+        assert_eq!(credit.event_type, EventType::Credit);
+        assert_eq!(credit.amount.value(), 750);
+        assert_eq!(credit.transfer_id, Some(10));
+    }
+}
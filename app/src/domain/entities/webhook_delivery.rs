@@ -0,0 +1,249 @@
+use crate::domain::entities::{
+    EventType, LedgerEvent, OutboxMessage, WebhookSubscription, BALANCE_BELOW_THRESHOLD,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Delivery state of a webhook notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookDeliveryStatus {
+    /// Not sent yet and never attempted
+    Pending,
+    /// Not sent yet, with at least one failed attempt
+    Retrying,
+    Delivered,
+    /// Given up on after the last allowed attempt failed; can be replayed
+    DeadLettered,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn from_string(s: &str) -> DomainResult<Self> {
+        match s.to_uppercase().as_str() {
+            "PENDING" => Ok(WebhookDeliveryStatus::Pending),
+            "RETRYING" => Ok(WebhookDeliveryStatus::Retrying),
+            "DELIVERED" => Ok(WebhookDeliveryStatus::Delivered),
+            "DEAD_LETTERED" => Ok(WebhookDeliveryStatus::DeadLettered),
+            _ => Err(DomainError::ValidationError(format!(
+                "Invalid webhook delivery status: {}",
+                s
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            WebhookDeliveryStatus::Pending => "PENDING",
+            WebhookDeliveryStatus::Retrying => "RETRYING",
+            WebhookDeliveryStatus::Delivered => "DELIVERED",
+            WebhookDeliveryStatus::DeadLettered => "DEAD_LETTERED",
+        }
+    }
+}
+
+/// One notification of one subscription, written in the same transaction as the
+/// outbox message it derives from and sent to the subscription's URL at least once
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookDelivery {
+    pub id: Option<i32>,
+    pub subscription_id: i32,
+    pub outbox_message_id: i32,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// Attempts made so far, successful or not; reset by a replay
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub delivered_at: Option<NaiveDateTime>,
+    pub dead_lettered_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl WebhookDelivery {
+    pub fn new(
+        subscription_id: i32,
+        outbox_message_id: i32,
+        event_type: &str,
+        payload: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: None,
+            subscription_id,
+            outbox_message_id,
+            event_type: event_type.to_string(),
+            payload,
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: None,
+            delivered_at: None,
+            dead_lettered_at: None,
+            created_at: None,
+        }
+    }
+
+    /// Deliveries of a saved outbox message to the subscriptions matching its topic
+    /// and account
+    pub fn for_message(
+        subscriptions: &[WebhookSubscription],
+        message_id: i32,
+        message: &OutboxMessage,
+    ) -> Vec<Self> {
+        subscriptions
+            .iter()
+            .filter(|subscription| subscription.matches(&message.topic, message.account_id()))
+            .filter_map(|subscription| subscription.id)
+            .map(|subscription_id| {
+                Self::new(
+                    subscription_id,
+                    message_id,
+                    &message.topic,
+                    message.payload.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// `balance.below_threshold` deliveries for the subscriptions whose threshold a
+    /// saved ledger event crossed, taking the account's balance to `balance`
+    pub fn for_balance_change(
+        subscriptions: &[WebhookSubscription],
+        message_id: i32,
+        event: &LedgerEvent,
+        balance: i64,
+    ) -> Vec<Self> {
+        let previous_balance = match event.event_type {
+            EventType::Credit => balance - event.amount.value(),
+            EventType::Debit => balance + event.amount.value(),
+        };
+
+        subscriptions
+            .iter()
+            .filter(|subscription| {
+                subscription.matches(BALANCE_BELOW_THRESHOLD, Some(event.account_id))
+                    && subscription.crossed_below(previous_balance, balance)
+            })
+            .filter_map(|subscription| {
+                let payload = json!({
+                    "account_id": event.account_id,
+                    "balance": balance,
+                    "previous_balance": previous_balance,
+                    "threshold": subscription.balance_threshold,
+                    "currency": event.amount.currency().code(),
+                    "ledger_event_id": event.id,
+                    "version": event.version
+                });
+                subscription
+                    .id
+                    .map(|id| Self::new(id, message_id, BALANCE_BELOW_THRESHOLD, payload))
+            })
+            .collect()
+    }
+
+    pub fn status(&self) -> WebhookDeliveryStatus {
+        match (self.delivered_at, self.dead_lettered_at, self.attempts) {
+            (Some(_), _, _) => WebhookDeliveryStatus::Delivered,
+            (None, Some(_), _) => WebhookDeliveryStatus::DeadLettered,
+            (None, None, 0) => WebhookDeliveryStatus::Pending,
+            (None, None, _) => WebhookDeliveryStatus::Retrying,
+        }
+    }
+
+    /// Fails unless the delivery is dead-lettered or delivered; pending and retrying
+    /// deliveries are already scheduled
+    pub fn ensure_replayable(&self) -> DomainResult<()> {
+        match self.status() {
+            WebhookDeliveryStatus::DeadLettered | WebhookDeliveryStatus::Delivered => Ok(()),
+            status => Err(DomainError::DeliveryNotReplayable {
+                delivery_id: self.id.unwrap_or_default(),
+                status: status.as_str().to_string(),
+            }),
+        }
+    }
+
+    /// JSON body POSTed to the subscriber; its id stays the same across retries
+    /// and replays, so receivers can deduplicate on it
+    pub fn body(&self) -> serde_json::Value {
+        json!({
+            "id": self.id,
+            "type": self.event_type,
+            "created_at": self.created_at,
+            "data": self.payload
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{ACCOUNT_CREATED, LEDGER_EVENT_CREATED};
+    use crate::domain::value_objects::{Currency, Money};
+
+    fn subscription(id: i32, event_type: &str) -> WebhookSubscription {
+        let mut subscription = WebhookSubscription::new(
+            "https://hooks.example.com".to_string(),
+            vec![event_type.to_string()],
+            "whsec_test".to_string(),
+        );
+        subscription.id = Some(id);
+        subscription
+    }
+
+    #[test]
+    fn test_for_message_fans_out_to_matching_subscriptions() {
+        let subscriptions = vec![
+            subscription(1, LEDGER_EVENT_CREATED),
+            subscription(2, ACCOUNT_CREATED),
+            subscription(3, LEDGER_EVENT_CREATED).with_account(Some(8)),
+            subscription(4, LEDGER_EVENT_CREATED).with_account(Some(7)),
+        ];
+        let message = OutboxMessage::new(LEDGER_EVENT_CREATED, "7".to_string(), json!({"id": 1}));
+
+        let deliveries = WebhookDelivery::for_message(&subscriptions, 50, &message);
+
+        let ids: Vec<i32> = deliveries.iter().map(|d| d.subscription_id).collect();
+        assert_eq!(ids, vec![1, 4]);
+        assert_eq!(deliveries[0].outbox_message_id, 50);
+        assert_eq!(deliveries[0].payload["id"], 1);
+        assert_eq!(deliveries[0].status(), WebhookDeliveryStatus::Pending);
+    }
+
+    #[test]
+    fn test_for_balance_change_fires_when_crossing_below() {
+        let subscriptions = vec![
+            subscription(1, BALANCE_BELOW_THRESHOLD).with_balance_threshold(Some(1000)),
+            subscription(2, BALANCE_BELOW_THRESHOLD).with_balance_threshold(Some(200)),
+        ];
+        let mut debit = LedgerEvent::new_debit(7, Money::new(500, Currency::USD).unwrap(), None);
+        debit.id = Some(30);
+
+        // 1200 -> 700 crosses 1000 but not 200
+        let deliveries = WebhookDelivery::for_balance_change(&subscriptions, 51, &debit, 700);
+
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].subscription_id, 1);
+        assert_eq!(deliveries[0].event_type, BALANCE_BELOW_THRESHOLD);
+        assert_eq!(deliveries[0].payload["previous_balance"], 1200);
+        assert_eq!(deliveries[0].payload["threshold"], 1000);
+        assert_eq!(deliveries[0].payload["ledger_event_id"], 30);
+
+        let credit = LedgerEvent::new_credit(7, Money::new(500, Currency::USD).unwrap(), None);
+        assert!(WebhookDelivery::for_balance_change(&subscriptions, 52, &credit, 100).is_empty());
+    }
+
+    #[test]
+    fn test_status_and_replay() {
+        let mut delivery = WebhookDelivery::new(1, 2, ACCOUNT_CREATED, json!({}));
+        delivery.attempts = 3;
+        assert_eq!(delivery.status(), WebhookDeliveryStatus::Retrying);
+        assert!(delivery.ensure_replayable().is_err());
+
+        delivery.dead_lettered_at = Some(chrono::Utc::now().naive_utc());
+        assert_eq!(delivery.status(), WebhookDeliveryStatus::DeadLettered);
+        assert!(delivery.ensure_replayable().is_ok());
+        assert_eq!(
+            WebhookDeliveryStatus::from_string("dead_lettered").unwrap(),
+            WebhookDeliveryStatus::DeadLettered
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::domain::entities::{ACCOUNT_CREATED, LEDGER_EVENT_CREATED};
+use crate::domain::errors::{DomainError, DomainResult};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Event type sent when a ledger event takes an account's balance from at or
+/// above a subscription's threshold to below it
+pub const BALANCE_BELOW_THRESHOLD: &str = "balance.below_threshold";
+
+/// Event types clients can subscribe to
+pub const WEBHOOK_EVENT_TYPES: [&str; 3] = [
+    LEDGER_EVENT_CREATED,
+    ACCOUNT_CREATED,
+    BALANCE_BELOW_THRESHOLD,
+];
+
+/// Client endpoint notified of the selected event types, optionally limited to
+/// one account
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookSubscription {
+    pub id: Option<i32>,
+    pub url: String,
+    pub event_types: Vec<String>,
+    /// Key of the HMAC signing each request; only shown when the subscription is created
+    pub secret: String,
+    /// Only events of this account are sent when set
+    pub account_id: Option<i32>,
+    /// Balance, in the account's minor units, below which `balance.below_threshold` fires
+    pub balance_threshold: Option<i64>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl WebhookSubscription {
+    pub fn new(url: String, event_types: Vec<String>, secret: String) -> Self {
+        Self {
+            id: None,
+            url,
+            event_types,
+            secret,
+            account_id: None,
+            balance_threshold: None,
+            created_at: None,
+        }
+    }
+
+    pub fn with_account(mut self, account_id: Option<i32>) -> Self {
+        self.account_id = account_id;
+        self
+    }
+
+    pub fn with_balance_threshold(mut self, balance_threshold: Option<i64>) -> Self {
+        self.balance_threshold = balance_threshold;
+        self
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            return Err(DomainError::ValidationError(format!(
+                "Webhook URL must be an http(s) URL, got {:?}",
+                self.url
+            )));
+        }
+        if self.event_types.is_empty() {
+            return Err(DomainError::ValidationError(
+                "At least one event type is required".to_string(),
+            ));
+        }
+        if let Some(unknown) = self
+            .event_types
+            .iter()
+            .find(|event_type| !WEBHOOK_EVENT_TYPES.contains(&event_type.as_str()))
+        {
+            return Err(DomainError::ValidationError(format!(
+                "Unknown event type {:?}, expected one of {}",
+                unknown,
+                WEBHOOK_EVENT_TYPES.join(", ")
+            )));
+        }
+
+        let watches_balance = self.subscribes_to(BALANCE_BELOW_THRESHOLD);
+        match (watches_balance, self.balance_threshold) {
+            (true, None) => Err(DomainError::ValidationError(format!(
+                "balance_threshold is required for {}",
+                BALANCE_BELOW_THRESHOLD
+            ))),
+            (false, Some(_)) => Err(DomainError::ValidationError(format!(
+                "balance_threshold only applies to {}",
+                BALANCE_BELOW_THRESHOLD
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn subscribes_to(&self, event_type: &str) -> bool {
+        self.event_types
+            .iter()
+            .any(|subscribed| subscribed == event_type)
+    }
+
+    /// Whether an event of `event_type` about `account_id` is sent to this subscription
+    pub fn matches(&self, event_type: &str, account_id: Option<i32>) -> bool {
+        self.subscribes_to(event_type)
+            && self
+                .account_id
+                .is_none_or(|subscribed| Some(subscribed) == account_id)
+    }
+
+    /// Whether a balance change from `before` to `after` crosses the threshold
+    /// downwards. Staying below it does not fire again until the balance has
+    /// recovered to the threshold.
+    pub fn crossed_below(&self, before: i64, after: i64) -> bool {
+        self.balance_threshold
+            .is_some_and(|threshold| before >= threshold && after < threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(event_types: &[&str]) -> WebhookSubscription {
+        WebhookSubscription::new(
+            "https://hooks.example.com/ledger".to_string(),
+            event_types
+                .iter()
+                .map(|event_type| event_type.to_string())
+                .collect(),
+            "whsec_test".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(subscription(&[LEDGER_EVENT_CREATED, ACCOUNT_CREATED])
+            .validate()
+            .is_ok());
+        assert!(subscription(&[]).validate().is_err());
+        assert!(subscription(&["account.deleted"]).validate().is_err());
+        assert!(subscription(&[BALANCE_BELOW_THRESHOLD]).validate().is_err());
+        assert!(subscription(&[BALANCE_BELOW_THRESHOLD])
+            .with_balance_threshold(Some(1000))
+            .validate()
+            .is_ok());
+        assert!(subscription(&[ACCOUNT_CREATED])
+            .with_balance_threshold(Some(1000))
+            .validate()
+            .is_err());
+
+        let mut ftp = subscription(&[ACCOUNT_CREATED]);
+        ftp.url = "ftp://hooks.example.com".to_string();
+        assert!(ftp.validate().is_err());
+    }
+
+    #[test]
+    fn test_matches_and_threshold_crossing() {
+        let all_accounts = subscription(&[LEDGER_EVENT_CREATED]);
+        assert!(all_accounts.matches(LEDGER_EVENT_CREATED, Some(3)));
+        assert!(!all_accounts.matches(ACCOUNT_CREATED, Some(3)));
+
+        let one_account = subscription(&[BALANCE_BELOW_THRESHOLD])
+            .with_account(Some(3))
+            .with_balance_threshold(Some(1000));
+        assert!(one_account.matches(BALANCE_BELOW_THRESHOLD, Some(3)));
+        assert!(!one_account.matches(BALANCE_BELOW_THRESHOLD, Some(4)));
+
+        assert!(one_account.crossed_below(1000, 999));
+        assert!(one_account.crossed_below(5000, -20));
+        assert!(!one_account.crossed_below(999, 500));
+        assert!(!one_account.crossed_below(5000, 1000));
+        assert!(!all_accounts.crossed_below(5000, 0));
+    }
+}
@@ -5,21 +5,62 @@ pub enum DomainError {
     #[error("Account not found: {0}")]
     AccountNotFound(String),
 
-    #[error("Transaction not found: {0}")]
-    TransactionNotFound(i32),
+    #[error("Account {0} is frozen")]
+    AccountFrozen(i32),
+
+    #[error("Account {0} is closed")]
+    AccountClosed(i32),
+
+    #[error("Account {account_id} cannot change status from {from} to {to}")]
+    InvalidStatusTransition {
+        account_id: i32,
+        from: String,
+        to: String,
+    },
+
+    #[error("Account {account_id} cannot be closed with a non-zero balance of {balance}")]
+    BalanceNotZero { account_id: i32, balance: i64 },
+
+    #[error("Ledger event not found: {0}")]
+    EventNotFound(i32),
+
+    #[error("Ledger event {event_id} is already reversed by event {reversal_event_id}")]
+    EventAlreadyReversed {
+        event_id: i32,
+        reversal_event_id: i32,
+    },
+
+    #[error("Account {account_id} is at version {actual}, expected {expected}")]
+    VersionConflict {
+        account_id: i32,
+        expected: i64,
+        actual: i64,
+    },
+
+    #[error("Webhook subscription not found: {0}")]
+    WebhookSubscriptionNotFound(i32),
+
+    #[error("Webhook delivery not found: {0}")]
+    WebhookDeliveryNotFound(i32),
+
+    #[error("Webhook delivery {delivery_id} is {status} and cannot be replayed")]
+    DeliveryNotReplayable { delivery_id: i32, status: String },
 
     #[error("Insufficient balance: required {required}, available {available}")]
     InsufficientBalance { required: i64, available: i64 },
 
+    #[error("Unbalanced journal entry: debits {debits}, credits {credits}")]
+    UnbalancedJournalEntry { debits: i64, credits: i64 },
+
+    #[error("Currency mismatch: expected {expected}, got {actual}")]
+    CurrencyMismatch { expected: String, actual: String },
+
     #[error("Invalid amount: {0}")]
     InvalidAmount(i64),
 
     #[error("Invalid account number: {0}")]
     InvalidAccountNumber(String),
 
-    #[error("Invalid transaction type: {0}")]
-    InvalidTransactionType(String),
-
     #[error("Duplicate account number: {0}")]
     DuplicateAccountNumber(String),
 
@@ -30,4 +71,31 @@ pub enum DomainError {
     ValidationError(String),
 }
 
+impl DomainError {
+    /// Stable machine-readable identifier of the error kind
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::AccountNotFound(_) => "ACCOUNT_NOT_FOUND",
+            DomainError::AccountFrozen(_) => "ACCOUNT_FROZEN",
+            DomainError::AccountClosed(_) => "ACCOUNT_CLOSED",
+            DomainError::InvalidStatusTransition { .. } => "INVALID_STATUS_TRANSITION",
+            DomainError::BalanceNotZero { .. } => "ACCOUNT_BALANCE_NOT_ZERO",
+            DomainError::EventNotFound(_) => "EVENT_NOT_FOUND",
+            DomainError::EventAlreadyReversed { .. } => "EVENT_ALREADY_REVERSED",
+            DomainError::VersionConflict { .. } => "VERSION_CONFLICT",
+            DomainError::WebhookSubscriptionNotFound(_) => "WEBHOOK_SUBSCRIPTION_NOT_FOUND",
+            DomainError::WebhookDeliveryNotFound(_) => "WEBHOOK_DELIVERY_NOT_FOUND",
+            DomainError::DeliveryNotReplayable { .. } => "DELIVERY_NOT_REPLAYABLE",
+            DomainError::InsufficientBalance { .. } => "INSUFFICIENT_BALANCE",
+            DomainError::UnbalancedJournalEntry { .. } => "UNBALANCED_JOURNAL_ENTRY",
+            DomainError::CurrencyMismatch { .. } => "CURRENCY_MISMATCH",
+            DomainError::InvalidAmount(_) => "INVALID_AMOUNT",
+            DomainError::InvalidAccountNumber(_) => "INVALID_ACCOUNT_NUMBER",
+            DomainError::DuplicateAccountNumber(_) => "DUPLICATE_ACCOUNT_NUMBER",
+            DomainError::RepositoryError(_) => "REPOSITORY_ERROR",
+            DomainError::ValidationError(_) => "VALIDATION_ERROR",
+        }
+    }
+}
+
 pub type DomainResult<T> = Result<T, DomainError>;
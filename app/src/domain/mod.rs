@@ -1,11 +1,20 @@
 pub mod entities;
 pub mod errors;
 pub mod repositories;
-pub mod services;
 pub mod value_objects;
 
-pub use entities::{Account, AccountBalance, EventType, LedgerEvent, Transaction};
+pub use entities::{
+    Account, AccountBalance, AccountStatement, AccountStatusChange, EventType, IdempotencyRecord,
+    JournalEntry, LedgerEvent, OutboxMessage, OutboxStatus, StatementLine, Transfer,
+    WebhookDelivery, WebhookDeliveryStatus, WebhookSubscription,
+};
 pub use errors::{DomainError, DomainResult};
-pub use repositories::{AccountBalanceRepository, AccountRepository, LedgerEventRepository};
-// pub use services::TransactionService; // Deprecated
-pub use value_objects::{AccountNumber, Money, TransactionType};
+pub use repositories::{
+    AccountBalanceRepository, AccountFilter, AccountRepository, IdempotencyRepository,
+    JournalEntryRepository, LedgerEventFilter, LedgerEventRepository, OutboxFilter,
+    OutboxRepository, TransferRepository, WebhookDeliveryFilter, WebhookRepository,
+};
+pub use value_objects::{
+    AccountNumber, AccountStatus, Balance, BalanceDelta, BalancePolicy, Currency, Money, Page,
+    PageRequest, RetryPolicy, WebhookSignature,
+};
@@ -1,6 +1,7 @@
 use crate::domain::entities::AccountBalance;
 use crate::domain::errors::DomainResult;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
 #[cfg(test)]
 use mockall::automock;
@@ -9,7 +10,19 @@ use mockall::automock;
 #[async_trait]
 pub trait AccountBalanceRepository: Send + Sync {
     async fn save(&self, balance: &AccountBalance) -> DomainResult<AccountBalance>;
-    async fn find_latest_by_account_id(&self, account_id: i32)
-        -> DomainResult<Option<AccountBalance>>;
+    /// Latest snapshot with a known event position
+    async fn find_latest_by_account_id(
+        &self,
+        account_id: i32,
+    ) -> DomainResult<Option<AccountBalance>>;
+    /// Latest snapshot with a known event position taken before `at`
+    async fn find_latest_before(
+        &self,
+        account_id: i32,
+        at: NaiveDateTime,
+    ) -> DomainResult<Option<AccountBalance>>;
+    /// Every snapshot of the account, newest first
     async fn find_all_by_account_id(&self, account_id: i32) -> DomainResult<Vec<AccountBalance>>;
+    /// Deletes the given snapshots, returning how many existed
+    async fn delete_by_ids(&self, ids: &[i32]) -> DomainResult<usize>;
 }
@@ -1,8 +1,39 @@
-use crate::domain::entities::Account;
-use crate::domain::errors::DomainResult;
-use crate::domain::value_objects::AccountNumber;
+use crate::domain::entities::{Account, AccountStatusChange};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::{AccountNumber, Page, PageRequest};
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
+#[cfg(test)]
+use mockall::automock;
+
+/// Criteria for listing accounts; every set field must match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccountFilter {
+    /// Case-insensitive substring of the account number or name
+    pub search: Option<String>,
+    pub balance_policy: Option<String>,
+    /// Inclusive lower bound on `created_at`
+    pub created_from: Option<NaiveDateTime>,
+    /// Exclusive upper bound on `created_at`
+    pub created_to: Option<NaiveDateTime>,
+}
+
+impl AccountFilter {
+    pub fn validate(&self) -> DomainResult<()> {
+        if let (Some(from), Some(to)) = (self.created_from, self.created_to) {
+            if from > to {
+                return Err(DomainError::ValidationError(format!(
+                    "created_from {} is after created_to {}",
+                    from, to
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(test, automock)]
 #[async_trait]
 pub trait AccountRepository: Send + Sync {
     async fn save(&self, account: &Account) -> DomainResult<Account>;
@@ -10,6 +41,24 @@ pub trait AccountRepository: Send + Sync {
     async fn find_by_account_number(&self, account_number: &AccountNumber)
         -> DomainResult<Account>;
     async fn find_all(&self) -> DomainResult<Vec<Account>>;
+    /// Accounts matching the filter in ascending id order, starting after the page cursor
+    async fn find_page(
+        &self,
+        filter: &AccountFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<Account>>;
+    /// Updates name and balance policy; status only changes through `change_status`
     async fn update(&self, account: &Account) -> DomainResult<Account>;
+    /// Moves the account to `change.to_status` and records the change atomically.
+    ///
+    /// The transition is re-checked against the account's current status under its
+    /// row lock, and closing requires a zero balance at that point. The returned
+    /// record's `from_status` is the status the account actually had.
+    async fn change_status(
+        &self,
+        change: &AccountStatusChange,
+    ) -> DomainResult<AccountStatusChange>;
+    /// Status changes of the account, oldest first
+    async fn find_status_changes(&self, account_id: i32) -> DomainResult<Vec<AccountStatusChange>>;
     async fn exists_by_account_number(&self, account_number: &AccountNumber) -> DomainResult<bool>;
 }
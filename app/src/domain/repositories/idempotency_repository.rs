@@ -0,0 +1,31 @@
+use crate::domain::entities::IdempotencyRecord;
+use crate::domain::errors::DomainResult;
+use async_trait::async_trait;
+use chrono::Duration;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    /// Claims `key` for a new request expiring after `ttl`. Returns None when the key
+    /// was claimed, or the existing unexpired record when the key is already in use.
+    async fn reserve(
+        &self,
+        key: &str,
+        request_fingerprint: &str,
+        ttl: Duration,
+    ) -> DomainResult<Option<IdempotencyRecord>>;
+    /// Stores the response of the request holding `key`
+    async fn complete(
+        &self,
+        key: &str,
+        response_status: u16,
+        response_content_type: Option<String>,
+        response_body: &str,
+    ) -> DomainResult<()>;
+    /// Frees a key whose request produced no response worth replaying
+    async fn release(&self, key: &str) -> DomainResult<()>;
+    async fn delete_expired(&self) -> DomainResult<usize>;
+}
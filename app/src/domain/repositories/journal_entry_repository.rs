@@ -0,0 +1,13 @@
+use crate::domain::entities::JournalEntry;
+use crate::domain::errors::DomainResult;
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait JournalEntryRepository: Send + Sync {
+    /// Persists the entry and all of its ledger event legs atomically
+    async fn save(&self, entry: &JournalEntry) -> DomainResult<JournalEntry>;
+}
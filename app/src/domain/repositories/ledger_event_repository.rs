@@ -1,15 +1,91 @@
-use crate::domain::entities::LedgerEvent;
-use crate::domain::errors::DomainResult;
+use crate::domain::entities::{EventType, LedgerEvent};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::{BalanceDelta, Page, PageRequest};
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
 #[cfg(test)]
 use mockall::automock;
 
+/// Criteria for listing ledger events; every set field must match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LedgerEventFilter {
+    pub account_id: Option<i32>,
+    pub event_type: Option<EventType>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    /// Inclusive lower bound on `created_at`
+    pub created_from: Option<NaiveDateTime>,
+    /// Exclusive upper bound on `created_at`
+    pub created_to: Option<NaiveDateTime>,
+    /// Case-insensitive substring of the description
+    pub description: Option<String>,
+}
+
+impl LedgerEventFilter {
+    pub fn validate(&self) -> DomainResult<()> {
+        if let (Some(min), Some(max)) = (self.min_amount, self.max_amount) {
+            if min > max {
+                return Err(DomainError::ValidationError(format!(
+                    "min_amount {} is greater than max_amount {}",
+                    min, max
+                )));
+            }
+        }
+        if let (Some(from), Some(to)) = (self.created_from, self.created_to) {
+            if from > to {
+                return Err(DomainError::ValidationError(format!(
+                    "created_from {} is after created_to {}",
+                    from, to
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait LedgerEventRepository: Send + Sync {
+    /// Saves the event and, atomically with it, its `ledger_event.created` outbox message
     async fn save(&self, event: &LedgerEvent) -> DomainResult<LedgerEvent>;
+    /// Saves the event only if the account's latest event has `expected_version`,
+    /// failing with `VersionConflict` when another writer appended first
+    async fn save_at_version(
+        &self,
+        event: &LedgerEvent,
+        expected_version: i64,
+    ) -> DomainResult<LedgerEvent>;
+    async fn find_by_id(&self, id: i32) -> DomainResult<LedgerEvent>;
+    /// Events of the account in version order
     async fn find_by_account_id(&self, account_id: i32) -> DomainResult<Vec<LedgerEvent>>;
-    async fn find_all(&self) -> DomainResult<Vec<LedgerEvent>>;
-    async fn calculate_balance(&self, account_id: i32) -> DomainResult<i64>;
+    /// Events matching the filter in sequence order, starting after the page cursor,
+    /// which is the sequence of the last event of the previous page
+    async fn find_page(
+        &self,
+        filter: &LedgerEventFilter,
+        page: &PageRequest<i64>,
+    ) -> DomainResult<Page<LedgerEvent, i64>>;
+    /// Aggregates the account's events with an id greater than `after_event_id`
+    async fn calculate_balance_since(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+    ) -> DomainResult<BalanceDelta>;
+    /// Aggregates the account's events with an id greater than `after_event_id` and
+    /// at most `through_event_id`
+    async fn calculate_balance_between(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+        through_event_id: i32,
+    ) -> DomainResult<BalanceDelta>;
+    /// Aggregates the account's events with an id greater than `after_event_id`
+    /// created before `before`
+    async fn calculate_balance_before(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+        before: NaiveDateTime,
+    ) -> DomainResult<BalanceDelta>;
 }
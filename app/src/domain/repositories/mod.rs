@@ -1,9 +1,17 @@
 pub mod account_balance_repository;
 pub mod account_repository;
+pub mod idempotency_repository;
+pub mod journal_entry_repository;
 pub mod ledger_event_repository;
-// pub mod transaction_repository; // Deprecated in favor of event-sourcing
+pub mod outbox_repository;
+pub mod transfer_repository;
+pub mod webhook_repository;
 
 pub use account_balance_repository::AccountBalanceRepository;
-pub use account_repository::AccountRepository;
-pub use ledger_event_repository::LedgerEventRepository;
-// pub use transaction_repository::TransactionRepository;
+pub use account_repository::{AccountFilter, AccountRepository};
+pub use idempotency_repository::IdempotencyRepository;
+pub use journal_entry_repository::JournalEntryRepository;
+pub use ledger_event_repository::{LedgerEventFilter, LedgerEventRepository};
+pub use outbox_repository::{OutboxFilter, OutboxRepository};
+pub use transfer_repository::TransferRepository;
+pub use webhook_repository::{WebhookDeliveryFilter, WebhookRepository};
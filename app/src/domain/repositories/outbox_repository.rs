@@ -0,0 +1,39 @@
+use crate::domain::entities::{OutboxMessage, OutboxStatus};
+use crate::domain::errors::DomainResult;
+use crate::domain::value_objects::{Page, PageRequest};
+use async_trait::async_trait;
+use chrono::Duration;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Criteria for listing outbox messages; every set field must match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutboxFilter {
+    pub status: Option<OutboxStatus>,
+    pub topic: Option<String>,
+}
+
+/// Outbox messages are written by the ledger repositories in the same transaction
+/// as the events they describe; this repository serves the relay publishing them.
+/// Times are taken from the storage's clock.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    /// Takes up to `limit` unpublished messages that are due, oldest first, and
+    /// pushes their next attempt `lease` into the future so that no other relay
+    /// takes them meanwhile. A relay that dies holding them only delays their delivery.
+    async fn claim_due(&self, lease: Duration, limit: i64) -> DomainResult<Vec<OutboxMessage>>;
+    /// Records a successful delivery attempt
+    async fn mark_published(&self, id: i32) -> DomainResult<()>;
+    /// Records a failed delivery attempt, to be retried after `retry_in`
+    async fn mark_failed(&self, id: i32, error: &str, retry_in: Duration) -> DomainResult<()>;
+    /// Number of messages not published yet
+    async fn count_unpublished(&self) -> DomainResult<i64>;
+    /// Messages matching the filter in ascending id order, starting after the page cursor
+    async fn find_page(
+        &self,
+        filter: &OutboxFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<OutboxMessage>>;
+}
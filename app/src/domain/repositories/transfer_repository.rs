@@ -0,0 +1,13 @@
+use crate::domain::entities::Transfer;
+use crate::domain::errors::DomainResult;
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TransferRepository: Send + Sync {
+    /// Persists the transfer and both of its ledger event legs atomically
+    async fn save(&self, transfer: &Transfer) -> DomainResult<Transfer>;
+}
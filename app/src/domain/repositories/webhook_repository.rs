@@ -0,0 +1,62 @@
+use crate::domain::entities::{WebhookDelivery, WebhookDeliveryStatus, WebhookSubscription};
+use crate::domain::errors::DomainResult;
+use crate::domain::value_objects::{Page, PageRequest};
+use async_trait::async_trait;
+use chrono::Duration;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Criteria for listing webhook deliveries; every set field must match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WebhookDeliveryFilter {
+    pub subscription_id: Option<i32>,
+    pub status: Option<WebhookDeliveryStatus>,
+}
+
+/// Webhook subscriptions and their deliveries.
+///
+/// Deliveries are written alongside the outbox messages they derive from, in the
+/// same transaction; this repository serves the API and the dispatcher sending them.
+/// Times are taken from the storage's clock.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn save_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<WebhookSubscription>;
+    async fn find_subscription(&self, id: i32) -> DomainResult<WebhookSubscription>;
+    /// Subscriptions in ascending id order, starting after the page cursor
+    async fn find_subscriptions(
+        &self,
+        page: &PageRequest,
+    ) -> DomainResult<Page<WebhookSubscription>>;
+    /// Deletes the subscription along with its deliveries, sent or not
+    async fn delete_subscription(&self, id: i32) -> DomainResult<()>;
+
+    /// Takes up to `limit` due deliveries, oldest first, and pushes their next
+    /// attempt `lease` into the future so that no other dispatcher takes them meanwhile
+    async fn claim_due_deliveries(
+        &self,
+        lease: Duration,
+        limit: i64,
+    ) -> DomainResult<Vec<WebhookDelivery>>;
+    /// Records a successful delivery attempt
+    async fn mark_delivered(&self, id: i32) -> DomainResult<()>;
+    /// Records a failed delivery attempt, to be retried after `retry_in`
+    async fn mark_failed(&self, id: i32, error: &str, retry_in: Duration) -> DomainResult<()>;
+    /// Records the last allowed delivery attempt as failed; the delivery is not
+    /// retried unless replayed
+    async fn mark_dead_lettered(&self, id: i32, error: &str) -> DomainResult<()>;
+    /// Deliveries matching the filter in ascending id order, starting after the page cursor
+    async fn find_deliveries(
+        &self,
+        filter: &WebhookDeliveryFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<WebhookDelivery>>;
+    async fn find_delivery(&self, id: i32) -> DomainResult<WebhookDelivery>;
+    /// Schedules the delivery to be sent again right away with a fresh attempt
+    /// count, keeping its id
+    async fn replay_delivery(&self, id: i32) -> DomainResult<WebhookDelivery>;
+}
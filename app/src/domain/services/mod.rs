@@ -1,2 +0,0 @@
-// pub mod transaction_service; // Deprecated in favor of event-sourcing with ledger events
-// pub use transaction_service::TransactionService;
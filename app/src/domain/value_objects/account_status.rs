@@ -0,0 +1,121 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle state of an account. Only active accounts accept ledger events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountStatus {
+    #[default]
+    Active,
+    Frozen,
+    Closed,
+}
+
+impl AccountStatus {
+    pub fn from_string(s: &str) -> DomainResult<Self> {
+        match s.to_uppercase().as_str() {
+            "ACTIVE" => Ok(AccountStatus::Active),
+            "FROZEN" => Ok(AccountStatus::Frozen),
+            "CLOSED" => Ok(AccountStatus::Closed),
+            _ => Err(DomainError::ValidationError(format!(
+                "Invalid account status: {}",
+                s
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            AccountStatus::Active => "ACTIVE",
+            AccountStatus::Frozen => "FROZEN",
+            AccountStatus::Closed => "CLOSED",
+        }
+    }
+
+    /// Fails unless ledger events may be appended to an account in this status
+    pub fn ensure_accepts_events(&self, account_id: i32) -> DomainResult<()> {
+        match self {
+            AccountStatus::Active => Ok(()),
+            AccountStatus::Frozen => Err(DomainError::AccountFrozen(account_id)),
+            AccountStatus::Closed => Err(DomainError::AccountClosed(account_id)),
+        }
+    }
+
+    /// Fails unless an account in this status may move to `target`. Active accounts
+    /// can be frozen, frozen accounts unfrozen, and either can be closed; closing is final.
+    pub fn ensure_can_transition_to(
+        &self,
+        target: AccountStatus,
+        account_id: i32,
+    ) -> DomainResult<()> {
+        use AccountStatus::*;
+
+        match (self, target) {
+            (Active, Frozen) | (Frozen, Active) | (Active, Closed) | (Frozen, Closed) => Ok(()),
+            _ => Err(DomainError::InvalidStatusTransition {
+                account_id,
+                from: self.as_str().to_string(),
+                to: target.as_str().to_string(),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for AccountStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(
+            AccountStatus::from_string("active").unwrap(),
+            AccountStatus::Active
+        );
+        assert_eq!(
+            AccountStatus::from_string("FROZEN").unwrap(),
+            AccountStatus::Frozen
+        );
+        assert!(AccountStatus::from_string("DORMANT").is_err());
+    }
+
+    #[test]
+    fn test_ensure_accepts_events() {
+        assert!(AccountStatus::Active.ensure_accepts_events(1).is_ok());
+        assert!(matches!(
+            AccountStatus::Frozen.ensure_accepts_events(1),
+            Err(DomainError::AccountFrozen(1))
+        ));
+        assert!(matches!(
+            AccountStatus::Closed.ensure_accepts_events(1),
+            Err(DomainError::AccountClosed(1))
+        ));
+    }
+
+    #[test]
+    fn test_transitions() {
+        use AccountStatus::*;
+
+        assert!(Active.ensure_can_transition_to(Frozen, 1).is_ok());
+        assert!(Frozen.ensure_can_transition_to(Active, 1).is_ok());
+        assert!(Active.ensure_can_transition_to(Closed, 1).is_ok());
+        assert!(Frozen.ensure_can_transition_to(Closed, 1).is_ok());
+
+        for (from, to) in [
+            (Active, Active),
+            (Frozen, Frozen),
+            (Closed, Active),
+            (Closed, Frozen),
+            (Closed, Closed),
+        ] {
+            assert!(matches!(
+                from.ensure_can_transition_to(to, 1),
+                Err(DomainError::InvalidStatusTransition { .. })
+            ));
+        }
+    }
+}
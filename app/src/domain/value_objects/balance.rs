@@ -0,0 +1,90 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::Money;
+use serde::{Deserialize, Serialize};
+
+/// Signed account balance. Unlike `Money`, which is always a non-negative amount,
+/// a balance is negative when debits exceed credits (overdrawn or liability accounts).
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Balance(i64);
+
+impl Balance {
+    pub fn new(value: i64) -> Self {
+        Self(value)
+    }
+
+    pub fn zero() -> Self {
+        Self(0)
+    }
+
+    pub fn value(&self) -> i64 {
+        self.0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    /// Balance after adding a signed net change
+    pub fn adjust(&self, delta: i64) -> DomainResult<Balance> {
+        self.0
+            .checked_add(delta)
+            .map(Balance)
+            .ok_or_else(|| DomainError::ValidationError("Balance overflow".to_string()))
+    }
+
+    pub fn credit(&self, amount: &Money) -> DomainResult<Balance> {
+        self.adjust(amount.value())
+    }
+
+    pub fn debit(&self, amount: &Money) -> DomainResult<Balance> {
+        self.adjust(-amount.value())
+    }
+}
+
+impl From<Money> for Balance {
+    fn from(money: Money) -> Self {
+        Self(money.value())
+    }
+}
+
+impl std::fmt::Display for Balance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Currency;
+
+    #[test]
+    fn test_negative_balance() {
+        let balance = Balance::zero()
+            .debit(&Money::new(1500, Currency::USD).unwrap())
+            .unwrap();
+
+        assert_eq!(balance.value(), -1500);
+        assert!(balance.is_negative());
+    }
+
+    #[test]
+    fn test_credit_and_debit() {
+        let balance = Balance::new(-500)
+            .credit(&Money::new(2000, Currency::USD).unwrap())
+            .unwrap()
+            .debit(&Money::new(300, Currency::USD).unwrap())
+            .unwrap();
+
+        assert_eq!(balance.value(), 1200);
+        assert!(!balance.is_negative());
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert!(Balance::new(i64::MAX).adjust(1).is_err());
+        assert!(Balance::new(i64::MIN).adjust(-1).is_err());
+    }
+}
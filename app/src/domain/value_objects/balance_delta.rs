@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Net effect of a contiguous run of an account's ledger events on its balance
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceDelta {
+    /// Sum of CREDIT amounts minus sum of DEBIT amounts
+    pub amount: i64,
+    /// Id of the newest event in the run, None when the run is empty
+    pub last_event_id: Option<i32>,
+    pub event_count: i64,
+}
+
+impl BalanceDelta {
+    pub fn new(amount: i64, last_event_id: Option<i32>, event_count: i64) -> Self {
+        Self {
+            amount,
+            last_event_id,
+            event_count,
+        }
+    }
+}
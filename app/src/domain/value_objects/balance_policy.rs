@@ -0,0 +1,168 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::{Balance, Currency, Money};
+use serde::{Deserialize, Serialize};
+
+/// Rule deciding how far a DEBIT may take an account's balance
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BalancePolicy {
+    /// Balance may never go below zero
+    #[default]
+    NoOverdraft,
+    /// Balance may go below zero down to the negated limit
+    Overdraft { limit: Money },
+    /// Debits are never rejected for lack of funds
+    Unrestricted,
+}
+
+impl BalancePolicy {
+    /// Builds a policy from its stored form; the overdraft limit is in `currency`
+    pub fn from_parts(
+        kind: &str,
+        overdraft_limit: Option<i64>,
+        currency: Currency,
+    ) -> DomainResult<Self> {
+        match (kind.to_uppercase().as_str(), overdraft_limit) {
+            ("NO_OVERDRAFT", None) => Ok(BalancePolicy::NoOverdraft),
+            ("OVERDRAFT", Some(limit)) => Ok(BalancePolicy::Overdraft {
+                limit: Money::new(limit, currency)?,
+            }),
+            ("OVERDRAFT", None) => Err(DomainError::ValidationError(
+                "Overdraft policy requires an overdraft limit".to_string(),
+            )),
+            ("UNRESTRICTED", None) => Ok(BalancePolicy::Unrestricted),
+            ("NO_OVERDRAFT", Some(_)) | ("UNRESTRICTED", Some(_)) => {
+                Err(DomainError::ValidationError(format!(
+                    "Overdraft limit is only allowed with the OVERDRAFT policy, got {}",
+                    kind
+                )))
+            }
+            _ => Err(DomainError::ValidationError(format!(
+                "Invalid balance policy: {}",
+                kind
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            BalancePolicy::NoOverdraft => "NO_OVERDRAFT",
+            BalancePolicy::Overdraft { .. } => "OVERDRAFT",
+            BalancePolicy::Unrestricted => "UNRESTRICTED",
+        }
+    }
+
+    pub fn overdraft_limit(&self) -> Option<i64> {
+        match self {
+            BalancePolicy::Overdraft { limit } => Some(limit.value()),
+            _ => None,
+        }
+    }
+
+    /// Checks that debiting `amount` from an account holding `balance` is allowed
+    pub fn check_debit(&self, balance: Balance, amount: &Money) -> DomainResult<()> {
+        let available = match self {
+            BalancePolicy::NoOverdraft => balance.value(),
+            BalancePolicy::Overdraft { limit } => balance.value().saturating_add(limit.value()),
+            BalancePolicy::Unrestricted => return Ok(()),
+        };
+
+        if amount.value() > available {
+            return Err(DomainError::InsufficientBalance {
+                required: amount.value(),
+                available,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for BalancePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_parts() {
+        assert_eq!(
+            BalancePolicy::from_parts("NO_OVERDRAFT", None, Currency::USD).unwrap(),
+            BalancePolicy::NoOverdraft
+        );
+        assert_eq!(
+            BalancePolicy::from_parts("overdraft", Some(500), Currency::USD).unwrap(),
+            BalancePolicy::Overdraft {
+                limit: Money::new(500, Currency::USD).unwrap()
+            }
+        );
+        assert_eq!(
+            BalancePolicy::from_parts("UNRESTRICTED", None, Currency::USD).unwrap(),
+            BalancePolicy::Unrestricted
+        );
+        assert!(BalancePolicy::from_parts("OVERDRAFT", None, Currency::USD).is_err());
+        assert!(BalancePolicy::from_parts("OVERDRAFT", Some(-1), Currency::USD).is_err());
+        assert!(BalancePolicy::from_parts("NO_OVERDRAFT", Some(500), Currency::USD).is_err());
+        assert!(BalancePolicy::from_parts("INVALID", None, Currency::USD).is_err());
+    }
+
+    #[test]
+    fn test_no_overdraft() {
+        let policy = BalancePolicy::NoOverdraft;
+
+        assert!(policy
+            .check_debit(
+                Balance::new(1000),
+                &Money::new(1000, Currency::USD).unwrap()
+            )
+            .is_ok());
+        assert!(matches!(
+            policy.check_debit(
+                Balance::new(1000),
+                &Money::new(1001, Currency::USD).unwrap()
+            ),
+            Err(DomainError::InsufficientBalance {
+                required: 1001,
+                available: 1000
+            })
+        ));
+    }
+
+    #[test]
+    fn test_overdraft_limit() {
+        let policy = BalancePolicy::Overdraft {
+            limit: Money::new(500, Currency::USD).unwrap(),
+        };
+
+        assert!(policy
+            .check_debit(
+                Balance::new(1000),
+                &Money::new(1500, Currency::USD).unwrap()
+            )
+            .is_ok());
+        assert!(matches!(
+            policy.check_debit(
+                Balance::new(1000),
+                &Money::new(1501, Currency::USD).unwrap()
+            ),
+            Err(DomainError::InsufficientBalance {
+                required: 1501,
+                available: 1500
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unrestricted() {
+        let policy = BalancePolicy::Unrestricted;
+
+        assert!(policy
+            .check_debit(
+                Balance::new(-1000),
+                &Money::new(5000, Currency::USD).unwrap()
+            )
+            .is_ok());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Supported ISO 4217 currencies and the number of digits of their minor unit
+const CURRENCIES: &[(&str, u8)] = &[
+    ("AUD", 2),
+    ("BHD", 3),
+    ("BRL", 2),
+    ("CAD", 2),
+    ("CHF", 2),
+    ("CNY", 2),
+    ("CZK", 2),
+    ("DKK", 2),
+    ("EUR", 2),
+    ("GBP", 2),
+    ("HKD", 2),
+    ("INR", 2),
+    ("JPY", 0),
+    ("KRW", 0),
+    ("KWD", 3),
+    ("MXN", 2),
+    ("NOK", 2),
+    ("NZD", 2),
+    ("PLN", 2),
+    ("SEK", 2),
+    ("SGD", 2),
+    ("USD", 2),
+    ("ZAR", 2),
+];
+
+/// ISO 4217 currency. Amounts are always held in the currency's minor unit
+/// (cents for USD), `exponent` tells how many decimal places that unit has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    code: &'static str,
+    exponent: u8,
+}
+
+impl Currency {
+    pub const USD: Currency = Currency {
+        code: "USD",
+        exponent: 2,
+    };
+    pub const EUR: Currency = Currency {
+        code: "EUR",
+        exponent: 2,
+    };
+    pub const JPY: Currency = Currency {
+        code: "JPY",
+        exponent: 0,
+    };
+
+    pub fn from_code(code: &str) -> DomainResult<Self> {
+        let upper = code.trim().to_uppercase();
+        CURRENCIES
+            .iter()
+            .find(|(known, _)| *known == upper)
+            .map(|&(code, exponent)| Currency { code, exponent })
+            .ok_or_else(|| DomainError::ValidationError(format!("Unsupported currency: {}", code)))
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn exponent(&self) -> u8 {
+        self.exponent
+    }
+
+    /// Formats an amount of minor units as a decimal string, e.g. 1234 USD -> "12.34"
+    pub fn format_minor_units(&self, amount: i64) -> String {
+        if self.exponent == 0 {
+            return amount.to_string();
+        }
+
+        let scale = 10u64.pow(self.exponent as u32);
+        let sign = if amount < 0 { "-" } else { "" };
+        let abs = amount.unsigned_abs();
+        format!(
+            "{}{}.{:0width$}",
+            sign,
+            abs / scale,
+            abs % scale,
+            width = self.exponent as usize
+        )
+    }
+}
+
+// Serialized as the bare ISO code
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code)
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Currency::from_code(&code).map_err(serde::de::Error::custom)
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(Currency::from_code("USD").unwrap(), Currency::USD);
+        assert_eq!(Currency::from_code("eur").unwrap(), Currency::EUR);
+        assert_eq!(Currency::from_code("KWD").unwrap().exponent(), 3);
+        assert!(Currency::from_code("XXX").is_err());
+        assert!(Currency::from_code("").is_err());
+    }
+
+    #[test]
+    fn test_format_minor_units() {
+        assert_eq!(Currency::USD.format_minor_units(1234), "12.34");
+        assert_eq!(Currency::USD.format_minor_units(5), "0.05");
+        assert_eq!(Currency::USD.format_minor_units(-150), "-1.50");
+        assert_eq!(Currency::JPY.format_minor_units(1234), "1234");
+        assert_eq!(
+            Currency::from_code("KWD").unwrap().format_minor_units(1234),
+            "1.234"
+        );
+    }
+
+    #[test]
+    fn test_serde_uses_code() {
+        let json = serde_json::to_string(&Currency::EUR).unwrap();
+        assert_eq!(json, "\"EUR\"");
+
+        let currency: Currency = serde_json::from_str("\"JPY\"").unwrap();
+        assert_eq!(currency, Currency::JPY);
+    }
+}
@@ -1,7 +1,21 @@
 pub mod account_number;
+pub mod account_status;
+pub mod balance;
+pub mod balance_delta;
+pub mod balance_policy;
+pub mod currency;
 pub mod money;
-pub mod transaction_type;
+pub mod page;
+pub mod retry_policy;
+pub mod webhook_signature;
 
 pub use account_number::AccountNumber;
+pub use account_status::AccountStatus;
+pub use balance::Balance;
+pub use balance_delta::BalanceDelta;
+pub use balance_policy::BalancePolicy;
+pub use currency::Currency;
 pub use money::Money;
-pub use transaction_type::TransactionType;
+pub use page::{Page, PageRequest, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+pub use retry_policy::RetryPolicy;
+pub use webhook_signature::WebhookSignature;
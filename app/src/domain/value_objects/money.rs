@@ -1,45 +1,121 @@
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::Currency;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct Money(i64);
+/// Non-negative amount in the minor unit of its currency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Money {
+    amount: i64,
+    currency: Currency,
+}
 
 impl Money {
-    pub fn new(amount: i64) -> DomainResult<Self> {
+    pub fn new(amount: i64, currency: Currency) -> DomainResult<Self> {
         if amount < 0 {
             return Err(DomainError::InvalidAmount(amount));
         }
-        Ok(Self(amount))
+        Ok(Self { amount, currency })
     }
 
-    pub fn zero() -> Self {
-        Self(0)
+    pub fn zero(currency: Currency) -> Self {
+        Self {
+            amount: 0,
+            currency,
+        }
     }
 
     pub fn value(&self) -> i64 {
-        self.0
+        self.amount
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Fails with `CurrencyMismatch` unless this amount is in `currency`
+    pub fn ensure_currency(&self, currency: Currency) -> DomainResult<()> {
+        if self.currency != currency {
+            return Err(DomainError::CurrencyMismatch {
+                expected: currency.code().to_string(),
+                actual: self.currency.code().to_string(),
+            });
+        }
+        Ok(())
     }
 
     pub fn add(&self, other: &Money) -> DomainResult<Money> {
-        self.0
-            .checked_add(other.0)
-            .map(Money)
+        other.ensure_currency(self.currency)?;
+        self.amount
+            .checked_add(other.amount)
+            .map(|amount| Money {
+                amount,
+                currency: self.currency,
+            })
             .ok_or_else(|| DomainError::ValidationError("Amount overflow".to_string()))
     }
 
     pub fn subtract(&self, other: &Money) -> DomainResult<Money> {
-        if self.0 < other.0 {
+        other.ensure_currency(self.currency)?;
+        if self.amount < other.amount {
             return Err(DomainError::InsufficientBalance {
-                required: other.0,
-                available: self.0,
+                required: other.amount,
+                available: self.amount,
             });
         }
-        Ok(Money(self.0 - other.0))
+        Ok(Money {
+            amount: self.amount - other.amount,
+            currency: self.currency,
+        })
     }
 }
 
 impl std::fmt::Display for Money {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(
+            f,
+            "{} {}",
+            self.currency.format_minor_units(self.amount),
+            self.currency
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let money = Money::new(1234, Currency::EUR).unwrap();
+
+        assert_eq!(money.value(), 1234);
+        assert_eq!(money.currency(), Currency::EUR);
+        assert_eq!(money.to_string(), "12.34 EUR");
+        assert!(Money::new(-1, Currency::USD).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_same_currency() {
+        let a = Money::new(1000, Currency::USD).unwrap();
+        let b = Money::new(250, Currency::USD).unwrap();
+
+        assert_eq!(a.add(&b).unwrap().value(), 1250);
+        assert_eq!(a.subtract(&b).unwrap().value(), 750);
+        assert!(b.subtract(&a).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_rejects_mixed_currencies() {
+        let usd = Money::new(1000, Currency::USD).unwrap();
+        let eur = Money::new(250, Currency::EUR).unwrap();
+
+        assert!(matches!(
+            usd.add(&eur),
+            Err(DomainError::CurrencyMismatch { .. })
+        ));
+        assert!(matches!(
+            usd.subtract(&eur),
+            Err(DomainError::CurrencyMismatch { .. })
+        ));
     }
 }
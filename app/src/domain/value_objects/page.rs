@@ -0,0 +1,83 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+/// Keyset page position: rows with a key greater than `after`, at most `limit` of them.
+/// The key is the row id unless a listing pages on another column, such as the
+/// ledger event `sequence`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest<C = i32> {
+    pub after: Option<C>,
+    pub limit: i64,
+}
+
+impl<C> PageRequest<C> {
+    pub fn new(after: Option<C>, limit: Option<i64>) -> DomainResult<Self> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+            return Err(DomainError::ValidationError(format!(
+                "Page size must be between 1 and {}, got {}",
+                MAX_PAGE_SIZE, limit
+            )));
+        }
+        Ok(Self { after, limit })
+    }
+}
+
+/// One page of results and the cursor of the next page, None on the last page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T, C = i32> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<C>,
+}
+
+impl<T, C> Page<T, C> {
+    /// Builds a page from a query that fetched up to `limit + 1` rows; the extra row
+    /// only tells whether there is a next page and is dropped.
+    pub fn from_overfetch(mut items: Vec<T>, limit: i64, id_of: impl Fn(&T) -> Option<C>) -> Self {
+        let has_more = items.len() as i64 > limit;
+        items.truncate(limit as usize);
+
+        let next_cursor = if has_more {
+            items.last().and_then(id_of)
+        } else {
+            None
+        };
+
+        Self { items, next_cursor }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_request_limits() {
+        assert_eq!(
+            PageRequest::<i32>::new(None, None).unwrap().limit,
+            DEFAULT_PAGE_SIZE
+        );
+        assert_eq!(PageRequest::new(Some(10), Some(1)).unwrap().after, Some(10));
+        assert!(PageRequest::<i32>::new(None, Some(0)).is_err());
+        assert!(PageRequest::<i32>::new(None, Some(MAX_PAGE_SIZE + 1)).is_err());
+    }
+
+    #[test]
+    fn test_from_overfetch_with_more() {
+        let page = Page::from_overfetch(vec![1, 2, 3], 2, |id| Some(*id));
+
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.next_cursor, Some(2));
+    }
+
+    #[test]
+    fn test_from_overfetch_last_page() {
+        let page = Page::from_overfetch(vec![1, 2], 2, |id| Some(*id));
+
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.next_cursor, None);
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+/// Exponential backoff between delivery attempts: `base` after the first failure,
+/// doubling after each further one, never more than `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self { base, max }
+    }
+
+    /// Delay before the next attempt once `failures` attempts have failed
+    pub fn delay(&self, failures: i32) -> Duration {
+        let doublings = failures.saturating_sub(1).clamp(0, 30) as u32;
+        self.base
+            .checked_mul(1 << doublings)
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_up_to_max() {
+        let policy = RetryPolicy::new(Duration::seconds(2), Duration::seconds(60));
+
+        assert_eq!(policy.delay(1), Duration::seconds(2));
+        assert_eq!(policy.delay(2), Duration::seconds(4));
+        assert_eq!(policy.delay(5), Duration::seconds(32));
+        assert_eq!(policy.delay(6), Duration::seconds(60));
+        assert_eq!(policy.delay(1000), Duration::seconds(60));
+    }
+}
@@ -1,34 +0,0 @@
-use crate::domain::errors::{DomainError, DomainResult};
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum TransactionType {
-    Deposit,
-    Withdrawal,
-    Transfer,
-}
-
-impl TransactionType {
-    pub fn from_string(s: &str) -> DomainResult<Self> {
-        match s.to_lowercase().as_str() {
-            "deposit" => Ok(TransactionType::Deposit),
-            "withdrawal" => Ok(TransactionType::Withdrawal),
-            "transfer" => Ok(TransactionType::Transfer),
-            _ => Err(DomainError::InvalidTransactionType(s.to_string())),
-        }
-    }
-
-    pub fn as_str(&self) -> &str {
-        match self {
-            TransactionType::Deposit => "deposit",
-            TransactionType::Withdrawal => "withdrawal",
-            TransactionType::Transfer => "transfer",
-        }
-    }
-}
-
-impl std::fmt::Display for TransactionType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_str())
-    }
-}
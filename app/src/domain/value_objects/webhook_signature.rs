@@ -0,0 +1,102 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Signature of a webhook request: the hex HMAC-SHA256 of `"{timestamp}.{body}"`
+/// keyed with the subscription's secret. Signing the timestamp along with the body
+/// lets receivers reject old requests replayed by a third party.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookSignature {
+    /// Unix time in seconds when the request was signed
+    pub timestamp: i64,
+    pub signature: String,
+}
+
+impl WebhookSignature {
+    pub fn sign(secret: &str, timestamp: i64, body: &str) -> Self {
+        let signature = Self::mac(secret, timestamp, body)
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        Self {
+            timestamp,
+            signature,
+        }
+    }
+
+    /// Parses an `X-Webhook-Signature` header value
+    pub fn parse(header: &str) -> Option<Self> {
+        let mut timestamp = None;
+        let mut signature = None;
+        for part in header.split(',') {
+            match part.trim().split_once('=') {
+                Some(("t", value)) => timestamp = value.parse().ok(),
+                Some(("v1", value)) => signature = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        Some(Self {
+            timestamp: timestamp?,
+            signature: signature?,
+        })
+    }
+
+    /// Value of the `X-Webhook-Signature` header, `t=<timestamp>,v1=<signature>`
+    pub fn header_value(&self) -> String {
+        format!("t={},v1={}", self.timestamp, self.signature)
+    }
+
+    /// Whether the signature was made with `secret` over `body`, compared in
+    /// constant time
+    pub fn verify(&self, secret: &str, body: &str) -> bool {
+        let Some(expected) = decode_hex(&self.signature) else {
+            return false;
+        };
+        Self::mac(secret, self.timestamp, body)
+            .verify_slice(&expected)
+            .is_ok()
+    }
+
+    fn mac(secret: &str, timestamp: i64, body: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body.as_bytes());
+        mac
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let body = r#"{"id":1}"#;
+        let signature = WebhookSignature::sign("whsec_test", 1_700_000_000, body);
+
+        assert_eq!(
+            signature.header_value(),
+            "t=1700000000,v1=2f441ba4b3b2d50d28a9ab9d9fd8880376ecd1eb5d0435401553f5d8d0a5dcf8"
+        );
+
+        let parsed = WebhookSignature::parse(&signature.header_value()).unwrap();
+        assert_eq!(parsed, signature);
+        assert!(parsed.verify("whsec_test", body));
+        assert!(!parsed.verify("whsec_other", body));
+        assert!(!parsed.verify("whsec_test", r#"{"id":2}"#));
+        assert!(WebhookSignature::parse("v1=abc").is_none());
+    }
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+
+/// Outcome of one readiness check
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthCheck {
+    pub name: &'static str,
+    /// None when the check passed
+    pub error: Option<String>,
+}
+
+impl HealthCheck {
+    pub fn ok(name: &'static str) -> Self {
+        Self { name, error: None }
+    }
+
+    pub fn failed(name: &'static str, error: impl Into<String>) -> Self {
+        Self {
+            name,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Health of the storage backend, behind `/health/ready` and the pool gauges on
+/// `/metrics`
+#[async_trait]
+pub trait StorageHealth: Send + Sync {
+    async fn check(&self) -> Vec<HealthCheck>;
+
+    /// Publishes connection pool gauges; backends without a pool publish nothing
+    fn record_pool_metrics(&self) {}
+}
@@ -0,0 +1,72 @@
+use crate::domain::entities::AccountBalance;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::AccountBalanceRepository;
+use crate::infrastructure::memory::store::{InMemoryStore, StoreState};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+pub struct InMemoryAccountBalanceRepository {
+    store: InMemoryStore,
+}
+
+impl InMemoryAccountBalanceRepository {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl AccountBalanceRepository for InMemoryAccountBalanceRepository {
+    async fn save(&self, balance: &AccountBalance) -> DomainResult<AccountBalance> {
+        let mut state = self.store.lock();
+        state.account(balance.account_id)?;
+
+        let mut saved = balance.clone();
+        saved.id = Some(state.next_id("account_balances"));
+        saved.snapshot_at = Some(StoreState::now());
+
+        state.balances.push(saved.clone());
+        Ok(saved)
+    }
+
+    async fn find_latest_by_account_id(
+        &self,
+        account_id: i32,
+    ) -> DomainResult<Option<AccountBalance>> {
+        Ok(self.store.lock().latest_snapshot(account_id).cloned())
+    }
+
+    async fn find_latest_before(
+        &self,
+        account_id: i32,
+        at: NaiveDateTime,
+    ) -> DomainResult<Option<AccountBalance>> {
+        Ok(self
+            .store
+            .lock()
+            .latest_snapshot_before(account_id, at)
+            .cloned())
+    }
+
+    async fn find_all_by_account_id(&self, account_id: i32) -> DomainResult<Vec<AccountBalance>> {
+        // Newest first, like the database query ordering by snapshot_at descending
+        Ok(self
+            .store
+            .lock()
+            .balances
+            .iter()
+            .rev()
+            .filter(|snapshot| snapshot.account_id == account_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_by_ids(&self, ids: &[i32]) -> DomainResult<usize> {
+        let mut state = self.store.lock();
+        let before = state.balances.len();
+        state
+            .balances
+            .retain(|snapshot| !snapshot.id.is_some_and(|id| ids.contains(&id)));
+        Ok(before - state.balances.len())
+    }
+}
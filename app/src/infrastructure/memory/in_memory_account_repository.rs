@@ -0,0 +1,265 @@
+use crate::domain::{
+    Account, AccountFilter, AccountNumber, AccountRepository, AccountStatus, AccountStatusChange,
+    DomainError, DomainResult, OutboxMessage, Page, PageRequest,
+};
+use crate::infrastructure::memory::store::{contains_ignore_case, InMemoryStore, StoreState};
+use async_trait::async_trait;
+
+pub struct InMemoryAccountRepository {
+    store: InMemoryStore,
+}
+
+impl InMemoryAccountRepository {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+
+    fn matches(account: &Account, filter: &AccountFilter) -> bool {
+        if let Some(search) = &filter.search {
+            if !contains_ignore_case(account.account_number.value(), search)
+                && !contains_ignore_case(&account.account_name, search)
+            {
+                return false;
+            }
+        }
+        if let Some(balance_policy) = &filter.balance_policy {
+            if account.balance_policy.as_str() != balance_policy {
+                return false;
+            }
+        }
+        if let Some(created_from) = filter.created_from {
+            if account.created_at < Some(created_from) {
+                return false;
+            }
+        }
+        if let Some(created_to) = filter.created_to {
+            if account.created_at >= Some(created_to) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl AccountRepository for InMemoryAccountRepository {
+    async fn save(&self, account: &Account) -> DomainResult<Account> {
+        let mut state = self.store.lock();
+
+        // Same error the Diesel repository maps the unique index on account_number to
+        if state
+            .accounts
+            .values()
+            .any(|existing| existing.account_number == account.account_number)
+        {
+            return Err(DomainError::DuplicateAccountNumber(
+                account.account_number.value().to_string(),
+            ));
+        }
+
+        let now = StoreState::now();
+        let mut saved = account.clone();
+        saved.id = Some(state.next_id("accounts"));
+        saved.created_at = Some(now);
+        saved.updated_at = Some(now);
+
+        state
+            .accounts
+            .insert(saved.id.unwrap_or_default(), saved.clone());
+        state.enqueue(OutboxMessage::account_created(&saved));
+        Ok(saved)
+    }
+
+    async fn find_by_id(&self, id: i32) -> DomainResult<Account> {
+        self.store.lock().account(id).cloned()
+    }
+
+    async fn find_by_account_number(
+        &self,
+        account_number: &AccountNumber,
+    ) -> DomainResult<Account> {
+        self.store
+            .lock()
+            .accounts
+            .values()
+            .find(|account| &account.account_number == account_number)
+            .cloned()
+            .ok_or_else(|| {
+                DomainError::AccountNotFound(format!(
+                    "Account with number {} not found",
+                    account_number
+                ))
+            })
+    }
+
+    async fn find_all(&self) -> DomainResult<Vec<Account>> {
+        Ok(self.store.lock().accounts.values().cloned().collect())
+    }
+
+    async fn find_page(
+        &self,
+        filter: &AccountFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<Account>> {
+        let state = self.store.lock();
+        let after = page.after.unwrap_or(i32::MIN);
+
+        // One extra row tells whether there is a next page
+        let accounts = state
+            .accounts
+            .values()
+            .filter(|account| account.id.is_some_and(|id| id > after))
+            .filter(|account| Self::matches(account, filter))
+            .take(page.limit as usize + 1)
+            .cloned()
+            .collect();
+
+        Ok(Page::from_overfetch(accounts, page.limit, |account| {
+            account.id
+        }))
+    }
+
+    async fn update(&self, account: &Account) -> DomainResult<Account> {
+        let account_id = account.id.ok_or_else(|| {
+            DomainError::ValidationError("Cannot update account without id".to_string())
+        })?;
+
+        let mut state = self.store.lock();
+        let stored = state.accounts.get_mut(&account_id).ok_or_else(|| {
+            DomainError::RepositoryError(format!(
+                "Failed to update account: account {} not found",
+                account_id
+            ))
+        })?;
+
+        stored.account_name = account.account_name.clone();
+        stored.balance_policy = account.balance_policy;
+        stored.updated_at = Some(StoreState::now());
+        Ok(stored.clone())
+    }
+
+    async fn change_status(
+        &self,
+        change: &AccountStatusChange,
+    ) -> DomainResult<AccountStatusChange> {
+        let mut state = self.store.lock();
+        let account_id = change.account_id;
+
+        let from_status = state.account(account_id)?.status;
+        from_status.ensure_can_transition_to(change.to_status, account_id)?;
+
+        if change.to_status == AccountStatus::Closed {
+            let balance = state.balance_of(account_id)?;
+            if balance.value() != 0 {
+                return Err(DomainError::BalanceNotZero {
+                    account_id,
+                    balance: balance.value(),
+                });
+            }
+        }
+
+        let now = StoreState::now();
+        let mut saved = change.clone();
+        saved.id = Some(state.next_id("account_status_changes"));
+        saved.from_status = from_status;
+        saved.created_at = Some(now);
+
+        if let Some(account) = state.accounts.get_mut(&account_id) {
+            account.status = change.to_status;
+            account.updated_at = Some(now);
+        }
+        state.status_changes.push(saved.clone());
+        Ok(saved)
+    }
+
+    async fn find_status_changes(&self, account_id: i32) -> DomainResult<Vec<AccountStatusChange>> {
+        Ok(self
+            .store
+            .lock()
+            .status_changes
+            .iter()
+            .filter(|change| change.account_id == account_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn exists_by_account_number(&self, account_number: &AccountNumber) -> DomainResult<bool> {
+        Ok(self
+            .store
+            .lock()
+            .accounts
+            .values()
+            .any(|account| &account.account_number == account_number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Currency;
+
+    fn account(number: &str, name: &str) -> Account {
+        Account::new(
+            AccountNumber::new(number.to_string()).unwrap(),
+            name.to_string(),
+            Currency::USD,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_save_rejects_duplicate_account_number() {
+        let repo = InMemoryAccountRepository::new(InMemoryStore::new());
+
+        let saved = repo.save(&account("ACC001", "First")).await.unwrap();
+        let result = repo.save(&account("ACC001", "Second")).await;
+
+        assert!(saved.id.is_some());
+        assert!(matches!(
+            result,
+            Err(DomainError::DuplicateAccountNumber(number)) if number == "ACC001"
+        ));
+        assert!(repo
+            .exists_by_account_number(&saved.account_number)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_find_page_filters_in_id_order() {
+        let repo = InMemoryAccountRepository::new(InMemoryStore::new());
+        for (number, name) in [
+            ("ACC001", "Savings"),
+            ("ACC002", "Checking"),
+            ("ACC003", "Holiday savings"),
+            ("ACC004", "SAVINGS pot"),
+        ] {
+            repo.save(&account(number, name)).await.unwrap();
+        }
+        let filter = AccountFilter {
+            search: Some("savings".to_string()),
+            ..Default::default()
+        };
+
+        let first = repo
+            .find_page(&filter, &PageRequest::new(None, Some(2)).unwrap())
+            .await
+            .unwrap();
+        let second = repo
+            .find_page(
+                &filter,
+                &PageRequest::new(first.next_cursor, Some(2)).unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let numbers = |page: &Page<Account>| {
+            page.items
+                .iter()
+                .map(|account| account.account_number.value().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(&first), ["ACC001", "ACC003"]);
+        assert_eq!(numbers(&second), ["ACC004"]);
+        assert_eq!(second.next_cursor, None);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::domain::entities::IdempotencyRecord;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::IdempotencyRepository;
+use crate::infrastructure::memory::store::{InMemoryStore, StoreState};
+use async_trait::async_trait;
+use chrono::Duration;
+
+pub struct InMemoryIdempotencyRepository {
+    store: InMemoryStore,
+}
+
+impl InMemoryIdempotencyRepository {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepository for InMemoryIdempotencyRepository {
+    async fn reserve(
+        &self,
+        key: &str,
+        request_fingerprint: &str,
+        ttl: Duration,
+    ) -> DomainResult<Option<IdempotencyRecord>> {
+        let mut state = self.store.lock();
+        let now = StoreState::now();
+
+        // An expired key is free to be claimed again
+        if let Some(existing) = state.idempotency_keys.get(key) {
+            if existing.expires_at > now {
+                return Ok(Some(existing.clone()));
+            }
+        }
+
+        state.idempotency_keys.insert(
+            key.to_string(),
+            IdempotencyRecord {
+                key: key.to_string(),
+                request_fingerprint: request_fingerprint.to_string(),
+                response_status: None,
+                response_body: None,
+                response_content_type: None,
+                created_at: now,
+                expires_at: now + ttl,
+            },
+        );
+        Ok(None)
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        response_status: u16,
+        response_content_type: Option<String>,
+        response_body: &str,
+    ) -> DomainResult<()> {
+        if let Some(record) = self.store.lock().idempotency_keys.get_mut(key) {
+            record.response_status = Some(response_status);
+            record.response_body = Some(response_body.to_string());
+            record.response_content_type = response_content_type;
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> DomainResult<()> {
+        let mut state = self.store.lock();
+        if state
+            .idempotency_keys
+            .get(key)
+            .is_some_and(|record| !record.is_completed())
+        {
+            state.idempotency_keys.remove(key);
+        }
+        Ok(())
+    }
+
+    async fn delete_expired(&self) -> DomainResult<usize> {
+        let mut state = self.store.lock();
+        let now = StoreState::now();
+
+        let before = state.idempotency_keys.len();
+        state
+            .idempotency_keys
+            .retain(|_, record| record.expires_at > now);
+        Ok(before - state.idempotency_keys.len())
+    }
+}
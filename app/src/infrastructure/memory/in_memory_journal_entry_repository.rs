@@ -0,0 +1,41 @@
+use crate::domain::entities::JournalEntry;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::JournalEntryRepository;
+use crate::infrastructure::memory::store::{InMemoryStore, StoreState};
+use async_trait::async_trait;
+
+pub struct InMemoryJournalEntryRepository {
+    store: InMemoryStore,
+}
+
+impl InMemoryJournalEntryRepository {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl JournalEntryRepository for InMemoryJournalEntryRepository {
+    async fn save(&self, entry: &JournalEntry) -> DomainResult<JournalEntry> {
+        let mut state = self.store.lock();
+
+        // The entry and every leg are stored together or not at all, so a rejected
+        // leg (frozen account, insufficient balance) rolls back the others
+        state.atomically(|state| {
+            let mut saved = entry.clone();
+            saved.id = Some(state.next_id("journal_entries"));
+            saved.created_at = Some(StoreState::now());
+
+            saved.legs = saved
+                .linked_legs()
+                .iter()
+                .map(|leg| state.append_event(leg))
+                .collect::<DomainResult<Vec<_>>>()?;
+
+            state
+                .journal_entries
+                .insert(saved.id.unwrap_or_default(), saved.clone());
+            Ok(saved)
+        })
+    }
+}
@@ -0,0 +1,157 @@
+use crate::domain::entities::LedgerEvent;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{LedgerEventFilter, LedgerEventRepository};
+use crate::domain::value_objects::{BalanceDelta, Page, PageRequest};
+use crate::infrastructure::memory::store::{contains_ignore_case, InMemoryStore};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+pub struct InMemoryLedgerEventRepository {
+    store: InMemoryStore,
+}
+
+impl InMemoryLedgerEventRepository {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+
+    fn matches(event: &LedgerEvent, filter: &LedgerEventFilter) -> bool {
+        if filter
+            .account_id
+            .is_some_and(|account_id| event.account_id != account_id)
+        {
+            return false;
+        }
+        if filter
+            .event_type
+            .as_ref()
+            .is_some_and(|event_type| &event.event_type != event_type)
+        {
+            return false;
+        }
+        if filter
+            .min_amount
+            .is_some_and(|min_amount| event.amount.value() < min_amount)
+        {
+            return false;
+        }
+        if filter
+            .max_amount
+            .is_some_and(|max_amount| event.amount.value() > max_amount)
+        {
+            return false;
+        }
+        if let Some(created_from) = filter.created_from {
+            if event.created_at < Some(created_from) {
+                return false;
+            }
+        }
+        if let Some(created_to) = filter.created_to {
+            if event.created_at >= Some(created_to) {
+                return false;
+            }
+        }
+        if let Some(description) = &filter.description {
+            let matched = event
+                .description
+                .as_deref()
+                .is_some_and(|value| contains_ignore_case(value, description));
+            if !matched {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl LedgerEventRepository for InMemoryLedgerEventRepository {
+    async fn save(&self, event: &LedgerEvent) -> DomainResult<LedgerEvent> {
+        self.store.lock().append_event(event)
+    }
+
+    async fn save_at_version(
+        &self,
+        event: &LedgerEvent,
+        expected_version: i64,
+    ) -> DomainResult<LedgerEvent> {
+        self.store
+            .lock()
+            .append_event_expecting(event, Some(expected_version))
+    }
+
+    async fn find_by_id(&self, id: i32) -> DomainResult<LedgerEvent> {
+        let state = self.store.lock();
+        state
+            .events
+            .get(&id)
+            .map(|event| state.event_view(event))
+            .ok_or(DomainError::EventNotFound(id))
+    }
+
+    async fn find_by_account_id(&self, account_id: i32) -> DomainResult<Vec<LedgerEvent>> {
+        let state = self.store.lock();
+        Ok(state
+            .events
+            .values()
+            .filter(|event| event.account_id == account_id)
+            .map(|event| state.event_view(event))
+            .collect())
+    }
+
+    async fn find_page(
+        &self,
+        filter: &LedgerEventFilter,
+        page: &PageRequest<i64>,
+    ) -> DomainResult<Page<LedgerEvent, i64>> {
+        let state = self.store.lock();
+        let after = page.after.unwrap_or(i64::MIN);
+
+        // Ids and sequences are handed out together, so id order is sequence order.
+        // One extra row tells whether there is a next page
+        let events = state
+            .events
+            .values()
+            .filter(|event| event.sequence.is_some_and(|sequence| sequence > after))
+            .filter(|event| Self::matches(event, filter))
+            .take(page.limit as usize + 1)
+            .map(|event| state.event_view(event))
+            .collect();
+
+        Ok(Page::from_overfetch(events, page.limit, |event| {
+            event.sequence
+        }))
+    }
+
+    async fn calculate_balance_since(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+    ) -> DomainResult<BalanceDelta> {
+        Ok(self.store.lock().balance_delta(account_id, after_event_id))
+    }
+
+    async fn calculate_balance_between(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+        through_event_id: i32,
+    ) -> DomainResult<BalanceDelta> {
+        Ok(self
+            .store
+            .lock()
+            .balance_delta_between(account_id, after_event_id, through_event_id))
+    }
+
+    async fn calculate_balance_before(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+        before: NaiveDateTime,
+    ) -> DomainResult<BalanceDelta> {
+        Ok(self
+            .store
+            .lock()
+            .balance_delta_before(account_id, after_event_id, Some(before)))
+    }
+}
@@ -0,0 +1,107 @@
+use crate::domain::entities::OutboxMessage;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{OutboxFilter, OutboxRepository};
+use crate::domain::value_objects::{Page, PageRequest};
+use crate::infrastructure::memory::store::{InMemoryStore, StoreState};
+use async_trait::async_trait;
+use chrono::Duration;
+
+pub struct InMemoryOutboxRepository {
+    store: InMemoryStore,
+}
+
+impl InMemoryOutboxRepository {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+
+    fn matches(message: &OutboxMessage, filter: &OutboxFilter) -> bool {
+        filter
+            .status
+            .is_none_or(|status| message.status() == status)
+            && filter
+                .topic
+                .as_ref()
+                .is_none_or(|topic| &message.topic == topic)
+    }
+
+    fn message_mut(state: &mut StoreState, id: i32) -> DomainResult<&mut OutboxMessage> {
+        state
+            .outbox
+            .get_mut(&id)
+            .ok_or_else(|| DomainError::RepositoryError(format!("Outbox message {} not found", id)))
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for InMemoryOutboxRepository {
+    async fn claim_due(&self, lease: Duration, limit: i64) -> DomainResult<Vec<OutboxMessage>> {
+        let mut state = self.store.lock();
+        let now = StoreState::now();
+        let lease_until = now + lease;
+
+        let claimed = state
+            .outbox
+            .values_mut()
+            .filter(|message| message.published_at.is_none())
+            .filter(|message| message.next_attempt_at.is_none_or(|due| due <= now))
+            .take(limit.max(0) as usize)
+            .map(|message| {
+                message.next_attempt_at = Some(lease_until);
+                message.clone()
+            })
+            .collect();
+
+        Ok(claimed)
+    }
+
+    async fn mark_published(&self, id: i32) -> DomainResult<()> {
+        let mut state = self.store.lock();
+        let message = Self::message_mut(&mut state, id)?;
+        message.attempts += 1;
+        message.published_at = Some(StoreState::now());
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i32, error: &str, retry_in: Duration) -> DomainResult<()> {
+        let mut state = self.store.lock();
+        let message = Self::message_mut(&mut state, id)?;
+        message.attempts += 1;
+        message.last_error = Some(error.to_string());
+        message.next_attempt_at = Some(StoreState::now() + retry_in);
+        Ok(())
+    }
+
+    async fn count_unpublished(&self) -> DomainResult<i64> {
+        let state = self.store.lock();
+        let count = state
+            .outbox
+            .values()
+            .filter(|message| message.published_at.is_none())
+            .count();
+        Ok(count as i64)
+    }
+
+    async fn find_page(
+        &self,
+        filter: &OutboxFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<OutboxMessage>> {
+        let state = self.store.lock();
+        let after = page.after.unwrap_or(i32::MIN);
+
+        // One extra row tells whether there is a next page
+        let messages = state
+            .outbox
+            .values()
+            .filter(|message| message.id.is_some_and(|id| id > after))
+            .filter(|message| Self::matches(message, filter))
+            .take(page.limit as usize + 1)
+            .cloned()
+            .collect();
+
+        Ok(Page::from_overfetch(messages, page.limit, |message| {
+            message.id
+        }))
+    }
+}
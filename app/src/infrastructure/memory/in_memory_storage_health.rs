@@ -0,0 +1,13 @@
+use crate::infrastructure::health::{HealthCheck, StorageHealth};
+use async_trait::async_trait;
+
+/// In-memory storage has no connection to lose, so it is always ready
+#[derive(Default)]
+pub struct InMemoryStorageHealth;
+
+#[async_trait]
+impl StorageHealth for InMemoryStorageHealth {
+    async fn check(&self) -> Vec<HealthCheck> {
+        vec![HealthCheck::ok("storage")]
+    }
+}
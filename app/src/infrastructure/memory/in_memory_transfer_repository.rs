@@ -0,0 +1,37 @@
+use crate::domain::entities::Transfer;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::TransferRepository;
+use crate::infrastructure::memory::store::{InMemoryStore, StoreState};
+use async_trait::async_trait;
+
+pub struct InMemoryTransferRepository {
+    store: InMemoryStore,
+}
+
+impl InMemoryTransferRepository {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl TransferRepository for InMemoryTransferRepository {
+    async fn save(&self, transfer: &Transfer) -> DomainResult<Transfer> {
+        let mut state = self.store.lock();
+
+        // The transfer and both legs are stored together or not at all
+        state.atomically(|state| {
+            let mut saved = transfer.clone();
+            saved.id = Some(state.next_id("transfers"));
+            saved.created_at = Some(StoreState::now());
+
+            saved.debit_event = Some(state.append_event(&saved.debit_leg())?);
+            saved.credit_event = Some(state.append_event(&saved.credit_leg())?);
+
+            state
+                .transfers
+                .insert(saved.id.unwrap_or_default(), saved.clone());
+            Ok(saved)
+        })
+    }
+}
@@ -0,0 +1,193 @@
+use crate::domain::entities::{WebhookDelivery, WebhookSubscription};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{WebhookDeliveryFilter, WebhookRepository};
+use crate::domain::value_objects::{Page, PageRequest};
+use crate::infrastructure::memory::store::{InMemoryStore, StoreState};
+use async_trait::async_trait;
+use chrono::Duration;
+
+pub struct InMemoryWebhookRepository {
+    store: InMemoryStore,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+
+    fn matches(delivery: &WebhookDelivery, filter: &WebhookDeliveryFilter) -> bool {
+        filter
+            .subscription_id
+            .is_none_or(|id| delivery.subscription_id == id)
+            && filter
+                .status
+                .is_none_or(|status| delivery.status() == status)
+    }
+
+    fn delivery_mut(state: &mut StoreState, id: i32) -> DomainResult<&mut WebhookDelivery> {
+        state
+            .webhook_deliveries
+            .get_mut(&id)
+            .ok_or(DomainError::WebhookDeliveryNotFound(id))
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for InMemoryWebhookRepository {
+    async fn save_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<WebhookSubscription> {
+        let mut state = self.store.lock();
+
+        // Same outcome as the foreign key on account_id
+        if let Some(account_id) = subscription.account_id {
+            state.account(account_id)?;
+        }
+
+        let mut saved = subscription.clone();
+        let id = state.next_id("webhook_subscriptions");
+        saved.id = Some(id);
+        saved.created_at = Some(StoreState::now());
+        state.webhook_subscriptions.insert(id, saved.clone());
+        Ok(saved)
+    }
+
+    async fn find_subscription(&self, id: i32) -> DomainResult<WebhookSubscription> {
+        self.store
+            .lock()
+            .webhook_subscriptions
+            .get(&id)
+            .cloned()
+            .ok_or(DomainError::WebhookSubscriptionNotFound(id))
+    }
+
+    async fn find_subscriptions(
+        &self,
+        page: &PageRequest,
+    ) -> DomainResult<Page<WebhookSubscription>> {
+        let state = self.store.lock();
+        let after = page.after.unwrap_or(i32::MIN);
+
+        // One extra row tells whether there is a next page
+        let subscriptions = state
+            .webhook_subscriptions
+            .range(after.saturating_add(1)..)
+            .take(page.limit as usize + 1)
+            .map(|(_, subscription)| subscription.clone())
+            .collect();
+
+        Ok(Page::from_overfetch(
+            subscriptions,
+            page.limit,
+            |subscription| subscription.id,
+        ))
+    }
+
+    async fn delete_subscription(&self, id: i32) -> DomainResult<()> {
+        let mut state = self.store.lock();
+        if state.webhook_subscriptions.remove(&id).is_none() {
+            return Err(DomainError::WebhookSubscriptionNotFound(id));
+        }
+        state
+            .webhook_deliveries
+            .retain(|_, delivery| delivery.subscription_id != id);
+        Ok(())
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        lease: Duration,
+        limit: i64,
+    ) -> DomainResult<Vec<WebhookDelivery>> {
+        let mut state = self.store.lock();
+        let now = StoreState::now();
+        let lease_until = now + lease;
+
+        let claimed = state
+            .webhook_deliveries
+            .values_mut()
+            .filter(|delivery| delivery.delivered_at.is_none())
+            .filter(|delivery| delivery.dead_lettered_at.is_none())
+            .filter(|delivery| delivery.next_attempt_at.is_none_or(|due| due <= now))
+            .take(limit.max(0) as usize)
+            .map(|delivery| {
+                delivery.next_attempt_at = Some(lease_until);
+                delivery.clone()
+            })
+            .collect();
+
+        Ok(claimed)
+    }
+
+    async fn mark_delivered(&self, id: i32) -> DomainResult<()> {
+        let mut state = self.store.lock();
+        let delivery = Self::delivery_mut(&mut state, id)?;
+        delivery.attempts += 1;
+        delivery.delivered_at = Some(StoreState::now());
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i32, error: &str, retry_in: Duration) -> DomainResult<()> {
+        let mut state = self.store.lock();
+        let delivery = Self::delivery_mut(&mut state, id)?;
+        delivery.attempts += 1;
+        delivery.last_error = Some(error.to_string());
+        delivery.next_attempt_at = Some(StoreState::now() + retry_in);
+        Ok(())
+    }
+
+    async fn mark_dead_lettered(&self, id: i32, error: &str) -> DomainResult<()> {
+        let mut state = self.store.lock();
+        let delivery = Self::delivery_mut(&mut state, id)?;
+        delivery.attempts += 1;
+        delivery.last_error = Some(error.to_string());
+        delivery.dead_lettered_at = Some(StoreState::now());
+        Ok(())
+    }
+
+    async fn find_deliveries(
+        &self,
+        filter: &WebhookDeliveryFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<WebhookDelivery>> {
+        let state = self.store.lock();
+        let after = page.after.unwrap_or(i32::MIN);
+
+        // One extra row tells whether there is a next page
+        let deliveries = state
+            .webhook_deliveries
+            .range(after.saturating_add(1)..)
+            .map(|(_, delivery)| delivery)
+            .filter(|delivery| Self::matches(delivery, filter))
+            .take(page.limit as usize + 1)
+            .cloned()
+            .collect();
+
+        Ok(Page::from_overfetch(deliveries, page.limit, |delivery| {
+            delivery.id
+        }))
+    }
+
+    async fn find_delivery(&self, id: i32) -> DomainResult<WebhookDelivery> {
+        self.store
+            .lock()
+            .webhook_deliveries
+            .get(&id)
+            .cloned()
+            .ok_or(DomainError::WebhookDeliveryNotFound(id))
+    }
+
+    async fn replay_delivery(&self, id: i32) -> DomainResult<WebhookDelivery> {
+        let mut state = self.store.lock();
+        let delivery = Self::delivery_mut(&mut state, id)?;
+        delivery.ensure_replayable()?;
+
+        delivery.attempts = 0;
+        delivery.last_error = None;
+        delivery.delivered_at = None;
+        delivery.dead_lettered_at = None;
+        delivery.next_attempt_at = Some(StoreState::now());
+        Ok(delivery.clone())
+    }
+}
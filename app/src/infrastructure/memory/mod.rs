@@ -0,0 +1,21 @@
+pub mod in_memory_account_balance_repository;
+pub mod in_memory_account_repository;
+pub mod in_memory_idempotency_repository;
+pub mod in_memory_journal_entry_repository;
+pub mod in_memory_ledger_event_repository;
+pub mod in_memory_outbox_repository;
+pub mod in_memory_storage_health;
+pub mod in_memory_transfer_repository;
+pub mod in_memory_webhook_repository;
+mod store;
+
+pub use in_memory_account_balance_repository::InMemoryAccountBalanceRepository;
+pub use in_memory_account_repository::InMemoryAccountRepository;
+pub use in_memory_idempotency_repository::InMemoryIdempotencyRepository;
+pub use in_memory_journal_entry_repository::InMemoryJournalEntryRepository;
+pub use in_memory_ledger_event_repository::InMemoryLedgerEventRepository;
+pub use in_memory_outbox_repository::InMemoryOutboxRepository;
+pub use in_memory_storage_health::InMemoryStorageHealth;
+pub use in_memory_transfer_repository::InMemoryTransferRepository;
+pub use in_memory_webhook_repository::InMemoryWebhookRepository;
+pub use store::InMemoryStore;
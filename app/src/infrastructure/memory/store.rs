@@ -0,0 +1,483 @@
+use crate::domain::entities::{
+    Account, AccountBalance, AccountStatusChange, EventType, IdempotencyRecord, JournalEntry,
+    LedgerEvent, OutboxMessage, Transfer, WebhookDelivery, WebhookSubscription,
+    BALANCE_BELOW_THRESHOLD,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::value_objects::{Balance, BalanceDelta, BalancePolicy};
+use chrono::{NaiveDateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Shared state behind the in-memory repositories, playing the role of the database.
+///
+/// Every repository built from the same store sees the same data. Each operation
+/// holds the store's lock from start to finish, which serializes writes the way the
+/// account row locks do in PostgreSQL.
+#[derive(Clone, Default)]
+pub struct InMemoryStore {
+    state: Arc<Mutex<StoreState>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, StoreState> {
+        // Keep serving after a panic in another holder instead of failing every later
+        // call; the store backs tests and demos, not production data
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct StoreState {
+    pub accounts: BTreeMap<i32, Account>,
+    pub status_changes: Vec<AccountStatusChange>,
+    /// Stored without `reversed_by_event_id`, which is derived from `reversals`
+    pub events: BTreeMap<i32, LedgerEvent>,
+    /// Reversed event id to the id of the event reversing it
+    pub reversals: HashMap<i32, i32>,
+    pub balances: Vec<AccountBalance>,
+    pub transfers: BTreeMap<i32, Transfer>,
+    pub journal_entries: BTreeMap<i32, JournalEntry>,
+    pub idempotency_keys: HashMap<String, IdempotencyRecord>,
+    pub outbox: BTreeMap<i32, OutboxMessage>,
+    pub webhook_subscriptions: BTreeMap<i32, WebhookSubscription>,
+    pub webhook_deliveries: BTreeMap<i32, WebhookDelivery>,
+    /// Last id handed out per table
+    sequences: HashMap<&'static str, i32>,
+}
+
+impl StoreState {
+    /// Next id from the table's sequence. Like database sequences, ids used by a
+    /// rolled back write are not handed out again.
+    pub fn next_id(&mut self, table: &'static str) -> i32 {
+        let last_id = self.sequences.entry(table).or_default();
+        *last_id += 1;
+        *last_id
+    }
+
+    pub fn now() -> NaiveDateTime {
+        Utc::now().naive_utc()
+    }
+
+    pub fn account(&self, account_id: i32) -> DomainResult<&Account> {
+        self.accounts.get(&account_id).ok_or_else(|| {
+            DomainError::AccountNotFound(format!("Account with id {} not found", account_id))
+        })
+    }
+
+    /// The event as the repositories return it, with `reversed_by_event_id` filled in
+    pub fn event_view(&self, event: &LedgerEvent) -> LedgerEvent {
+        let mut event = event.clone();
+        event.reversed_by_event_id = event.id.and_then(|id| self.reversals.get(&id).copied());
+        event
+    }
+
+    /// Runs a multi-row write, discarding the ledger events, outbox messages and
+    /// webhook deliveries it appended if it fails, so a rejected leg rolls back the
+    /// legs written before it
+    pub fn atomically<T>(
+        &mut self,
+        write: impl FnOnce(&mut StoreState) -> DomainResult<T>,
+    ) -> DomainResult<T> {
+        let first_new_id = self.sequences.get("ledger_events").copied().unwrap_or(0) + 1;
+        let first_new_message_id = self.sequences.get("outbox_messages").copied().unwrap_or(0) + 1;
+        let first_new_delivery_id = self
+            .sequences
+            .get("webhook_deliveries")
+            .copied()
+            .unwrap_or(0)
+            + 1;
+        let result = write(self);
+        if result.is_err() {
+            for event in self.events.split_off(&first_new_id).into_values() {
+                if let Some(reversed_id) = event.reverses_event_id {
+                    self.reversals.remove(&reversed_id);
+                }
+            }
+            self.outbox.split_off(&first_new_message_id);
+            self.webhook_deliveries.split_off(&first_new_delivery_id);
+        }
+        result
+    }
+
+    /// Appends a ledger event under the same rules as the database writer: the
+    /// account must be active, the event in the account's currency, a reversal must
+    /// not reverse an already reversed event, and DEBIT events must respect the
+    /// account's balance policy.
+    pub fn append_event(&mut self, event: &LedgerEvent) -> DomainResult<LedgerEvent> {
+        self.append_event_expecting(event, None)
+    }
+
+    /// Like `append_event`, failing with a version conflict unless the account's
+    /// latest event has `expected_version` when it is set
+    pub fn append_event_expecting(
+        &mut self,
+        event: &LedgerEvent,
+        expected_version: Option<i64>,
+    ) -> DomainResult<LedgerEvent> {
+        let account = self.account(event.account_id)?;
+        let version = self.current_version(event.account_id);
+        if let Some(expected) = expected_version {
+            if expected != version {
+                return Err(DomainError::VersionConflict {
+                    account_id: event.account_id,
+                    expected,
+                    actual: version,
+                });
+            }
+        }
+
+        account.status.ensure_accepts_events(event.account_id)?;
+        event.amount.ensure_currency(account.currency)?;
+        let policy = account.balance_policy;
+
+        if let Some(event_id) = event.reverses_event_id {
+            if let Some(&reversal_event_id) = self.reversals.get(&event_id) {
+                return Err(DomainError::EventAlreadyReversed {
+                    event_id,
+                    reversal_event_id,
+                });
+            }
+        }
+
+        if event.event_type == EventType::Debit && policy != BalancePolicy::Unrestricted {
+            let balance = self.balance_of(event.account_id)?;
+            policy.check_debit(balance, &event.amount)?;
+        }
+
+        let mut saved = event.clone();
+        saved.id = Some(self.next_id("ledger_events"));
+        saved.version = Some(version + 1);
+        saved.sequence = Some(i64::from(self.next_id("ledger_events_sequence")));
+        saved.reversed_by_event_id = None;
+        saved.created_at = Some(Self::now());
+
+        let id = saved.id.unwrap_or_default();
+        if let Some(reversed_id) = saved.reverses_event_id {
+            self.reversals.insert(reversed_id, id);
+        }
+        self.events.insert(id, saved.clone());
+        let message = self.enqueue(OutboxMessage::ledger_event_created(&saved));
+        self.notify_balance_change(message.id.unwrap_or_default(), &saved)?;
+
+        Ok(saved)
+    }
+
+    /// Adds a message to the outbox, due immediately, along with a delivery for
+    /// every webhook subscription matching it
+    pub fn enqueue(&mut self, message: OutboxMessage) -> OutboxMessage {
+        let mut saved = message;
+        let id = self.next_id("outbox_messages");
+        saved.id = Some(id);
+        saved.created_at = Some(Self::now());
+        saved.next_attempt_at = saved.created_at;
+        self.outbox.insert(id, saved.clone());
+
+        let subscriptions: Vec<WebhookSubscription> =
+            self.webhook_subscriptions.values().cloned().collect();
+        for delivery in WebhookDelivery::for_message(&subscriptions, id, &saved) {
+            self.add_delivery(delivery);
+        }
+        saved
+    }
+
+    /// Adds `balance.below_threshold` deliveries for the subscriptions whose
+    /// threshold the saved event crossed
+    fn notify_balance_change(&mut self, message_id: i32, event: &LedgerEvent) -> DomainResult<()> {
+        let watchers: Vec<WebhookSubscription> = self
+            .webhook_subscriptions
+            .values()
+            .filter(|subscription| {
+                subscription.matches(BALANCE_BELOW_THRESHOLD, Some(event.account_id))
+            })
+            .cloned()
+            .collect();
+        if watchers.is_empty() {
+            return Ok(());
+        }
+
+        let balance = self.balance_of(event.account_id)?.value();
+        for delivery in WebhookDelivery::for_balance_change(&watchers, message_id, event, balance) {
+            self.add_delivery(delivery);
+        }
+        Ok(())
+    }
+
+    fn add_delivery(&mut self, delivery: WebhookDelivery) {
+        let mut saved = delivery;
+        let id = self.next_id("webhook_deliveries");
+        saved.id = Some(id);
+        saved.created_at = Some(Self::now());
+        saved.next_attempt_at = saved.created_at;
+        self.webhook_deliveries.insert(id, saved);
+    }
+
+    /// Version of the account's latest event, 0 when it has none
+    pub fn current_version(&self, account_id: i32) -> i64 {
+        self.events
+            .values()
+            .filter(|event| event.account_id == account_id)
+            .filter_map(|event| event.version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Latest snapshot of the account with a known event position
+    pub fn latest_snapshot(&self, account_id: i32) -> Option<&AccountBalance> {
+        self.balances
+            .iter()
+            .filter(|snapshot| snapshot.account_id == account_id)
+            .filter(|snapshot| snapshot.last_event_id.is_some())
+            .max_by_key(|snapshot| (snapshot.last_event_id, snapshot.id))
+    }
+
+    /// Latest positioned snapshot of the account taken before `at`
+    pub fn latest_snapshot_before(
+        &self,
+        account_id: i32,
+        at: NaiveDateTime,
+    ) -> Option<&AccountBalance> {
+        self.balances
+            .iter()
+            .filter(|snapshot| snapshot.account_id == account_id)
+            .filter(|snapshot| snapshot.last_event_id.is_some())
+            .filter(|snapshot| snapshot.snapshot_at.is_some_and(|taken| taken < at))
+            .max_by_key(|snapshot| (snapshot.last_event_id, snapshot.id))
+    }
+
+    /// Sums the account's events with an id greater than `after_event_id`
+    pub fn balance_delta(&self, account_id: i32, after_event_id: i32) -> BalanceDelta {
+        self.balance_delta_before(account_id, after_event_id, None)
+    }
+
+    /// Sums the account's events with an id in `after_event_id + 1..=through_event_id`
+    pub fn balance_delta_between(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+        through_event_id: i32,
+    ) -> BalanceDelta {
+        if through_event_id <= after_event_id {
+            return BalanceDelta::new(0, None, 0);
+        }
+        let events = self.events.range(after_event_id + 1..=through_event_id);
+        Self::sum_events(events.filter(|(_, event)| event.account_id == account_id))
+    }
+
+    /// Like `balance_delta`, skipping events created at or after `before` when set
+    pub fn balance_delta_before(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+        before: Option<NaiveDateTime>,
+    ) -> BalanceDelta {
+        let events = self
+            .events
+            .range(after_event_id + 1..)
+            .filter(|(_, event)| {
+                event.account_id == account_id
+                    && before.is_none_or(|before| event.created_at.is_none_or(|at| at < before))
+            });
+        Self::sum_events(events)
+    }
+
+    fn sum_events<'a>(events: impl Iterator<Item = (&'a i32, &'a LedgerEvent)>) -> BalanceDelta {
+        let mut delta = BalanceDelta::new(0, None, 0);
+        for (id, event) in events {
+            delta.amount += match event.event_type {
+                EventType::Credit => event.amount.value(),
+                EventType::Debit => -event.amount.value(),
+            };
+            delta.last_event_id = Some(*id);
+            delta.event_count += 1;
+        }
+        delta
+    }
+
+    /// Current balance of the account: latest positioned snapshot plus the events after it
+    pub fn balance_of(&self, account_id: i32) -> DomainResult<Balance> {
+        let (base, after_event_id) = match self.latest_snapshot(account_id) {
+            Some(snapshot) => (snapshot.balance, snapshot.last_event_id.unwrap_or(0)),
+            None => (Balance::zero(), 0),
+        };
+
+        base.adjust(self.balance_delta(account_id, after_event_id).amount)
+    }
+}
+
+/// Case-insensitive substring match, the in-memory counterpart of ILIKE '%term%'
+pub(crate) fn contains_ignore_case(value: &str, term: &str) -> bool {
+    value.to_lowercase().contains(&term.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{AccountNumber, Currency, Money};
+
+    fn open_account(state: &mut StoreState, number: &str) -> i32 {
+        let mut account = Account::new(
+            AccountNumber::new(number.to_string()).unwrap(),
+            "Test Account".to_string(),
+            Currency::USD,
+        );
+        let id = state.next_id("accounts");
+        account.id = Some(id);
+        state.accounts.insert(id, account);
+        id
+    }
+
+    fn event(account_id: i32, event_type: EventType, amount: i64) -> LedgerEvent {
+        LedgerEvent::new(
+            account_id,
+            event_type,
+            Money::new(amount, Currency::USD).unwrap(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_append_event_checks_balance_policy() {
+        let mut state = StoreState::default();
+        let account_id = open_account(&mut state, "ACC001");
+
+        state
+            .append_event(&event(account_id, EventType::Credit, 100))
+            .unwrap();
+        let result = state.append_event(&event(account_id, EventType::Debit, 150));
+
+        assert!(matches!(
+            result,
+            Err(DomainError::InsufficientBalance { .. })
+        ));
+        assert_eq!(state.balance_of(account_id).unwrap(), Balance::new(100));
+    }
+
+    #[test]
+    fn test_atomically_rolls_back_appended_events() {
+        let mut state = StoreState::default();
+        let funded = open_account(&mut state, "ACC001");
+        let empty = open_account(&mut state, "ACC002");
+        state
+            .append_event(&event(funded, EventType::Credit, 100))
+            .unwrap();
+
+        let result = state.atomically(|state| {
+            state.append_event(&event(funded, EventType::Debit, 50))?;
+            state.append_event(&event(empty, EventType::Debit, 50))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(state.events.len(), 1);
+        assert_eq!(state.outbox.len(), 1);
+        assert_eq!(state.balance_of(funded).unwrap(), Balance::new(100));
+    }
+
+    #[test]
+    fn test_versions_count_per_account_and_guard_appends() {
+        let mut state = StoreState::default();
+        let first = open_account(&mut state, "ACC001");
+        let second = open_account(&mut state, "ACC002");
+
+        state
+            .append_event(&event(first, EventType::Credit, 100))
+            .unwrap();
+        let other = state
+            .append_event(&event(second, EventType::Credit, 100))
+            .unwrap();
+        let next = state
+            .append_event_expecting(&event(first, EventType::Credit, 50), Some(1))
+            .unwrap();
+
+        assert_eq!(other.version, Some(1));
+        assert_eq!(state.outbox.len(), 3);
+        assert_eq!(state.outbox[&3].payload["version"], 2);
+        assert_eq!(next.version, Some(2));
+        assert!(next.sequence > other.sequence);
+
+        // A writer that read version 1 lost the race to the append above
+        let result = state.append_event_expecting(&event(first, EventType::Debit, 50), Some(1));
+        assert!(matches!(
+            result,
+            Err(DomainError::VersionConflict {
+                expected: 1,
+                actual: 2,
+                ..
+            })
+        ));
+        assert_eq!(state.current_version(first), 2);
+    }
+
+    #[test]
+    fn test_webhook_deliveries_are_written_with_messages() {
+        let mut state = StoreState::default();
+        let account_id = open_account(&mut state, "ACC001");
+        let mut subscription = WebhookSubscription::new(
+            "https://hooks.example.com".to_string(),
+            vec![
+                crate::domain::entities::LEDGER_EVENT_CREATED.to_string(),
+                BALANCE_BELOW_THRESHOLD.to_string(),
+            ],
+            "whsec_test".to_string(),
+        )
+        .with_balance_threshold(Some(80));
+        let subscription_id = state.next_id("webhook_subscriptions");
+        subscription.id = Some(subscription_id);
+        state
+            .webhook_subscriptions
+            .insert(subscription_id, subscription);
+
+        state
+            .append_event(&event(account_id, EventType::Credit, 100))
+            .unwrap();
+        state
+            .append_event(&event(account_id, EventType::Debit, 30))
+            .unwrap();
+        let result = state.atomically(|state| {
+            state.append_event(&event(account_id, EventType::Debit, 10))?;
+            state.append_event(&event(account_id, EventType::Debit, 500))
+        });
+
+        assert!(result.is_err());
+        let types: Vec<&str> = state
+            .webhook_deliveries
+            .values()
+            .map(|delivery| delivery.event_type.as_str())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                "ledger_event.created",
+                "ledger_event.created",
+                BALANCE_BELOW_THRESHOLD
+            ]
+        );
+        assert_eq!(state.webhook_deliveries[&3].payload["balance"], 70);
+    }
+
+    #[test]
+    fn test_event_can_be_reversed_once() {
+        let mut state = StoreState::default();
+        let account_id = open_account(&mut state, "ACC001");
+        let original = state
+            .append_event(&event(account_id, EventType::Credit, 100))
+            .unwrap();
+
+        let reversal = original.reversal(None).unwrap();
+        let saved = state.append_event(&reversal).unwrap();
+
+        let view = state.event_view(&state.events[&original.id.unwrap()]);
+        assert_eq!(view.reversed_by_event_id, saved.id);
+        assert!(matches!(
+            state.append_event(&reversal),
+            Err(DomainError::EventAlreadyReversed { .. })
+        ));
+        assert_eq!(state.balance_of(account_id).unwrap(), Balance::zero());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::application::{MessageSink, SinkError};
+use crate::domain::entities::OutboxMessage;
+use async_trait::async_trait;
+use std::path::PathBuf;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Appends each message envelope to a file as one line of JSON; meant for local
+/// runs and tests rather than for feeding other services
+pub struct FileSink {
+    path: PathBuf,
+    // Keeps concurrent publishes from interleaving their lines
+    write_lock: Mutex<()>,
+}
+
+impl FileSink {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            write_lock: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageSink for FileSink {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), SinkError> {
+        let mut line = message.envelope().to_string();
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let write = async {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await?;
+            file.write_all(line.as_bytes()).await?;
+            file.sync_data().await
+        };
+        write
+            .await
+            .map_err(|e| SinkError(format!("cannot write {}: {}", self.path.display(), e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_publish_appends_json_lines() {
+        let path = std::env::temp_dir().join(format!("outbox-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let sink = FileSink::new(path.clone());
+
+        for id in 1..=2 {
+            let mut message = OutboxMessage::new("test", "1".to_string(), json!({"n": id}));
+            message.id = Some(id);
+            sink.publish(&message).await.unwrap();
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["id"], 1);
+        assert_eq!(lines[1]["payload"]["n"], 2);
+    }
+}
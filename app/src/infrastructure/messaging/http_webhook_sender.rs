@@ -0,0 +1,105 @@
+use crate::application::{SinkError, WebhookRequest, WebhookSender};
+use async_trait::async_trait;
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+
+/// POSTs signed webhook requests to subscriber URLs. The delivery id, event type
+/// and signature travel in the `X-Webhook-Id`, `X-Webhook-Event` and
+/// `X-Webhook-Signature` headers.
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+}
+
+impl HttpWebhookSender {
+    pub fn new(timeout: Duration) -> Result<Self, SinkError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| SinkError(format!("cannot build HTTP client: {}", e)))?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, request: &WebhookRequest) -> Result<(), SinkError> {
+        let response = self
+            .client
+            .post(&request.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-Webhook-Id", request.delivery_id.to_string())
+            .header("X-Webhook-Event", &request.event_type)
+            .header("X-Webhook-Signature", request.signature.header_value())
+            .body(request.body.clone())
+            .send()
+            .await
+            .map_err(|e| SinkError(format!("request to {} failed: {}", request.url, e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SinkError(format!("{} answered {}", request.url, status)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::WebhookSignature;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_send_posts_signed_body() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let recorder = received.clone();
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(move |headers: HeaderMap, body: String| {
+                    let recorder = recorder.clone();
+                    async move {
+                        let header = |name: &str| headers[name].to_str().unwrap().to_string();
+                        recorder.lock().unwrap().push((
+                            header("x-webhook-id"),
+                            header("x-webhook-event"),
+                            header("x-webhook-signature"),
+                            body,
+                        ));
+                        StatusCode::OK
+                    }
+                }),
+            )
+            .route("/gone", post(|| async { StatusCode::GONE }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let body = r#"{"id":3,"type":"account.created"}"#.to_string();
+        let mut request = WebhookRequest {
+            url: format!("http://{}/hook", address),
+            delivery_id: 3,
+            event_type: "account.created".to_string(),
+            signature: WebhookSignature::sign("whsec_test", 1_700_000_000, &body),
+            body,
+        };
+        let sender = HttpWebhookSender::new(Duration::from_secs(5)).unwrap();
+        sender.send(&request).await.unwrap();
+
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received.len(), 1);
+        let (id, event_type, signature, body) = &received[0];
+        assert_eq!(id, "3");
+        assert_eq!(event_type, "account.created");
+        assert!(WebhookSignature::parse(signature)
+            .unwrap()
+            .verify("whsec_test", body));
+
+        request.url = format!("http://{}/gone", address);
+        let error = sender.send(&request).await.unwrap_err();
+        assert!(error.0.contains("410"));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod file_sink;
+pub mod http_webhook_sender;
+pub mod nats_sink;
+pub mod webhook_sink;
+
+pub use file_sink::FileSink;
+pub use http_webhook_sender::HttpWebhookSender;
+pub use nats_sink::NatsSink;
+pub use webhook_sink::WebhookSink;
@@ -0,0 +1,202 @@
+use crate::application::{MessageSink, SinkError};
+use crate::domain::entities::OutboxMessage;
+use async_trait::async_trait;
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+const CONNECT: &[u8] = b"CONNECT {\"verbose\":false,\"pedantic\":false,\"name\":\"transaction-processor\",\"lang\":\"rust\",\"protocol\":0}\r\n";
+
+/// Publishes each message envelope to a NATS server, on a subject named after the
+/// message topic, over the plain-text core protocol.
+///
+/// Core NATS does not acknowledge publishes, so every publish is followed by a
+/// PING: the server answers in order, and its PONG means it has processed the
+/// message. TLS and authentication are not supported.
+pub struct NatsSink {
+    address: String,
+    timeout: Duration,
+    connection: Mutex<Option<Connection>>,
+}
+
+struct Connection {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl NatsSink {
+    /// `address` is `host:port`, optionally prefixed with `nats://`
+    pub fn new(address: &str, timeout: Duration) -> Self {
+        let address = address.trim_start_matches("nats://").to_string();
+        Self {
+            address,
+            timeout,
+            connection: Mutex::new(None),
+        }
+    }
+
+    async fn connect(&self) -> io::Result<Connection> {
+        let stream = TcpStream::connect(&self.address).await?;
+        let (reader, writer) = stream.into_split();
+        let mut connection = Connection {
+            reader: BufReader::new(reader),
+            writer,
+        };
+
+        // The server opens with an INFO line describing itself
+        let greeting = connection.read_line().await?;
+        if !greeting.starts_with("INFO") {
+            return Err(protocol_error(format!(
+                "unexpected greeting {:?}",
+                greeting
+            )));
+        }
+        connection.writer.write_all(CONNECT).await?;
+        Ok(connection)
+    }
+
+    async fn publish_on(
+        connection: &mut Connection,
+        subject: &str,
+        payload: &[u8],
+    ) -> io::Result<()> {
+        let mut frame = format!("PUB {} {}\r\n", subject, payload.len()).into_bytes();
+        frame.extend_from_slice(payload);
+        frame.extend_from_slice(b"\r\nPING\r\n");
+        connection.writer.write_all(&frame).await?;
+
+        loop {
+            let line = connection.read_line().await?;
+            match line.as_str() {
+                "PONG" => return Ok(()),
+                "PING" => connection.writer.write_all(b"PONG\r\n").await?,
+                _ if line.starts_with("-ERR") => return Err(protocol_error(line)),
+                // +OK in verbose mode and INFO updates about the cluster
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Connection {
+    async fn read_line(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).await? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed by server",
+            ));
+        }
+        Ok(line.trim_end().to_string())
+    }
+}
+
+fn protocol_error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[async_trait]
+impl MessageSink for NatsSink {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), SinkError> {
+        let payload = message.envelope().to_string();
+        let mut connection = self.connection.lock().await;
+
+        let publish = async {
+            if connection.is_none() {
+                *connection = Some(self.connect().await?);
+            }
+            match connection.as_mut() {
+                Some(open) => Self::publish_on(open, &message.topic, payload.as_bytes()).await,
+                None => Err(protocol_error("not connected".to_string())),
+            }
+        };
+        let result = match tokio::time::timeout(self.timeout, publish).await {
+            Ok(result) => result,
+            Err(_) => Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")),
+        };
+
+        result.map_err(|e| {
+            // Start over with a fresh connection on the next publish
+            *connection = None;
+            SinkError(format!(
+                "publishing to NATS at {} failed: {}",
+                self.address, e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// Accepts one client and answers its first publish with `reply`, returning the
+    /// lines the client sent
+    async fn fake_server(reply: &'static str) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_all(b"INFO {\"server_id\":\"test\"}\r\n")
+                .await
+                .unwrap();
+
+            let mut received = String::new();
+            let mut buffer = [0u8; 1024];
+            while !received.ends_with("PING\r\n") {
+                let read = socket.read(&mut buffer).await.unwrap();
+                received.push_str(std::str::from_utf8(&buffer[..read]).unwrap());
+            }
+            socket.write_all(reply.as_bytes()).await.unwrap();
+            received.lines().map(str::to_string).collect()
+        });
+        (format!("nats://{}", address), server)
+    }
+
+    fn message() -> OutboxMessage {
+        let mut message = OutboxMessage::new("ledger_event.created", "3".to_string(), json!({}));
+        message.id = Some(5);
+        message
+    }
+
+    #[tokio::test]
+    async fn test_publish_waits_for_pong() {
+        let (address, server) = fake_server("PONG\r\n").await;
+        let sink = NatsSink::new(&address, Duration::from_secs(5));
+
+        sink.publish(&message()).await.unwrap();
+
+        let lines = server.await.unwrap();
+        assert!(lines[0].starts_with("CONNECT {"));
+        let payload = message().envelope().to_string();
+        assert_eq!(
+            lines[1],
+            format!("PUB ledger_event.created {}", payload.len())
+        );
+        assert_eq!(lines[2], payload);
+        assert_eq!(lines[3], "PING");
+    }
+
+    #[tokio::test]
+    async fn test_publish_fails_on_server_error() {
+        let (address, server) = fake_server("-ERR 'Permissions Violation'\r\n").await;
+        let sink = NatsSink::new(&address, Duration::from_secs(5));
+
+        let error = sink.publish(&message()).await.unwrap_err();
+
+        assert!(error.0.contains("Permissions Violation"));
+        assert!(sink.connection.lock().await.is_none());
+        server.await.unwrap();
+    }
+}
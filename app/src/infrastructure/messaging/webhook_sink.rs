@@ -0,0 +1,99 @@
+use crate::application::{MessageSink, SinkError};
+use crate::domain::entities::OutboxMessage;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// POSTs each message envelope as JSON to a fixed URL. Any 2xx response counts
+/// as delivered; the message id is sent in `X-Message-Id` for deduplication.
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: String, timeout: Duration) -> Result<Self, SinkError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| SinkError(format!("cannot build HTTP client: {}", e)))?;
+        Ok(Self { client, url })
+    }
+}
+
+#[async_trait]
+impl MessageSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), SinkError> {
+        let id = message.id.unwrap_or_default().to_string();
+        let response = self
+            .client
+            .post(&self.url)
+            .header("X-Message-Id", id)
+            .header("X-Message-Topic", &message.topic)
+            .json(&message.envelope())
+            .send()
+            .await
+            .map_err(|e| SinkError(format!("request to {} failed: {}", self.url, e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SinkError(format!("{} answered {}", self.url, status)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_publish_posts_envelope() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let recorder = received.clone();
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(
+                    move |headers: HeaderMap, Json(body): Json<serde_json::Value>| {
+                        let recorder = recorder.clone();
+                        async move {
+                            let id = headers["x-message-id"].to_str().unwrap().to_string();
+                            recorder.lock().unwrap().push((id, body));
+                            StatusCode::NO_CONTENT
+                        }
+                    },
+                ),
+            )
+            .route("/down", post(|| async { StatusCode::SERVICE_UNAVAILABLE }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut message = OutboxMessage::new("test.created", "7".to_string(), json!({"n": 1}));
+        message.id = Some(12);
+
+        let sink =
+            WebhookSink::new(format!("http://{}/hook", address), Duration::from_secs(5)).unwrap();
+        sink.publish(&message).await.unwrap();
+
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0, "12");
+        assert_eq!(received[0].1["topic"], "test.created");
+        assert_eq!(received[0].1["payload"]["n"], 1);
+
+        let down =
+            WebhookSink::new(format!("http://{}/down", address), Duration::from_secs(5)).unwrap();
+        let error = down.publish(&message).await.unwrap_err();
+        assert!(error.0.contains("503"));
+    }
+}
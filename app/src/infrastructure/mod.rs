@@ -1,5 +1,17 @@
+pub mod health;
+pub mod memory;
+pub mod messaging;
 pub mod persistence;
 
+pub use health::{HealthCheck, StorageHealth};
+pub use memory::{
+    InMemoryAccountBalanceRepository, InMemoryAccountRepository, InMemoryIdempotencyRepository,
+    InMemoryJournalEntryRepository, InMemoryLedgerEventRepository, InMemoryOutboxRepository,
+    InMemoryStorageHealth, InMemoryStore, InMemoryTransferRepository, InMemoryWebhookRepository,
+};
+pub use messaging::{FileSink, HttpWebhookSender, NatsSink, WebhookSink};
 pub use persistence::{
-    DieselAccountBalanceRepository, DieselAccountRepository, DieselLedgerEventRepository,
+    DieselAccountBalanceRepository, DieselAccountRepository, DieselIdempotencyRepository,
+    DieselJournalEntryRepository, DieselLedgerEventRepository, DieselOutboxRepository,
+    DieselStorageHealth, DieselTransferRepository, DieselWebhookRepository,
 };
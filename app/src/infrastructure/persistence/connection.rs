@@ -0,0 +1,29 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::DbPool;
+use diesel::PgConnection;
+use tracing::error;
+
+/// Runs `f` with a pooled connection on tokio's blocking thread pool.
+///
+/// Diesel queries and r2d2 checkouts are synchronous; running them on the async
+/// worker threads would stall every other request scheduled on the same worker
+/// while a query or a pool wait is in progress.
+pub(crate) async fn with_connection<T, F>(pool: &DbPool, f: F) -> DomainResult<T>
+where
+    F: FnOnce(&mut PgConnection) -> DomainResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = pool.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| {
+            error!("Failed to get DB connection: {}", e);
+            DomainError::RepositoryError(format!("Failed to get DB connection: {}", e))
+        })?;
+        f(&mut conn)
+    })
+    .await
+    .map_err(|e| {
+        error!("Database task failed: {}", e);
+        DomainError::RepositoryError(format!("Database task failed: {}", e))
+    })?
+}
@@ -1,10 +1,12 @@
 use crate::domain::entities::AccountBalance;
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::domain::repositories::AccountBalanceRepository;
-use crate::domain::value_objects::Money;
+use crate::domain::value_objects::{Balance, Currency};
+use crate::infrastructure::persistence::connection::with_connection;
 use crate::models::{AccountBalance as DbAccountBalance, NewAccountBalance};
 use crate::schema::account_balances;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 
@@ -21,7 +23,9 @@ impl DieselAccountBalanceRepository {
         Ok(AccountBalance {
             id: Some(db_balance.id),
             account_id: db_balance.account_id,
-            balance: Money::new(db_balance.balance)?,
+            balance: Balance::new(db_balance.balance),
+            currency: Currency::from_code(&db_balance.currency)?,
+            last_event_id: db_balance.last_event_id,
             snapshot_at: Some(db_balance.snapshot_at),
         })
     }
@@ -30,62 +34,104 @@ impl DieselAccountBalanceRepository {
         NewAccountBalance {
             account_id: balance.account_id,
             balance: balance.balance.value(),
+            last_event_id: balance.last_event_id,
+            currency: balance.currency.code().to_string(),
         }
     }
+
+    /// Latest snapshot of the account with a known event position
+    pub(crate) fn latest_snapshot(
+        conn: &mut PgConnection,
+        account_id: i32,
+    ) -> QueryResult<Option<DbAccountBalance>> {
+        account_balances::table
+            .filter(account_balances::account_id.eq(account_id))
+            .filter(account_balances::last_event_id.is_not_null())
+            .order((
+                account_balances::last_event_id.desc(),
+                account_balances::id.desc(),
+            ))
+            .first(conn)
+            .optional()
+    }
 }
 
 #[async_trait]
 impl AccountBalanceRepository for DieselAccountBalanceRepository {
     async fn save(&self, balance: &AccountBalance) -> DomainResult<AccountBalance> {
-        let mut conn = self
-            .pool
-            .get()
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
-
         let new_balance = Self::to_db(balance);
 
-        let db_balance: DbAccountBalance = diesel::insert_into(account_balances::table)
-            .values(&new_balance)
-            .get_result(&mut conn)
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+        with_connection(&self.pool, move |conn| {
+            let db_balance: DbAccountBalance = diesel::insert_into(account_balances::table)
+                .values(&new_balance)
+                .get_result(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
 
-        Self::to_domain(db_balance)
+            Self::to_domain(db_balance)
+        })
+        .await
     }
 
     async fn find_latest_by_account_id(
         &self,
         account_id: i32,
     ) -> DomainResult<Option<AccountBalance>> {
-        let mut conn = self
-            .pool
-            .get()
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+        with_connection(&self.pool, move |conn| {
+            let result = Self::latest_snapshot(conn, account_id)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
 
-        let result: Option<DbAccountBalance> = account_balances::table
-            .filter(account_balances::account_id.eq(account_id))
-            .order(account_balances::snapshot_at.desc())
-            .first(&mut conn)
-            .optional()
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+            match result {
+                Some(db_balance) => Ok(Some(Self::to_domain(db_balance)?)),
+                None => Ok(None),
+            }
+        })
+        .await
+    }
 
-        match result {
-            Some(db_balance) => Ok(Some(Self::to_domain(db_balance)?)),
-            None => Ok(None),
-        }
+    async fn find_latest_before(
+        &self,
+        account_id: i32,
+        at: NaiveDateTime,
+    ) -> DomainResult<Option<AccountBalance>> {
+        with_connection(&self.pool, move |conn| {
+            let result: Option<DbAccountBalance> = account_balances::table
+                .filter(account_balances::account_id.eq(account_id))
+                .filter(account_balances::last_event_id.is_not_null())
+                .filter(account_balances::snapshot_at.lt(at))
+                .order((
+                    account_balances::last_event_id.desc(),
+                    account_balances::id.desc(),
+                ))
+                .first(conn)
+                .optional()
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            result.map(Self::to_domain).transpose()
+        })
+        .await
     }
 
     async fn find_all_by_account_id(&self, account_id: i32) -> DomainResult<Vec<AccountBalance>> {
-        let mut conn = self
-            .pool
-            .get()
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+        with_connection(&self.pool, move |conn| {
+            let db_balances: Vec<DbAccountBalance> = account_balances::table
+                .filter(account_balances::account_id.eq(account_id))
+                .order(account_balances::snapshot_at.desc())
+                .load(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
 
-        let db_balances: Vec<DbAccountBalance> = account_balances::table
-            .filter(account_balances::account_id.eq(account_id))
-            .order(account_balances::snapshot_at.desc())
-            .load(&mut conn)
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+            db_balances.into_iter().map(Self::to_domain).collect()
+        })
+        .await
+    }
 
-        db_balances.into_iter().map(Self::to_domain).collect()
+    async fn delete_by_ids(&self, ids: &[i32]) -> DomainResult<usize> {
+        let ids = ids.to_vec();
+
+        with_connection(&self.pool, move |conn| {
+            diesel::delete(account_balances::table.filter(account_balances::id.eq_any(ids)))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))
+        })
+        .await
     }
 }
@@ -1,9 +1,16 @@
-use crate::domain::{Account, AccountNumber, AccountRepository, DomainError, DomainResult};
+use crate::domain::{
+    Account, AccountFilter, AccountNumber, AccountRepository, AccountStatus, AccountStatusChange,
+    BalancePolicy, Currency, DomainError, DomainResult, OutboxMessage, Page, PageRequest,
+};
+use crate::infrastructure::persistence::connection::with_connection;
+use crate::infrastructure::persistence::ledger_writer::{self, TxError};
+use crate::infrastructure::persistence::{DieselLedgerEventRepository, DieselOutboxRepository};
 use crate::models;
 use crate::schema;
 use async_trait::async_trait;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
 use tracing::{error, info};
 
 pub struct DieselAccountRepository {
@@ -14,6 +21,54 @@ impl DieselAccountRepository {
     pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
         Self { pool }
     }
+
+    fn to_domain(db_account: models::Account) -> DomainResult<Account> {
+        let currency = Currency::from_code(&db_account.currency)?;
+
+        Ok(Account {
+            id: Some(db_account.id),
+            account_number: AccountNumber::new(db_account.account_number)?,
+            account_name: db_account.account_name,
+            currency,
+            balance_policy: BalancePolicy::from_parts(
+                &db_account.balance_policy,
+                db_account.overdraft_limit,
+                currency,
+            )?,
+            status: AccountStatus::from_string(&db_account.status)?,
+            created_at: Some(db_account.created_at),
+            updated_at: Some(db_account.updated_at),
+        })
+    }
+
+    /// Maps a failed insert, so that a request racing past the handler's
+    /// duplicate check still gets `DuplicateAccountNumber` from the unique index
+    fn save_error(err: TxError, account_number: String) -> DomainError {
+        match err {
+            TxError::Database(DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                DomainError::DuplicateAccountNumber(account_number)
+            }
+            TxError::Database(e) => {
+                error!("Failed to save account: {}", e);
+                DomainError::RepositoryError(format!("Failed to save account: {}", e))
+            }
+            TxError::Domain(e) => e,
+        }
+    }
+
+    fn status_change_to_domain(
+        db_change: models::AccountStatusChange,
+    ) -> DomainResult<AccountStatusChange> {
+        Ok(AccountStatusChange {
+            id: Some(db_change.id),
+            account_id: db_change.account_id,
+            from_status: AccountStatus::from_string(&db_change.from_status)?,
+            to_status: AccountStatus::from_string(&db_change.to_status)?,
+            actor: db_change.actor,
+            reason: db_change.reason,
+            created_at: Some(db_change.created_at),
+        })
+    }
 }
 
 #[async_trait]
@@ -24,62 +79,56 @@ impl AccountRepository for DieselAccountRepository {
         let new_account = models::NewAccount {
             account_number: account.account_number.value().to_string(),
             account_name: account.account_name.clone(),
+            balance_policy: account.balance_policy.as_str().to_string(),
+            overdraft_limit: account.balance_policy.overdraft_limit(),
+            currency: account.currency.code().to_string(),
+            status: account.status.as_str().to_string(),
         };
 
-        let mut conn = self.pool.get().map_err(|e| {
-            error!("Failed to get DB connection: {}", e);
-            DomainError::RepositoryError(format!("Failed to get DB connection: {}", e))
-        })?;
-
-        let saved = diesel::insert_into(accounts::table)
-            .values(&new_account)
-            .returning(models::Account::as_returning())
-            .get_result(&mut conn)
-            .map_err(|e| {
-                error!("Failed to save account: {}", e);
-                DomainError::RepositoryError(format!("Failed to save account: {}", e))
-            })?;
+        with_connection(&self.pool, move |conn| {
+            let account_number = new_account.account_number.clone();
+            // The account.created outbox message commits or rolls back with the account
+            let saved = conn
+                .transaction::<_, TxError, _>(|conn| {
+                    let db_account = diesel::insert_into(accounts::table)
+                        .values(&new_account)
+                        .returning(models::Account::as_returning())
+                        .get_result(conn)?;
+                    let saved = Self::to_domain(db_account)?;
+                    DieselOutboxRepository::enqueue(conn, &OutboxMessage::account_created(&saved))?;
+                    Ok(saved)
+                })
+                .map_err(|e| Self::save_error(e, account_number))?;
 
-        info!("Account saved to database: id={}", saved.id);
+            info!("Account saved to database: id={:?}", saved.id);
 
-        Ok(Account {
-            id: Some(saved.id),
-            account_number: AccountNumber::new(saved.account_number)?,
-            account_name: saved.account_name,
-            created_at: Some(saved.created_at),
-            updated_at: Some(saved.updated_at),
+            Ok(saved)
         })
+        .await
     }
 
     async fn find_by_id(&self, id: i32) -> DomainResult<Account> {
         use schema::accounts::dsl;
 
-        let mut conn = self.pool.get().map_err(|e| {
-            error!("Failed to get DB connection: {}", e);
-            DomainError::RepositoryError(format!("Failed to get DB connection: {}", e))
-        })?;
-
-        let account = dsl::accounts
-            .find(id)
-            .select(models::Account::as_select())
-            .first(&mut conn)
-            .map_err(|e| {
-                error!("Failed to find account by id {}: {}", id, e);
-                match e {
-                    diesel::result::Error::NotFound => {
-                        DomainError::AccountNotFound(format!("Account with id {} not found", id))
+        with_connection(&self.pool, move |conn| {
+            let account = dsl::accounts
+                .find(id)
+                .select(models::Account::as_select())
+                .first(conn)
+                .map_err(|e| {
+                    error!("Failed to find account by id {}: {}", id, e);
+                    match e {
+                        diesel::result::Error::NotFound => DomainError::AccountNotFound(format!(
+                            "Account with id {} not found",
+                            id
+                        )),
+                        _ => DomainError::RepositoryError(format!("Failed to find account: {}", e)),
                     }
-                    _ => DomainError::RepositoryError(format!("Failed to find account: {}", e)),
-                }
-            })?;
+                })?;
 
-        Ok(Account {
-            id: Some(account.id),
-            account_number: AccountNumber::new(account.account_number)?,
-            account_name: account.account_name,
-            created_at: Some(account.created_at),
-            updated_at: Some(account.updated_at),
+            Self::to_domain(account)
         })
+        .await
     }
 
     async fn find_by_account_number(
@@ -88,63 +137,100 @@ impl AccountRepository for DieselAccountRepository {
     ) -> DomainResult<Account> {
         use schema::accounts::dsl;
 
-        let mut conn = self.pool.get().map_err(|e| {
-            error!("Failed to get DB connection: {}", e);
-            DomainError::RepositoryError(format!("Failed to get DB connection: {}", e))
-        })?;
-
-        let account = dsl::accounts
-            .filter(dsl::account_number.eq(account_number.value()))
-            .select(models::Account::as_select())
-            .first(&mut conn)
-            .map_err(|e| {
-                error!("Failed to find account by number {}: {}", account_number, e);
-                match e {
-                    diesel::result::Error::NotFound => DomainError::AccountNotFound(format!(
-                        "Account with number {} not found",
-                        account_number
-                    )),
-                    _ => DomainError::RepositoryError(format!("Failed to find account: {}", e)),
-                }
-            })?;
+        let account_number = account_number.clone();
+        with_connection(&self.pool, move |conn| {
+            let account = dsl::accounts
+                .filter(dsl::account_number.eq(account_number.value()))
+                .select(models::Account::as_select())
+                .first(conn)
+                .map_err(|e| {
+                    error!("Failed to find account by number {}: {}", account_number, e);
+                    match e {
+                        diesel::result::Error::NotFound => DomainError::AccountNotFound(format!(
+                            "Account with number {} not found",
+                            account_number
+                        )),
+                        _ => DomainError::RepositoryError(format!("Failed to find account: {}", e)),
+                    }
+                })?;
 
-        Ok(Account {
-            id: Some(account.id),
-            account_number: AccountNumber::new(account.account_number)?,
-            account_name: account.account_name,
-            created_at: Some(account.created_at),
-            updated_at: Some(account.updated_at),
+            Self::to_domain(account)
         })
+        .await
     }
 
     async fn find_all(&self) -> DomainResult<Vec<Account>> {
         use schema::accounts::dsl;
 
-        let mut conn = self.pool.get().map_err(|e| {
-            error!("Failed to get DB connection: {}", e);
-            DomainError::RepositoryError(format!("Failed to get DB connection: {}", e))
-        })?;
+        with_connection(&self.pool, |conn| {
+            let accounts = dsl::accounts
+                .select(models::Account::as_select())
+                .load(conn)
+                .map_err(|e| {
+                    error!("Failed to find all accounts: {}", e);
+                    DomainError::RepositoryError(format!("Failed to find all accounts: {}", e))
+                })?;
 
-        let accounts = dsl::accounts
-            .select(models::Account::as_select())
-            .load(&mut conn)
-            .map_err(|e| {
-                error!("Failed to find all accounts: {}", e);
-                DomainError::RepositoryError(format!("Failed to find all accounts: {}", e))
-            })?;
-
-        accounts
-            .into_iter()
-            .map(|account| {
-                Ok(Account {
-                    id: Some(account.id),
-                    account_number: AccountNumber::new(account.account_number)?,
-                    account_name: account.account_name,
-                    created_at: Some(account.created_at),
-                    updated_at: Some(account.updated_at),
-                })
-            })
-            .collect()
+            accounts.into_iter().map(Self::to_domain).collect()
+        })
+        .await
+    }
+
+    async fn find_page(
+        &self,
+        filter: &AccountFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<Account>> {
+        use schema::accounts::dsl;
+
+        let filter = filter.clone();
+        let page = *page;
+        with_connection(&self.pool, move |conn| {
+            let mut query = dsl::accounts
+                .select(models::Account::as_select())
+                .into_boxed();
+
+            if let Some(search) = &filter.search {
+                let pattern = DieselLedgerEventRepository::contains_pattern(search);
+                query = query.filter(
+                    dsl::account_number
+                        .ilike(pattern.clone())
+                        .or(dsl::account_name.ilike(pattern)),
+                );
+            }
+            if let Some(balance_policy) = &filter.balance_policy {
+                query = query.filter(dsl::balance_policy.eq(balance_policy));
+            }
+            if let Some(created_from) = filter.created_from {
+                query = query.filter(dsl::created_at.ge(created_from));
+            }
+            if let Some(created_to) = filter.created_to {
+                query = query.filter(dsl::created_at.lt(created_to));
+            }
+            if let Some(after) = page.after {
+                query = query.filter(dsl::id.gt(after));
+            }
+
+            // One extra row tells whether there is a next page
+            let accounts = query
+                .order(dsl::id.asc())
+                .limit(page.limit + 1)
+                .load(conn)
+                .map_err(|e| {
+                    error!("Failed to list accounts: {}", e);
+                    DomainError::RepositoryError(format!("Failed to list accounts: {}", e))
+                })?;
+
+            let accounts = accounts
+                .into_iter()
+                .map(Self::to_domain)
+                .collect::<DomainResult<Vec<_>>>()?;
+
+            Ok(Page::from_overfetch(accounts, page.limit, |account| {
+                account.id
+            }))
+        })
+        .await
     }
 
     async fn update(&self, account: &Account) -> DomainResult<Account> {
@@ -155,48 +241,180 @@ impl AccountRepository for DieselAccountRepository {
             DomainError::ValidationError("Cannot update account without id".to_string())
         })?;
 
-        let mut conn = self.pool.get().map_err(|e| {
-            error!("Failed to get DB connection: {}", e);
-            DomainError::RepositoryError(format!("Failed to get DB connection: {}", e))
-        })?;
+        let account = account.clone();
+        with_connection(&self.pool, move |conn| {
+            let updated = diesel::update(dsl::accounts.find(account_id))
+                .set((
+                    dsl::account_name.eq(&account.account_name),
+                    dsl::balance_policy.eq(account.balance_policy.as_str()),
+                    dsl::overdraft_limit.eq(account.balance_policy.overdraft_limit()),
+                ))
+                .returning(models::Account::as_returning())
+                .get_result(conn)
+                .map_err(|e| {
+                    error!("Failed to update account: {}", e);
+                    DomainError::RepositoryError(format!("Failed to update account: {}", e))
+                })?;
 
-        let updated = diesel::update(dsl::accounts.find(account_id))
-            .set(dsl::account_name.eq(&account.account_name))
-            .returning(models::Account::as_returning())
-            .get_result(&mut conn)
-            .map_err(|e| {
-                error!("Failed to update account: {}", e);
-                DomainError::RepositoryError(format!("Failed to update account: {}", e))
-            })?;
+            info!("Account updated in database: id={}", updated.id);
 
-        info!("Account updated in database: id={}", updated.id);
+            Self::to_domain(updated)
+        })
+        .await
+    }
 
-        Ok(Account {
-            id: Some(updated.id),
-            account_number: AccountNumber::new(updated.account_number)?,
-            account_name: updated.account_name,
-            created_at: Some(updated.created_at),
-            updated_at: Some(updated.updated_at),
+    async fn change_status(
+        &self,
+        change: &AccountStatusChange,
+    ) -> DomainResult<AccountStatusChange> {
+        use schema::{account_status_changes, accounts};
+
+        let change = change.clone();
+        let account_id = change.account_id;
+        with_connection(&self.pool, move |conn| {
+            let saved = conn
+                .transaction::<_, TxError, _>(|conn| {
+                    // Same lock as ledger appends, so no event can land between the
+                    // balance check and the status update
+                    let account = ledger_writer::lock_account(conn, account_id)?;
+                    account
+                        .status
+                        .ensure_can_transition_to(change.to_status, account_id)?;
+
+                    if change.to_status == AccountStatus::Closed {
+                        let balance = ledger_writer::balance_of(conn, account_id)?;
+                        if balance.value() != 0 {
+                            return Err(TxError::Domain(DomainError::BalanceNotZero {
+                                account_id,
+                                balance: balance.value(),
+                            }));
+                        }
+                    }
+
+                    diesel::update(accounts::table.find(account_id))
+                        .set(accounts::status.eq(change.to_status.as_str()))
+                        .execute(conn)?;
+
+                    let saved = diesel::insert_into(account_status_changes::table)
+                        .values(&models::NewAccountStatusChange {
+                            account_id,
+                            from_status: account.status.as_str().to_string(),
+                            to_status: change.to_status.as_str().to_string(),
+                            actor: change.actor.clone(),
+                            reason: change.reason.clone(),
+                        })
+                        .returning(models::AccountStatusChange::as_returning())
+                        .get_result(conn)?;
+
+                    Ok(saved)
+                })
+                .map_err(|e| {
+                    if let TxError::Database(e) = &e {
+                        error!("Failed to change status of account {}: {}", account_id, e);
+                    }
+                    DomainError::from(e)
+                })?;
+
+            info!(
+                "Account {} status changed from {} to {} by {}",
+                account_id, saved.from_status, saved.to_status, saved.actor
+            );
+
+            Self::status_change_to_domain(saved)
         })
+        .await
+    }
+
+    async fn find_status_changes(&self, account_id: i32) -> DomainResult<Vec<AccountStatusChange>> {
+        use schema::account_status_changes::dsl;
+
+        with_connection(&self.pool, move |conn| {
+            let changes = dsl::account_status_changes
+                .filter(dsl::account_id.eq(account_id))
+                .order(dsl::id.asc())
+                .select(models::AccountStatusChange::as_select())
+                .load(conn)
+                .map_err(|e| {
+                    error!(
+                        "Failed to find status changes of account {}: {}",
+                        account_id, e
+                    );
+                    DomainError::RepositoryError(format!("Failed to find status changes: {}", e))
+                })?;
+
+            changes
+                .into_iter()
+                .map(Self::status_change_to_domain)
+                .collect()
+        })
+        .await
     }
 
     async fn exists_by_account_number(&self, account_number: &AccountNumber) -> DomainResult<bool> {
         use schema::accounts::dsl;
 
-        let mut conn = self.pool.get().map_err(|e| {
-            error!("Failed to get DB connection: {}", e);
-            DomainError::RepositoryError(format!("Failed to get DB connection: {}", e))
-        })?;
+        let account_number = account_number.value().to_string();
+        with_connection(&self.pool, move |conn| {
+            let count: i64 = dsl::accounts
+                .filter(dsl::account_number.eq(account_number))
+                .count()
+                .get_result(conn)
+                .map_err(|e| {
+                    error!("Failed to check account existence: {}", e);
+                    DomainError::RepositoryError(format!(
+                        "Failed to check account existence: {}",
+                        e
+                    ))
+                })?;
+
+            Ok(count > 0)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseConfig;
+    use crate::establish_connection_pool;
+
+    #[test]
+    fn test_unique_violation_is_duplicate_account_number() {
+        let violation = DatabaseError(
+            DatabaseErrorKind::UniqueViolation,
+            Box::new("duplicate key value violates unique constraint".to_string()),
+        );
+
+        let err = DieselAccountRepository::save_error(violation.into(), "ACC001".to_string());
+
+        assert!(matches!(err, DomainError::DuplicateAccountNumber(number) if number == "ACC001"));
+        let err = DieselAccountRepository::save_error(
+            diesel::result::Error::NotFound.into(),
+            "ACC001".to_string(),
+        );
+        assert!(matches!(err, DomainError::RepositoryError(_)));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated PostgreSQL database at DATABASE_URL"]
+    async fn test_save_duplicate_account_number() {
+        let config = DatabaseConfig {
+            url: std::env::var("DATABASE_URL").unwrap(),
+            ..DatabaseConfig::default()
+        };
+        let repository = DieselAccountRepository::new(establish_connection_pool(&config).unwrap());
+        let number = format!("DUP{}", chrono::Utc::now().timestamp_micros());
+        let account = Account::new(
+            AccountNumber::new(number.clone()).unwrap(),
+            "Alice".to_string(),
+            Currency::USD,
+        );
 
-        let count: i64 = dsl::accounts
-            .filter(dsl::account_number.eq(account_number.value()))
-            .count()
-            .get_result(&mut conn)
-            .map_err(|e| {
-                error!("Failed to check account existence: {}", e);
-                DomainError::RepositoryError(format!("Failed to check account existence: {}", e))
-            })?;
+        // No handler pre-check here, so the second insert reaches the unique index
+        repository.save(&account).await.unwrap();
+        let result = repository.save(&account).await;
 
-        Ok(count > 0)
+        assert!(matches!(result, Err(DomainError::DuplicateAccountNumber(n)) if n == number));
     }
 }
@@ -0,0 +1,144 @@
+use crate::domain::entities::IdempotencyRecord;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::IdempotencyRepository;
+use crate::infrastructure::persistence::connection::with_connection;
+use crate::models::IdempotencyKey as DbIdempotencyKey;
+use crate::schema::idempotency_keys;
+use async_trait::async_trait;
+use chrono::Duration;
+use diesel::dsl::now;
+use diesel::pg::expression::extensions::IntervalDsl;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+
+pub struct DieselIdempotencyRepository {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselIdempotencyRepository {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+
+    fn to_domain(db_key: DbIdempotencyKey) -> DomainResult<IdempotencyRecord> {
+        let response_status = db_key
+            .response_status
+            .map(u16::try_from)
+            .transpose()
+            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+        Ok(IdempotencyRecord {
+            key: db_key.key,
+            request_fingerprint: db_key.request_fingerprint,
+            response_status,
+            response_body: db_key.response_body,
+            response_content_type: db_key.response_content_type,
+            created_at: db_key.created_at,
+            expires_at: db_key.expires_at,
+        })
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepository for DieselIdempotencyRepository {
+    async fn reserve(
+        &self,
+        key: &str,
+        request_fingerprint: &str,
+        ttl: Duration,
+    ) -> DomainResult<Option<IdempotencyRecord>> {
+        let key = key.to_string();
+        let request_fingerprint = request_fingerprint.to_string();
+        with_connection(&self.pool, move |conn| {
+            // Expiry is computed with the database clock, like every other timestamp
+            let existing = conn
+                .transaction::<_, diesel::result::Error, _>(|conn| {
+                    diesel::delete(
+                        idempotency_keys::table
+                            .filter(idempotency_keys::key.eq(&key))
+                            .filter(idempotency_keys::expires_at.le(now)),
+                    )
+                    .execute(conn)?;
+
+                    // The conflicting row can be released between the insert and the
+                    // select; each statement sees newly committed rows, so try again
+                    loop {
+                        let reserved = diesel::insert_into(idempotency_keys::table)
+                            .values((
+                                idempotency_keys::key.eq(&key),
+                                idempotency_keys::request_fingerprint.eq(&request_fingerprint),
+                                idempotency_keys::expires_at.eq(now + ttl.num_seconds().seconds()),
+                            ))
+                            .on_conflict_do_nothing()
+                            .execute(conn)?;
+
+                        if reserved == 1 {
+                            return Ok(None);
+                        }
+
+                        let existing = idempotency_keys::table
+                            .find(&key)
+                            .select(DbIdempotencyKey::as_select())
+                            .first(conn)
+                            .optional()?;
+                        if existing.is_some() {
+                            return Ok(existing);
+                        }
+                    }
+                })
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            existing.map(Self::to_domain).transpose()
+        })
+        .await
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        response_status: u16,
+        response_content_type: Option<String>,
+        response_body: &str,
+    ) -> DomainResult<()> {
+        let key = key.to_string();
+        let response_body = response_body.to_string();
+        with_connection(&self.pool, move |conn| {
+            diesel::update(idempotency_keys::table.find(&key))
+                .set((
+                    idempotency_keys::response_status.eq(i32::from(response_status)),
+                    idempotency_keys::response_body.eq(&response_body),
+                    idempotency_keys::response_content_type.eq(&response_content_type),
+                ))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn release(&self, key: &str) -> DomainResult<()> {
+        let key = key.to_string();
+        with_connection(&self.pool, move |conn| {
+            diesel::delete(
+                idempotency_keys::table
+                    .filter(idempotency_keys::key.eq(&key))
+                    .filter(idempotency_keys::response_status.is_null()),
+            )
+            .execute(conn)
+            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete_expired(&self) -> DomainResult<usize> {
+        with_connection(&self.pool, |conn| {
+            diesel::delete(idempotency_keys::table.filter(idempotency_keys::expires_at.le(now)))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))
+        })
+        .await
+    }
+}
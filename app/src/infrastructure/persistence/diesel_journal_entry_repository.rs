@@ -0,0 +1,80 @@
+use crate::domain::entities::JournalEntry;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::JournalEntryRepository;
+use crate::infrastructure::persistence::connection::with_connection;
+use crate::infrastructure::persistence::ledger_writer::{self, TxError};
+use crate::infrastructure::persistence::DieselLedgerEventRepository;
+use crate::models::{
+    JournalEntry as DbJournalEntry, LedgerEvent as DbLedgerEvent, NewJournalEntry,
+};
+use crate::schema::journal_entries;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+
+pub struct DieselJournalEntryRepository {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselJournalEntryRepository {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+
+    fn to_domain(
+        db_entry: DbJournalEntry,
+        db_legs: Vec<DbLedgerEvent>,
+    ) -> DomainResult<JournalEntry> {
+        Ok(JournalEntry {
+            id: Some(db_entry.id),
+            description: db_entry.description,
+            legs: db_legs
+                .into_iter()
+                .map(DieselLedgerEventRepository::to_domain)
+                .collect::<DomainResult<Vec<_>>>()?,
+            created_at: Some(db_entry.created_at),
+        })
+    }
+}
+
+#[async_trait]
+impl JournalEntryRepository for DieselJournalEntryRepository {
+    async fn save(&self, entry: &JournalEntry) -> DomainResult<JournalEntry> {
+        let currency = entry.currency().ok_or_else(|| {
+            DomainError::ValidationError("A journal entry needs at least two legs".to_string())
+        })?;
+
+        let new_entry = NewJournalEntry {
+            description: entry.description.clone(),
+            currency: currency.code().to_string(),
+        };
+
+        let entry = entry.clone();
+        with_connection(&self.pool, move |conn| {
+            // The entry row and every leg are committed together or not at all, so a
+            // rejected leg (frozen account, insufficient balance) rolls back the others
+            let (db_entry, db_legs) = conn.transaction::<_, TxError, _>(|conn| {
+                let account_ids: Vec<i32> = entry.legs.iter().map(|leg| leg.account_id).collect();
+                ledger_writer::lock_accounts(conn, &account_ids)?;
+
+                let db_entry: DbJournalEntry = diesel::insert_into(journal_entries::table)
+                    .values(&new_entry)
+                    .get_result(conn)?;
+
+                let mut saved = entry.clone();
+                saved.id = Some(db_entry.id);
+
+                let db_legs = saved
+                    .linked_legs()
+                    .iter()
+                    .map(|leg| ledger_writer::append_event(conn, leg))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok((db_entry, db_legs))
+            })?;
+
+            Self::to_domain(db_entry, db_legs)
+        })
+        .await
+    }
+}
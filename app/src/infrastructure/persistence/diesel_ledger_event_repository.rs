@@ -1,12 +1,17 @@
 use crate::domain::entities::{EventType, LedgerEvent};
 use crate::domain::errors::{DomainError, DomainResult};
-use crate::domain::repositories::LedgerEventRepository;
-use crate::domain::value_objects::Money;
-use crate::models::{LedgerEvent as DbLedgerEvent, NewLedgerEvent};
+use crate::domain::repositories::{LedgerEventFilter, LedgerEventRepository};
+use crate::domain::value_objects::{BalanceDelta, Currency, Money, Page, PageRequest};
+use crate::infrastructure::persistence::connection::with_connection;
+use crate::infrastructure::persistence::ledger_writer;
+use crate::models::{BalanceDelta as DbBalanceDelta, LedgerEvent as DbLedgerEvent, NewLedgerEvent};
 use crate::schema::ledger_events;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sql_types::{Integer, Timestamp};
+use std::collections::HashMap;
 
 pub struct DieselLedgerEventRepository {
     pool: Pool<ConnectionManager<PgConnection>>,
@@ -17,23 +22,139 @@ impl DieselLedgerEventRepository {
         Self { pool }
     }
 
-    fn to_domain(db_event: DbLedgerEvent) -> DomainResult<LedgerEvent> {
+    pub(crate) fn to_domain(db_event: DbLedgerEvent) -> DomainResult<LedgerEvent> {
         Ok(LedgerEvent {
             id: Some(db_event.id),
             account_id: db_event.account_id,
             event_type: EventType::from_string(&db_event.event_type)?,
-            amount: Money::new(db_event.amount)?,
+            amount: Money::new(db_event.amount, Currency::from_code(&db_event.currency)?)?,
             description: db_event.description,
+            transfer_id: db_event.transfer_id,
+            journal_entry_id: db_event.journal_entry_id,
+            reverses_event_id: db_event.reverses_event_id,
+            reversed_by_event_id: None,
+            recorded_by: db_event.recorded_by,
+            version: Some(db_event.version),
+            sequence: Some(db_event.sequence),
             created_at: Some(db_event.created_at),
         })
     }
 
-    fn to_db(event: &LedgerEvent) -> NewLedgerEvent {
+    /// Converts loaded events, filling in which of them have been reversed
+    fn to_domain_with_reversals(
+        conn: &mut PgConnection,
+        db_events: Vec<DbLedgerEvent>,
+    ) -> DomainResult<Vec<LedgerEvent>> {
+        let ids: Vec<i32> = db_events.iter().map(|event| event.id).collect();
+        let reversals: HashMap<i32, i32> = ledger_events::table
+            .filter(ledger_events::reverses_event_id.eq_any(&ids))
+            .select((
+                ledger_events::reverses_event_id.assume_not_null(),
+                ledger_events::id,
+            ))
+            .load::<(i32, i32)>(conn)
+            .map_err(|e| DomainError::RepositoryError(e.to_string()))?
+            .into_iter()
+            .collect();
+
+        db_events
+            .into_iter()
+            .map(|db_event| {
+                let mut event = Self::to_domain(db_event)?;
+                event.reversed_by_event_id = event.id.and_then(|id| reversals.get(&id).copied());
+                Ok(event)
+            })
+            .collect()
+    }
+
+    /// Id of the event reversing `event_id`, if it has been reversed
+    pub(crate) fn reversal_of(conn: &mut PgConnection, event_id: i32) -> QueryResult<Option<i32>> {
+        ledger_events::table
+            .filter(ledger_events::reverses_event_id.eq(event_id))
+            .select(ledger_events::id)
+            .first(conn)
+            .optional()
+    }
+
+    /// Sums the account's events after `after_event_id` in the database
+    pub(crate) fn balance_delta(
+        conn: &mut PgConnection,
+        account_id: i32,
+        after_event_id: i32,
+    ) -> QueryResult<DbBalanceDelta> {
+        diesel::sql_query(
+            "SELECT COALESCE(SUM(CASE WHEN event_type = 'CREDIT' THEN amount ELSE -amount END), 0)::BIGINT AS amount, \
+                    MAX(id) AS last_event_id, \
+                    COUNT(*) AS event_count \
+             FROM ledger_events \
+             WHERE account_id = $1 AND id > $2",
+        )
+        .bind::<Integer, _>(account_id)
+        .bind::<Integer, _>(after_event_id)
+        .get_result(conn)
+    }
+
+    /// Sums the account's events with an id in `(after_event_id, through_event_id]`
+    pub(crate) fn balance_delta_between(
+        conn: &mut PgConnection,
+        account_id: i32,
+        after_event_id: i32,
+        through_event_id: i32,
+    ) -> QueryResult<DbBalanceDelta> {
+        diesel::sql_query(
+            "SELECT COALESCE(SUM(CASE WHEN event_type = 'CREDIT' THEN amount ELSE -amount END), 0)::BIGINT AS amount, \
+                    MAX(id) AS last_event_id, \
+                    COUNT(*) AS event_count \
+             FROM ledger_events \
+             WHERE account_id = $1 AND id > $2 AND id <= $3",
+        )
+        .bind::<Integer, _>(account_id)
+        .bind::<Integer, _>(after_event_id)
+        .bind::<Integer, _>(through_event_id)
+        .get_result(conn)
+    }
+
+    /// Sums the account's events after `after_event_id` created before `before`
+    pub(crate) fn balance_delta_before(
+        conn: &mut PgConnection,
+        account_id: i32,
+        after_event_id: i32,
+        before: NaiveDateTime,
+    ) -> QueryResult<DbBalanceDelta> {
+        diesel::sql_query(
+            "SELECT COALESCE(SUM(CASE WHEN event_type = 'CREDIT' THEN amount ELSE -amount END), 0)::BIGINT AS amount, \
+                    MAX(id) AS last_event_id, \
+                    COUNT(*) AS event_count \
+             FROM ledger_events \
+             WHERE account_id = $1 AND id > $2 AND created_at < $3",
+        )
+        .bind::<Integer, _>(account_id)
+        .bind::<Integer, _>(after_event_id)
+        .bind::<Timestamp, _>(before)
+        .get_result(conn)
+    }
+
+    /// ILIKE pattern matching values that contain `term`, with wildcards in `term` escaped
+    pub(crate) fn contains_pattern(term: &str) -> String {
+        let escaped = term
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("%{}%", escaped)
+    }
+
+    pub(crate) fn to_db(event: &LedgerEvent, version: i64) -> NewLedgerEvent {
         NewLedgerEvent {
             account_id: event.account_id,
             event_type: event.event_type.to_string(),
             amount: event.amount.value(),
             description: event.description.clone(),
+            transfer_id: event.transfer_id,
+            journal_entry_id: event.journal_entry_id,
+            reverses_event_id: event.reverses_event_id,
+            recorded_by: event.recorded_by.clone(),
+            currency: event.amount.currency().code().to_string(),
+            version,
         }
     }
 }
@@ -41,58 +162,266 @@ impl DieselLedgerEventRepository {
 #[async_trait]
 impl LedgerEventRepository for DieselLedgerEventRepository {
     async fn save(&self, event: &LedgerEvent) -> DomainResult<LedgerEvent> {
-        let mut conn = self
-            .pool
-            .get()
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+        let event = event.clone();
+        with_connection(&self.pool, move |conn| {
+            let db_event = conn.transaction::<_, ledger_writer::TxError, _>(|conn| {
+                ledger_writer::append_event(conn, &event)
+            })?;
+
+            Self::to_domain(db_event)
+        })
+        .await
+    }
 
-        let new_event = Self::to_db(event);
+    async fn save_at_version(
+        &self,
+        event: &LedgerEvent,
+        expected_version: i64,
+    ) -> DomainResult<LedgerEvent> {
+        let event = event.clone();
+        with_connection(&self.pool, move |conn| {
+            let db_event = conn.transaction::<_, ledger_writer::TxError, _>(|conn| {
+                ledger_writer::append_event_expecting(conn, &event, Some(expected_version))
+            })?;
 
-        let db_event: DbLedgerEvent = diesel::insert_into(ledger_events::table)
-            .values(&new_event)
-            .get_result(&mut conn)
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+            Self::to_domain(db_event)
+        })
+        .await
+    }
+
+    async fn find_by_id(&self, id: i32) -> DomainResult<LedgerEvent> {
+        with_connection(&self.pool, move |conn| {
+            let db_event: DbLedgerEvent =
+                ledger_events::table
+                    .find(id)
+                    .first(conn)
+                    .map_err(|e| match e {
+                        diesel::result::Error::NotFound => DomainError::EventNotFound(id),
+                        _ => DomainError::RepositoryError(e.to_string()),
+                    })?;
 
-        Self::to_domain(db_event)
+            let mut event = Self::to_domain(db_event)?;
+            event.reversed_by_event_id = Self::reversal_of(conn, id)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+            Ok(event)
+        })
+        .await
     }
 
     async fn find_by_account_id(&self, account_id: i32) -> DomainResult<Vec<LedgerEvent>> {
-        let mut conn = self
-            .pool
-            .get()
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+        with_connection(&self.pool, move |conn| {
+            let db_events: Vec<DbLedgerEvent> = ledger_events::table
+                .filter(ledger_events::account_id.eq(account_id))
+                .order(ledger_events::version.asc())
+                .load(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            Self::to_domain_with_reversals(conn, db_events)
+        })
+        .await
+    }
+
+    async fn find_page(
+        &self,
+        filter: &LedgerEventFilter,
+        page: &PageRequest<i64>,
+    ) -> DomainResult<Page<LedgerEvent, i64>> {
+        let filter = filter.clone();
+        let page = *page;
+        with_connection(&self.pool, move |conn| {
+            let mut query = ledger_events::table.into_boxed();
 
-        let db_events: Vec<DbLedgerEvent> = ledger_events::table
-            .filter(ledger_events::account_id.eq(account_id))
-            .order(ledger_events::created_at.asc())
-            .load(&mut conn)
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+            if let Some(account_id) = filter.account_id {
+                query = query.filter(ledger_events::account_id.eq(account_id));
+            }
+            if let Some(event_type) = &filter.event_type {
+                query = query.filter(ledger_events::event_type.eq(event_type.as_str()));
+            }
+            if let Some(min_amount) = filter.min_amount {
+                query = query.filter(ledger_events::amount.ge(min_amount));
+            }
+            if let Some(max_amount) = filter.max_amount {
+                query = query.filter(ledger_events::amount.le(max_amount));
+            }
+            if let Some(created_from) = filter.created_from {
+                query = query.filter(ledger_events::created_at.ge(created_from));
+            }
+            if let Some(created_to) = filter.created_to {
+                query = query.filter(ledger_events::created_at.lt(created_to));
+            }
+            if let Some(description) = &filter.description {
+                query = query
+                    .filter(ledger_events::description.ilike(Self::contains_pattern(description)));
+            }
+            if let Some(after) = page.after {
+                query = query.filter(ledger_events::sequence.gt(after));
+            }
 
-        db_events.into_iter().map(Self::to_domain).collect()
+            // One extra row tells whether there is a next page
+            let db_events: Vec<DbLedgerEvent> = query
+                .order(ledger_events::sequence.asc())
+                .limit(page.limit + 1)
+                .load(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            let events = Self::to_domain_with_reversals(conn, db_events)?;
+
+            Ok(Page::from_overfetch(events, page.limit, |event| {
+                event.sequence
+            }))
+        })
+        .await
     }
 
-    async fn find_all(&self) -> DomainResult<Vec<LedgerEvent>> {
-        let mut conn = self
-            .pool
-            .get()
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+    async fn calculate_balance_since(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+    ) -> DomainResult<BalanceDelta> {
+        with_connection(&self.pool, move |conn| {
+            let delta = Self::balance_delta(conn, account_id, after_event_id)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
 
-        let db_events: Vec<DbLedgerEvent> = ledger_events::table
-            .order(ledger_events::created_at.desc())
-            .load(&mut conn)
-            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+            Ok(BalanceDelta::new(
+                delta.amount,
+                delta.last_event_id,
+                delta.event_count,
+            ))
+        })
+        .await
+    }
+
+    async fn calculate_balance_between(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+        through_event_id: i32,
+    ) -> DomainResult<BalanceDelta> {
+        with_connection(&self.pool, move |conn| {
+            let delta =
+                Self::balance_delta_between(conn, account_id, after_event_id, through_event_id)
+                    .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
 
-        db_events.into_iter().map(Self::to_domain).collect()
+            Ok(BalanceDelta::new(
+                delta.amount,
+                delta.last_event_id,
+                delta.event_count,
+            ))
+        })
+        .await
     }
 
-    async fn calculate_balance(&self, account_id: i32) -> DomainResult<i64> {
-        let events = self.find_by_account_id(account_id).await?;
+    async fn calculate_balance_before(
+        &self,
+        account_id: i32,
+        after_event_id: i32,
+        before: NaiveDateTime,
+    ) -> DomainResult<BalanceDelta> {
+        with_connection(&self.pool, move |conn| {
+            let delta = Self::balance_delta_before(conn, account_id, after_event_id, before)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
 
-        let balance = events.iter().fold(0i64, |acc, event| match event.event_type {
-            EventType::Credit => acc + event.amount.value(),
-            EventType::Debit => acc - event.amount.value(),
-        });
+            Ok(BalanceDelta::new(
+                delta.amount,
+                delta.last_event_id,
+                delta.event_count,
+            ))
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseConfig;
+    use crate::domain::entities::Account;
+    use crate::domain::repositories::AccountRepository;
+    use crate::domain::value_objects::AccountNumber;
+    use crate::establish_connection_pool;
+    use crate::infrastructure::persistence::DieselAccountRepository;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "needs a migrated PostgreSQL database at DATABASE_URL"]
+    async fn test_cursor_paging_sees_concurrent_appends() {
+        let config = DatabaseConfig {
+            url: std::env::var("DATABASE_URL").unwrap(),
+            ..DatabaseConfig::default()
+        };
+        let pool = establish_connection_pool(&config).unwrap();
+        let accounts = DieselAccountRepository::new(pool.clone());
+        let repository = Arc::new(DieselLedgerEventRepository::new(pool));
+
+        let mut account_ids = Vec::new();
+        for writer in 0..2 {
+            let number = format!("SEQ{}{}", writer, chrono::Utc::now().timestamp_micros());
+            let account = Account::new(
+                AccountNumber::new(number).unwrap(),
+                "Alice".to_string(),
+                Currency::USD,
+            );
+            account_ids.push(accounts.save(&account).await.unwrap().id.unwrap());
+        }
+        let credit = |account_id| {
+            LedgerEvent::new_credit(account_id, Money::new(1, Currency::USD).unwrap(), None)
+        };
+        let mut cursor = repository
+            .save(&credit(account_ids[0]))
+            .await
+            .unwrap()
+            .sequence;
+
+        let writers: Vec<_> = account_ids
+            .iter()
+            .map(|&account_id| {
+                let repository = repository.clone();
+                tokio::spawn(async move {
+                    let mut ids = Vec::new();
+                    for _ in 0..50 {
+                        ids.push(
+                            repository
+                                .save(&credit(account_id))
+                                .await
+                                .unwrap()
+                                .id
+                                .unwrap(),
+                        );
+                    }
+                    ids
+                })
+            })
+            .collect();
 
-        Ok(balance)
+        // Page behind the writers, then drain what they committed last
+        let mut seen = HashSet::new();
+        let mut drained = false;
+        loop {
+            let writing = writers.iter().any(|writer| !writer.is_finished());
+            let page = repository
+                .find_page(
+                    &LedgerEventFilter::default(),
+                    &PageRequest::new(cursor, Some(100)).unwrap(),
+                )
+                .await
+                .unwrap();
+            if let Some(last) = page.items.last() {
+                cursor = last.sequence;
+            }
+            seen.extend(page.items.iter().filter_map(|event| event.id));
+            if page.next_cursor.is_none() && !writing {
+                if drained {
+                    break;
+                }
+                drained = true;
+            }
+        }
+
+        for writer in writers {
+            for id in writer.await.unwrap() {
+                assert!(seen.contains(&id), "event {} was skipped by paging", id);
+            }
+        }
     }
 }
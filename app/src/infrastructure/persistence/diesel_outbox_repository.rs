@@ -0,0 +1,183 @@
+use crate::domain::entities::{OutboxMessage, OutboxStatus};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{OutboxFilter, OutboxRepository};
+use crate::domain::value_objects::{Page, PageRequest};
+use crate::infrastructure::persistence::connection::with_connection;
+use crate::infrastructure::persistence::DieselWebhookRepository;
+use crate::models::{NewOutboxMessage, OutboxMessage as DbOutboxMessage};
+use crate::schema::outbox_messages;
+use async_trait::async_trait;
+use chrono::Duration;
+use diesel::dsl::now;
+use diesel::pg::expression::extensions::IntervalDsl;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+
+pub struct DieselOutboxRepository {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselOutboxRepository {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+
+    fn to_domain(db_message: DbOutboxMessage) -> OutboxMessage {
+        OutboxMessage {
+            id: Some(db_message.id),
+            topic: db_message.topic,
+            key: db_message.message_key,
+            payload: db_message.payload,
+            attempts: db_message.attempts,
+            last_error: db_message.last_error,
+            next_attempt_at: Some(db_message.next_attempt_at),
+            published_at: db_message.published_at,
+            created_at: Some(db_message.created_at),
+        }
+    }
+
+    /// Adds a message to the outbox on the caller's connection, along with a delivery
+    /// for every webhook subscription matching it, so that they commit or roll back
+    /// with the caller's transaction
+    pub(crate) fn enqueue(conn: &mut PgConnection, message: &OutboxMessage) -> QueryResult<i32> {
+        let id = diesel::insert_into(outbox_messages::table)
+            .values(&NewOutboxMessage {
+                topic: message.topic.clone(),
+                message_key: message.key.clone(),
+                payload: message.payload.clone(),
+            })
+            .returning(outbox_messages::id)
+            .get_result(conn)?;
+
+        DieselWebhookRepository::fan_out(conn, id, message)?;
+        Ok(id)
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for DieselOutboxRepository {
+    async fn claim_due(&self, lease: Duration, limit: i64) -> DomainResult<Vec<OutboxMessage>> {
+        with_connection(&self.pool, move |conn| {
+            // SKIP LOCKED lets concurrent relays claim disjoint batches
+            let mut claimed = conn
+                .transaction::<_, diesel::result::Error, _>(|conn| {
+                    let ids: Vec<i32> = outbox_messages::table
+                        .filter(outbox_messages::published_at.is_null())
+                        .filter(outbox_messages::next_attempt_at.le(now))
+                        .order(outbox_messages::id.asc())
+                        .limit(limit)
+                        .select(outbox_messages::id)
+                        .for_update()
+                        .skip_locked()
+                        .load(conn)?;
+
+                    diesel::update(outbox_messages::table.filter(outbox_messages::id.eq_any(&ids)))
+                        .set(
+                            outbox_messages::next_attempt_at
+                                .eq(now + lease.num_seconds().seconds()),
+                        )
+                        .returning(DbOutboxMessage::as_returning())
+                        .get_results(conn)
+                })
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            claimed.sort_by_key(|message| message.id);
+            Ok(claimed.into_iter().map(Self::to_domain).collect())
+        })
+        .await
+    }
+
+    async fn mark_published(&self, id: i32) -> DomainResult<()> {
+        with_connection(&self.pool, move |conn| {
+            diesel::update(outbox_messages::table.find(id))
+                .set((
+                    outbox_messages::attempts.eq(outbox_messages::attempts + 1),
+                    outbox_messages::published_at.eq(now),
+                ))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn mark_failed(&self, id: i32, error: &str, retry_in: Duration) -> DomainResult<()> {
+        let error = error.to_string();
+        with_connection(&self.pool, move |conn| {
+            diesel::update(outbox_messages::table.find(id))
+                .set((
+                    outbox_messages::attempts.eq(outbox_messages::attempts + 1),
+                    outbox_messages::last_error.eq(&error),
+                    outbox_messages::next_attempt_at.eq(now + retry_in.num_seconds().seconds()),
+                ))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn count_unpublished(&self) -> DomainResult<i64> {
+        with_connection(&self.pool, |conn| {
+            outbox_messages::table
+                .filter(outbox_messages::published_at.is_null())
+                .count()
+                .get_result(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))
+        })
+        .await
+    }
+
+    async fn find_page(
+        &self,
+        filter: &OutboxFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<OutboxMessage>> {
+        let filter = filter.clone();
+        let page = *page;
+        with_connection(&self.pool, move |conn| {
+            let mut query = outbox_messages::table.into_boxed();
+
+            match filter.status {
+                Some(OutboxStatus::Pending) => {
+                    query = query
+                        .filter(outbox_messages::published_at.is_null())
+                        .filter(outbox_messages::attempts.eq(0));
+                }
+                Some(OutboxStatus::Retrying) => {
+                    query = query
+                        .filter(outbox_messages::published_at.is_null())
+                        .filter(outbox_messages::attempts.gt(0));
+                }
+                Some(OutboxStatus::Published) => {
+                    query = query.filter(outbox_messages::published_at.is_not_null());
+                }
+                None => {}
+            }
+            if let Some(topic) = &filter.topic {
+                query = query.filter(outbox_messages::topic.eq(topic));
+            }
+            if let Some(after) = page.after {
+                query = query.filter(outbox_messages::id.gt(after));
+            }
+
+            // One extra row tells whether there is a next page
+            let db_messages: Vec<DbOutboxMessage> = query
+                .order(outbox_messages::id.asc())
+                .limit(page.limit + 1)
+                .load(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            let messages = db_messages.into_iter().map(Self::to_domain).collect();
+
+            Ok(Page::from_overfetch(
+                messages,
+                page.limit,
+                |message: &OutboxMessage| message.id,
+            ))
+        })
+        .await
+    }
+}
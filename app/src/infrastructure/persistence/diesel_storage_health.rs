@@ -0,0 +1,67 @@
+use crate::domain::errors::DomainError;
+use crate::infrastructure::health::{HealthCheck, StorageHealth};
+use crate::infrastructure::persistence::connection::with_connection;
+use crate::DbPool;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use metrics::gauge;
+
+/// Migrations compiled into the binary, compared against the ones applied to the
+/// database to tell whether the schema is what this build expects
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+pub struct DieselStorageHealth {
+    pool: DbPool,
+}
+
+impl DieselStorageHealth {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StorageHealth for DieselStorageHealth {
+    async fn check(&self) -> Vec<HealthCheck> {
+        let result = with_connection(&self.pool, |conn| {
+            diesel::sql_query("SELECT 1")
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            conn.pending_migrations(MIGRATIONS)
+                .map(|pending| {
+                    pending
+                        .iter()
+                        .map(|migration| migration.name().to_string())
+                        .collect::<Vec<_>>()
+                })
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))
+        })
+        .await;
+
+        match result {
+            Ok(pending) if pending.is_empty() => {
+                vec![HealthCheck::ok("database"), HealthCheck::ok("migrations")]
+            }
+            Ok(pending) => vec![
+                HealthCheck::ok("database"),
+                HealthCheck::failed(
+                    "migrations",
+                    format!("Pending migrations: {}", pending.join(", ")),
+                ),
+            ],
+            Err(e) => vec![
+                HealthCheck::failed("database", e.to_string()),
+                HealthCheck::failed("migrations", "Database unavailable"),
+            ],
+        }
+    }
+
+    fn record_pool_metrics(&self) {
+        let state = self.pool.state();
+        gauge!("db_pool_connections").set(state.connections);
+        gauge!("db_pool_idle_connections").set(state.idle_connections);
+        gauge!("db_pool_max_connections").set(self.pool.max_size());
+    }
+}
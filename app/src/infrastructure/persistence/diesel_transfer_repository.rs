@@ -0,0 +1,86 @@
+use crate::domain::entities::Transfer;
+use crate::domain::errors::DomainResult;
+use crate::domain::repositories::TransferRepository;
+use crate::domain::value_objects::{Currency, Money};
+use crate::infrastructure::persistence::connection::with_connection;
+use crate::infrastructure::persistence::ledger_writer::{self, TxError};
+use crate::infrastructure::persistence::DieselLedgerEventRepository;
+use crate::models::{LedgerEvent as DbLedgerEvent, NewTransfer, Transfer as DbTransfer};
+use crate::schema::transfers;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+
+pub struct DieselTransferRepository {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselTransferRepository {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+
+    fn to_domain(
+        db_transfer: DbTransfer,
+        db_debit: DbLedgerEvent,
+        db_credit: DbLedgerEvent,
+    ) -> DomainResult<Transfer> {
+        Ok(Transfer {
+            id: Some(db_transfer.id),
+            from_account_id: db_transfer.from_account_id,
+            to_account_id: db_transfer.to_account_id,
+            amount: Money::new(
+                db_transfer.amount,
+                Currency::from_code(&db_transfer.currency)?,
+            )?,
+            description: db_transfer.description,
+            recorded_by: db_debit.recorded_by.clone(),
+            debit_event: Some(DieselLedgerEventRepository::to_domain(db_debit)?),
+            credit_event: Some(DieselLedgerEventRepository::to_domain(db_credit)?),
+            created_at: Some(db_transfer.created_at),
+        })
+    }
+
+    fn to_db(transfer: &Transfer) -> NewTransfer {
+        NewTransfer {
+            from_account_id: transfer.from_account_id,
+            to_account_id: transfer.to_account_id,
+            amount: transfer.amount.value(),
+            description: transfer.description.clone(),
+            currency: transfer.amount.currency().code().to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TransferRepository for DieselTransferRepository {
+    async fn save(&self, transfer: &Transfer) -> DomainResult<Transfer> {
+        let new_transfer = Self::to_db(transfer);
+
+        let transfer = transfer.clone();
+        with_connection(&self.pool, move |conn| {
+            // The transfer row and both legs are committed together or not at all
+            let (db_transfer, db_debit, db_credit) = conn.transaction::<_, TxError, _>(|conn| {
+                ledger_writer::lock_accounts(
+                    conn,
+                    &[transfer.from_account_id, transfer.to_account_id],
+                )?;
+
+                let db_transfer: DbTransfer = diesel::insert_into(transfers::table)
+                    .values(&new_transfer)
+                    .get_result(conn)?;
+
+                let mut saved = transfer.clone();
+                saved.id = Some(db_transfer.id);
+
+                let db_debit = ledger_writer::append_event(conn, &saved.debit_leg())?;
+                let db_credit = ledger_writer::append_event(conn, &saved.credit_leg())?;
+
+                Ok((db_transfer, db_debit, db_credit))
+            })?;
+
+            Self::to_domain(db_transfer, db_debit, db_credit)
+        })
+        .await
+    }
+}
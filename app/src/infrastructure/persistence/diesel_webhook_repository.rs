@@ -0,0 +1,420 @@
+use crate::domain::entities::{
+    OutboxMessage, WebhookDelivery, WebhookDeliveryStatus, WebhookSubscription,
+    BALANCE_BELOW_THRESHOLD,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::{WebhookDeliveryFilter, WebhookRepository};
+use crate::domain::value_objects::{Page, PageRequest};
+use crate::infrastructure::persistence::connection::with_connection;
+use crate::models::{
+    NewWebhookDelivery, NewWebhookSubscription, WebhookDelivery as DbWebhookDelivery,
+    WebhookSubscription as DbWebhookSubscription,
+};
+use crate::schema::{webhook_deliveries, webhook_subscriptions};
+use async_trait::async_trait;
+use chrono::Duration;
+use diesel::dsl::now;
+use diesel::pg::expression::extensions::IntervalDsl;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+
+pub struct DieselWebhookRepository {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselWebhookRepository {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+
+    fn subscription_to_domain(db_subscription: DbWebhookSubscription) -> WebhookSubscription {
+        WebhookSubscription {
+            id: Some(db_subscription.id),
+            url: db_subscription.url,
+            event_types: db_subscription.event_types,
+            secret: db_subscription.secret,
+            account_id: db_subscription.account_id,
+            balance_threshold: db_subscription.balance_threshold,
+            created_at: Some(db_subscription.created_at),
+        }
+    }
+
+    fn delivery_to_domain(db_delivery: DbWebhookDelivery) -> WebhookDelivery {
+        WebhookDelivery {
+            id: Some(db_delivery.id),
+            subscription_id: db_delivery.subscription_id,
+            outbox_message_id: db_delivery.outbox_message_id,
+            event_type: db_delivery.event_type,
+            payload: db_delivery.payload,
+            attempts: db_delivery.attempts,
+            last_error: db_delivery.last_error,
+            next_attempt_at: Some(db_delivery.next_attempt_at),
+            delivered_at: db_delivery.delivered_at,
+            dead_lettered_at: db_delivery.dead_lettered_at,
+            created_at: Some(db_delivery.created_at),
+        }
+    }
+
+    fn subscribed_to(
+        conn: &mut PgConnection,
+        event_type: &str,
+    ) -> QueryResult<Vec<WebhookSubscription>> {
+        let db_subscriptions: Vec<DbWebhookSubscription> = webhook_subscriptions::table
+            .filter(webhook_subscriptions::event_types.contains(vec![event_type.to_string()]))
+            .order(webhook_subscriptions::id.asc())
+            .select(DbWebhookSubscription::as_select())
+            .load(conn)?;
+
+        Ok(db_subscriptions
+            .into_iter()
+            .map(Self::subscription_to_domain)
+            .collect())
+    }
+
+    /// Writes a delivery of a just saved outbox message to every subscription
+    /// matching it, on the caller's connection
+    pub(crate) fn fan_out(
+        conn: &mut PgConnection,
+        message_id: i32,
+        message: &OutboxMessage,
+    ) -> QueryResult<()> {
+        let subscriptions = Self::subscribed_to(conn, &message.topic)?;
+        let deliveries = WebhookDelivery::for_message(&subscriptions, message_id, message);
+        Self::insert_deliveries(conn, &deliveries)
+    }
+
+    /// Subscriptions to `balance.below_threshold` covering the account
+    pub(crate) fn balance_watchers(
+        conn: &mut PgConnection,
+        account_id: i32,
+    ) -> QueryResult<Vec<WebhookSubscription>> {
+        let mut watchers = Self::subscribed_to(conn, BALANCE_BELOW_THRESHOLD)?;
+        watchers
+            .retain(|subscription| subscription.matches(BALANCE_BELOW_THRESHOLD, Some(account_id)));
+        Ok(watchers)
+    }
+
+    pub(crate) fn insert_deliveries(
+        conn: &mut PgConnection,
+        deliveries: &[WebhookDelivery],
+    ) -> QueryResult<()> {
+        if deliveries.is_empty() {
+            return Ok(());
+        }
+
+        let new_deliveries: Vec<NewWebhookDelivery> = deliveries
+            .iter()
+            .map(|delivery| NewWebhookDelivery {
+                subscription_id: delivery.subscription_id,
+                outbox_message_id: delivery.outbox_message_id,
+                event_type: delivery.event_type.clone(),
+                payload: delivery.payload.clone(),
+            })
+            .collect();
+        diesel::insert_into(webhook_deliveries::table)
+            .values(&new_deliveries)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    fn find_delivery_on(conn: &mut PgConnection, id: i32) -> DomainResult<WebhookDelivery> {
+        webhook_deliveries::table
+            .find(id)
+            .select(DbWebhookDelivery::as_select())
+            .first(conn)
+            .map(Self::delivery_to_domain)
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => DomainError::WebhookDeliveryNotFound(id),
+                _ => DomainError::RepositoryError(e.to_string()),
+            })
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for DieselWebhookRepository {
+    async fn save_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<WebhookSubscription> {
+        let new_subscription = NewWebhookSubscription {
+            url: subscription.url.clone(),
+            event_types: subscription.event_types.clone(),
+            secret: subscription.secret.clone(),
+            account_id: subscription.account_id,
+            balance_threshold: subscription.balance_threshold,
+        };
+
+        with_connection(&self.pool, move |conn| {
+            let saved = diesel::insert_into(webhook_subscriptions::table)
+                .values(&new_subscription)
+                .returning(DbWebhookSubscription::as_returning())
+                .get_result(conn)
+                .map_err(|e| match e {
+                    diesel::result::Error::DatabaseError(
+                        diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                        _,
+                    ) => DomainError::AccountNotFound(format!(
+                        "Account with id {} not found",
+                        new_subscription.account_id.unwrap_or_default()
+                    )),
+                    _ => DomainError::RepositoryError(e.to_string()),
+                })?;
+
+            Ok(Self::subscription_to_domain(saved))
+        })
+        .await
+    }
+
+    async fn find_subscription(&self, id: i32) -> DomainResult<WebhookSubscription> {
+        with_connection(&self.pool, move |conn| {
+            webhook_subscriptions::table
+                .find(id)
+                .select(DbWebhookSubscription::as_select())
+                .first(conn)
+                .map(Self::subscription_to_domain)
+                .map_err(|e| match e {
+                    diesel::result::Error::NotFound => DomainError::WebhookSubscriptionNotFound(id),
+                    _ => DomainError::RepositoryError(e.to_string()),
+                })
+        })
+        .await
+    }
+
+    async fn find_subscriptions(
+        &self,
+        page: &PageRequest,
+    ) -> DomainResult<Page<WebhookSubscription>> {
+        let page = *page;
+        with_connection(&self.pool, move |conn| {
+            let mut query = webhook_subscriptions::table.into_boxed();
+            if let Some(after) = page.after {
+                query = query.filter(webhook_subscriptions::id.gt(after));
+            }
+
+            // One extra row tells whether there is a next page
+            let db_subscriptions: Vec<DbWebhookSubscription> = query
+                .order(webhook_subscriptions::id.asc())
+                .limit(page.limit + 1)
+                .select(DbWebhookSubscription::as_select())
+                .load(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            let subscriptions = db_subscriptions
+                .into_iter()
+                .map(Self::subscription_to_domain)
+                .collect();
+
+            Ok(Page::from_overfetch(
+                subscriptions,
+                page.limit,
+                |subscription: &WebhookSubscription| subscription.id,
+            ))
+        })
+        .await
+    }
+
+    async fn delete_subscription(&self, id: i32) -> DomainResult<()> {
+        with_connection(&self.pool, move |conn| {
+            // Deliveries go with it through ON DELETE CASCADE
+            let deleted = diesel::delete(webhook_subscriptions::table.find(id))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            if deleted == 0 {
+                return Err(DomainError::WebhookSubscriptionNotFound(id));
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        lease: Duration,
+        limit: i64,
+    ) -> DomainResult<Vec<WebhookDelivery>> {
+        with_connection(&self.pool, move |conn| {
+            // SKIP LOCKED lets concurrent dispatchers claim disjoint batches
+            let mut claimed = conn
+                .transaction::<_, diesel::result::Error, _>(|conn| {
+                    let ids: Vec<i32> = webhook_deliveries::table
+                        .filter(webhook_deliveries::delivered_at.is_null())
+                        .filter(webhook_deliveries::dead_lettered_at.is_null())
+                        .filter(webhook_deliveries::next_attempt_at.le(now))
+                        .order(webhook_deliveries::id.asc())
+                        .limit(limit)
+                        .select(webhook_deliveries::id)
+                        .for_update()
+                        .skip_locked()
+                        .load(conn)?;
+
+                    diesel::update(
+                        webhook_deliveries::table.filter(webhook_deliveries::id.eq_any(&ids)),
+                    )
+                    .set(
+                        webhook_deliveries::next_attempt_at.eq(now + lease.num_seconds().seconds()),
+                    )
+                    .returning(DbWebhookDelivery::as_returning())
+                    .get_results(conn)
+                })
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            claimed.sort_by_key(|delivery| delivery.id);
+            Ok(claimed.into_iter().map(Self::delivery_to_domain).collect())
+        })
+        .await
+    }
+
+    async fn mark_delivered(&self, id: i32) -> DomainResult<()> {
+        with_connection(&self.pool, move |conn| {
+            diesel::update(webhook_deliveries::table.find(id))
+                .set((
+                    webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                    webhook_deliveries::delivered_at.eq(now),
+                ))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn mark_failed(&self, id: i32, error: &str, retry_in: Duration) -> DomainResult<()> {
+        let error = error.to_string();
+        with_connection(&self.pool, move |conn| {
+            diesel::update(webhook_deliveries::table.find(id))
+                .set((
+                    webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                    webhook_deliveries::last_error.eq(&error),
+                    webhook_deliveries::next_attempt_at.eq(now + retry_in.num_seconds().seconds()),
+                ))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn mark_dead_lettered(&self, id: i32, error: &str) -> DomainResult<()> {
+        let error = error.to_string();
+        with_connection(&self.pool, move |conn| {
+            diesel::update(webhook_deliveries::table.find(id))
+                .set((
+                    webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                    webhook_deliveries::last_error.eq(&error),
+                    webhook_deliveries::dead_lettered_at.eq(now),
+                ))
+                .execute(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn find_deliveries(
+        &self,
+        filter: &WebhookDeliveryFilter,
+        page: &PageRequest,
+    ) -> DomainResult<Page<WebhookDelivery>> {
+        let filter = filter.clone();
+        let page = *page;
+        with_connection(&self.pool, move |conn| {
+            let mut query = webhook_deliveries::table.into_boxed();
+
+            if let Some(subscription_id) = filter.subscription_id {
+                query = query.filter(webhook_deliveries::subscription_id.eq(subscription_id));
+            }
+            match filter.status {
+                Some(WebhookDeliveryStatus::Pending) => {
+                    query = query
+                        .filter(webhook_deliveries::delivered_at.is_null())
+                        .filter(webhook_deliveries::dead_lettered_at.is_null())
+                        .filter(webhook_deliveries::attempts.eq(0));
+                }
+                Some(WebhookDeliveryStatus::Retrying) => {
+                    query = query
+                        .filter(webhook_deliveries::delivered_at.is_null())
+                        .filter(webhook_deliveries::dead_lettered_at.is_null())
+                        .filter(webhook_deliveries::attempts.gt(0));
+                }
+                Some(WebhookDeliveryStatus::Delivered) => {
+                    query = query.filter(webhook_deliveries::delivered_at.is_not_null());
+                }
+                Some(WebhookDeliveryStatus::DeadLettered) => {
+                    query = query
+                        .filter(webhook_deliveries::delivered_at.is_null())
+                        .filter(webhook_deliveries::dead_lettered_at.is_not_null());
+                }
+                None => {}
+            }
+            if let Some(after) = page.after {
+                query = query.filter(webhook_deliveries::id.gt(after));
+            }
+
+            // One extra row tells whether there is a next page
+            let db_deliveries: Vec<DbWebhookDelivery> = query
+                .order(webhook_deliveries::id.asc())
+                .limit(page.limit + 1)
+                .select(DbWebhookDelivery::as_select())
+                .load(conn)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            let deliveries = db_deliveries
+                .into_iter()
+                .map(Self::delivery_to_domain)
+                .collect();
+
+            Ok(Page::from_overfetch(
+                deliveries,
+                page.limit,
+                |delivery: &WebhookDelivery| delivery.id,
+            ))
+        })
+        .await
+    }
+
+    async fn find_delivery(&self, id: i32) -> DomainResult<WebhookDelivery> {
+        with_connection(&self.pool, move |conn| Self::find_delivery_on(conn, id)).await
+    }
+
+    async fn replay_delivery(&self, id: i32) -> DomainResult<WebhookDelivery> {
+        with_connection(&self.pool, move |conn| {
+            // Only finished deliveries are reset, so a replay cannot race the
+            // dispatcher over one it still holds
+            let replayed: Option<DbWebhookDelivery> = diesel::update(
+                webhook_deliveries::table.find(id).filter(
+                    webhook_deliveries::delivered_at
+                        .is_not_null()
+                        .or(webhook_deliveries::dead_lettered_at.is_not_null()),
+                ),
+            )
+            .set((
+                webhook_deliveries::attempts.eq(0),
+                webhook_deliveries::last_error.eq(None::<String>),
+                webhook_deliveries::delivered_at.eq(None::<chrono::NaiveDateTime>),
+                webhook_deliveries::dead_lettered_at.eq(None::<chrono::NaiveDateTime>),
+                webhook_deliveries::next_attempt_at.eq(now),
+            ))
+            .returning(DbWebhookDelivery::as_returning())
+            .get_result(conn)
+            .optional()
+            .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+
+            match replayed {
+                Some(delivery) => Ok(Self::delivery_to_domain(delivery)),
+                // Not found, or still scheduled; the lookup reports which
+                None => {
+                    Self::find_delivery_on(conn, id)?.ensure_replayable()?;
+                    Err(DomainError::RepositoryError(format!(
+                        "Webhook delivery {} could not be replayed",
+                        id
+                    )))
+                }
+            }
+        })
+        .await
+    }
+}
@@ -0,0 +1,229 @@
+use crate::domain::entities::{EventType, LedgerEvent, OutboxMessage, WebhookDelivery};
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{AccountStatus, Balance, BalancePolicy, Currency};
+use crate::infrastructure::persistence::{
+    DieselAccountBalanceRepository, DieselLedgerEventRepository, DieselOutboxRepository,
+    DieselWebhookRepository,
+};
+use crate::models::LedgerEvent as DbLedgerEvent;
+use crate::schema::{accounts, ledger_events};
+use diesel::prelude::*;
+
+/// Key of the transaction-level advisory lock that ledger event inserts take, so
+/// that sequences are assigned in commit order
+const SEQUENCE_LOCK_KEY: i64 = 0x6c65_6467_6572;
+
+/// Error raised inside a Diesel transaction, carrying either a domain rule
+/// violation or a database failure out of the transaction closure
+#[derive(Debug)]
+pub(crate) enum TxError {
+    Domain(DomainError),
+    Database(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for TxError {
+    fn from(err: diesel::result::Error) -> Self {
+        TxError::Database(err)
+    }
+}
+
+impl From<DomainError> for TxError {
+    fn from(err: DomainError) -> Self {
+        TxError::Domain(err)
+    }
+}
+
+impl From<TxError> for DomainError {
+    fn from(err: TxError) -> Self {
+        match err {
+            TxError::Domain(e) => e,
+            TxError::Database(e) => DomainError::RepositoryError(e.to_string()),
+        }
+    }
+}
+
+/// Account state read while taking its row lock
+pub(crate) struct LockedAccount {
+    pub balance_policy: BalancePolicy,
+    pub currency: Currency,
+    pub status: AccountStatus,
+}
+
+/// Appends a ledger event on a connection that is already inside a transaction.
+///
+/// The account row is locked until the transaction ends, so appends to the same
+/// account are serialized and event ids and versions of an account are committed
+/// in order.
+/// The insert takes a global lock held until the transaction ends, so an event's
+/// sequence is never committed after a higher one: a reader paging by sequence
+/// cannot skip an event that commits behind its cursor. Account locks are taken
+/// first, so writers acquire the two in the same order.
+/// The account must be active and the event in the account's currency, a reversal
+/// must not reverse an already reversed event, and DEBIT events are checked against
+/// the account's balance policy under that lock, so concurrent debits cannot both
+/// pass the check and a concurrent freeze or close cannot slip in between the check
+/// and the insert.
+/// The event's `ledger_event.created` outbox message is written alongside it, with
+/// the webhook deliveries it and the resulting balance call for.
+pub(crate) fn append_event(
+    conn: &mut PgConnection,
+    event: &LedgerEvent,
+) -> Result<DbLedgerEvent, TxError> {
+    append_event_expecting(conn, event, None)
+}
+
+/// Like `append_event`, failing with a version conflict unless the account's
+/// latest event has `expected_version` when it is set
+pub(crate) fn append_event_expecting(
+    conn: &mut PgConnection,
+    event: &LedgerEvent,
+    expected_version: Option<i64>,
+) -> Result<DbLedgerEvent, TxError> {
+    let account = lock_account(conn, event.account_id)?;
+
+    // Read under the account lock, so no other writer can take the next version
+    let version = current_version(conn, event.account_id)?;
+    if let Some(expected) = expected_version {
+        if expected != version {
+            return Err(TxError::Domain(DomainError::VersionConflict {
+                account_id: event.account_id,
+                expected,
+                actual: version,
+            }));
+        }
+    }
+
+    account.status.ensure_accepts_events(event.account_id)?;
+    event.amount.ensure_currency(account.currency)?;
+
+    // Reversals of an account's events are serialized by the account lock, so two
+    // concurrent reversals of the same event cannot both pass this check
+    if let Some(event_id) = event.reverses_event_id {
+        if let Some(reversal_event_id) = DieselLedgerEventRepository::reversal_of(conn, event_id)? {
+            return Err(TxError::Domain(DomainError::EventAlreadyReversed {
+                event_id,
+                reversal_event_id,
+            }));
+        }
+    }
+
+    let policy = account.balance_policy;
+    if event.event_type == EventType::Debit && policy != BalancePolicy::Unrestricted {
+        let balance = balance_of(conn, event.account_id)?;
+        policy.check_debit(balance, &event.amount)?;
+    }
+
+    lock_sequence(conn)?;
+    let db_event: DbLedgerEvent = diesel::insert_into(ledger_events::table)
+        .values(&DieselLedgerEventRepository::to_db(event, version + 1))
+        .get_result(conn)?;
+
+    let saved = DieselLedgerEventRepository::to_domain(db_event.clone())?;
+    let message_id =
+        DieselOutboxRepository::enqueue(conn, &OutboxMessage::ledger_event_created(&saved))?;
+    notify_balance_change(conn, message_id, &saved)?;
+
+    Ok(db_event)
+}
+
+/// Serializes sequence assignment until the transaction ends. The lock is
+/// reentrant, so transactions appending several events take it once per event.
+fn lock_sequence(conn: &mut PgConnection) -> QueryResult<()> {
+    diesel::sql_query("SELECT pg_advisory_xact_lock($1)")
+        .bind::<diesel::sql_types::BigInt, _>(SEQUENCE_LOCK_KEY)
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Writes `balance.below_threshold` deliveries for the webhook subscriptions whose
+/// threshold the saved event crossed. The balance is only computed when the
+/// account has such subscriptions.
+fn notify_balance_change(
+    conn: &mut PgConnection,
+    message_id: i32,
+    event: &LedgerEvent,
+) -> Result<(), TxError> {
+    let watchers = DieselWebhookRepository::balance_watchers(conn, event.account_id)?;
+    if watchers.is_empty() {
+        return Ok(());
+    }
+
+    let balance = balance_of(conn, event.account_id)?.value();
+    let deliveries = WebhookDelivery::for_balance_change(&watchers, message_id, event, balance);
+    DieselWebhookRepository::insert_deliveries(conn, &deliveries)?;
+    Ok(())
+}
+
+/// Locks several accounts in ascending id order so that transactions touching the
+/// same accounts cannot deadlock each other.
+pub(crate) fn lock_accounts(conn: &mut PgConnection, account_ids: &[i32]) -> Result<(), TxError> {
+    let mut ids = account_ids.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+
+    for account_id in ids {
+        lock_account(conn, account_id)?;
+    }
+    Ok(())
+}
+
+/// Locks the account row until the transaction ends and returns its balance policy,
+/// currency and status.
+///
+/// FOR NO KEY UPDATE is used rather than FOR UPDATE so that inserts referencing the
+/// account (which take FOR KEY SHARE) from other transactions are not blocked.
+pub(crate) fn lock_account(
+    conn: &mut PgConnection,
+    account_id: i32,
+) -> Result<LockedAccount, TxError> {
+    let (kind, overdraft_limit, currency, status): (String, Option<i64>, String, String) =
+        accounts::table
+            .find(account_id)
+            .select((
+                accounts::balance_policy,
+                accounts::overdraft_limit,
+                accounts::currency,
+                accounts::status,
+            ))
+            .for_no_key_update()
+            .first(conn)
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => TxError::Domain(DomainError::AccountNotFound(
+                    format!("Account with id {} not found", account_id),
+                )),
+                _ => TxError::Database(e),
+            })?;
+
+    let currency = Currency::from_code(&currency)?;
+    Ok(LockedAccount {
+        balance_policy: BalancePolicy::from_parts(&kind, overdraft_limit, currency)?,
+        currency,
+        status: AccountStatus::from_string(&status)?,
+    })
+}
+
+/// Version of the account's latest event, 0 when it has none
+pub(crate) fn current_version(conn: &mut PgConnection, account_id: i32) -> QueryResult<i64> {
+    ledger_events::table
+        .filter(ledger_events::account_id.eq(account_id))
+        .select(diesel::dsl::max(ledger_events::version))
+        .first::<Option<i64>>(conn)
+        .map(|version| version.unwrap_or(0))
+}
+
+/// Current balance of the account: latest positioned snapshot plus the events after it
+pub(crate) fn balance_of(conn: &mut PgConnection, account_id: i32) -> Result<Balance, TxError> {
+    let snapshot = DieselAccountBalanceRepository::latest_snapshot(conn, account_id)?;
+
+    let (base, after_event_id) = match snapshot {
+        Some(snapshot) => (
+            Balance::new(snapshot.balance),
+            snapshot.last_event_id.unwrap_or(0),
+        ),
+        None => (Balance::zero(), 0),
+    };
+
+    let delta = DieselLedgerEventRepository::balance_delta(conn, account_id, after_event_id)?;
+
+    Ok(base.adjust(delta.amount)?)
+}
@@ -1,9 +1,21 @@
+mod connection;
 pub mod diesel_account_balance_repository;
 pub mod diesel_account_repository;
+pub mod diesel_idempotency_repository;
+pub mod diesel_journal_entry_repository;
 pub mod diesel_ledger_event_repository;
-// pub mod diesel_transaction_repository; // Deprecated in favor of event-sourcing
+pub mod diesel_outbox_repository;
+pub mod diesel_storage_health;
+pub mod diesel_transfer_repository;
+pub mod diesel_webhook_repository;
+mod ledger_writer;
 
 pub use diesel_account_balance_repository::DieselAccountBalanceRepository;
 pub use diesel_account_repository::DieselAccountRepository;
+pub use diesel_idempotency_repository::DieselIdempotencyRepository;
+pub use diesel_journal_entry_repository::DieselJournalEntryRepository;
 pub use diesel_ledger_event_repository::DieselLedgerEventRepository;
-// pub use diesel_transaction_repository::DieselTransactionRepository;
+pub use diesel_outbox_repository::DieselOutboxRepository;
+pub use diesel_storage_health::DieselStorageHealth;
+pub use diesel_transfer_repository::DieselTransferRepository;
+pub use diesel_webhook_repository::DieselWebhookRepository;
@@ -1,6 +1,7 @@
 // DDD Layers
 pub mod api;
 pub mod application;
+pub mod config;
 pub mod domain;
 pub mod infrastructure;
 
@@ -8,20 +9,19 @@ pub mod infrastructure;
 pub mod models;
 pub mod schema;
 
+use config::DatabaseConfig;
 use diesel::prelude::*;
 use diesel::r2d2::{self, ConnectionManager};
-use dotenvy::dotenv;
-use std::env;
 
 pub type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
 
-pub fn establish_connection_pool() -> DbPool {
-    dotenv().ok();
-
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env file");
-
-    let manager = ConnectionManager::<PgConnection>::new(database_url);
+/// Builds the connection pool, failing if no connection can be opened within the
+/// configured timeout
+pub fn establish_connection_pool(config: &DatabaseConfig) -> Result<DbPool, r2d2::PoolError> {
+    let manager = ConnectionManager::<PgConnection>::new(&config.url);
     r2d2::Pool::builder()
+        .max_size(config.max_connections)
+        .min_idle(config.min_idle)
+        .connection_timeout(config.connection_timeout())
         .build(manager)
-        .expect("Failed to create pool.")
 }
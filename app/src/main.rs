@@ -1,46 +1,59 @@
+use dotenvy::dotenv;
 use std::sync::Arc;
-use tracing::{error, info, Level};
-use tracing_subscriber::FmtSubscriber;
-use transaction_processor::api::{start_server, AppState};
-use transaction_processor::application::Mediator;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use tracing_subscriber::{EnvFilter, FmtSubscriber};
+use transaction_processor::api::auth::Authenticator;
+use transaction_processor::api::idempotency::spawn_expired_key_purge;
+use transaction_processor::api::{metrics, shutdown_signal, start_server, AppState};
+use transaction_processor::application::commands::SnapshotAccountsCommand;
+use transaction_processor::application::{
+    spawn_outbox_relay, spawn_snapshot_scheduler, spawn_webhook_dispatcher, Mediator, MessageSink,
+    OutboxRelay, OutboxRelaySettings, WebhookDispatcher, WebhookDispatcherSettings,
+};
+use transaction_processor::config::{
+    AppConfig, DatabaseConfig, LogFormat, LoggingConfig, OutboxConfig, OutboxSink, StorageBackend,
+};
+use transaction_processor::domain::{
+    IdempotencyRepository, OutboxRepository, RetryPolicy, WebhookRepository,
+};
 use transaction_processor::infrastructure::{
-    DieselAccountBalanceRepository, DieselAccountRepository, DieselLedgerEventRepository,
+    DieselAccountBalanceRepository, DieselAccountRepository, DieselIdempotencyRepository,
+    DieselJournalEntryRepository, DieselLedgerEventRepository, DieselOutboxRepository,
+    DieselStorageHealth, DieselTransferRepository, DieselWebhookRepository, FileSink,
+    HttpWebhookSender, InMemoryAccountBalanceRepository, InMemoryAccountRepository,
+    InMemoryIdempotencyRepository, InMemoryJournalEntryRepository, InMemoryLedgerEventRepository,
+    InMemoryOutboxRepository, InMemoryStorageHealth, InMemoryStore, InMemoryTransferRepository,
+    InMemoryWebhookRepository, NatsSink, StorageHealth, WebhookSink,
 };
 use transaction_processor::*;
 
+/// How often histogram buckets of the metrics recorder are drained
+const METRICS_UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
 #[tokio::main]
 async fn main() {
-    // Initialize structured logging
-    FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
-        .with_target(false)
-        .with_thread_ids(true)
-        .with_file(true)
-        .with_line_number(true)
-        .init();
+    dotenv().ok();
 
-    info!("Transaction Processor - Event-Sourced Ledger REST API");
-    info!("=====================================================");
-
-    // Establish database connection pool
-    let pool = match std::panic::catch_unwind(establish_connection_pool) {
-        Ok(pool) => {
-            info!("✓ Database connection pool established");
-            pool
-        }
+    // Logging is not set up yet, so configuration errors go straight to stderr
+    let config = match AppConfig::load() {
+        Ok(config) => config,
         Err(e) => {
-            error!("Failed to establish database connection pool: {:?}", e);
+            eprintln!("Configuration error: {}", e);
             std::process::exit(1);
         }
     };
 
-    // Initialize repositories
-    let account_repository = Arc::new(DieselAccountRepository::new(pool.clone()));
-    let event_repository = Arc::new(DieselLedgerEventRepository::new(pool.clone()));
-    let balance_repository = Arc::new(DieselAccountBalanceRepository::new(pool.clone()));
+    // Initialize structured logging
+    init_logging(&config.logging);
 
-    // Initialize mediator with new event-sourcing repositories
-    let mediator = Mediator::new(account_repository, event_repository, balance_repository);
+    info!("Transaction Processor - Event-Sourced Ledger REST API");
+    info!("=====================================================");
+
+    let storage = match config.storage.backend {
+        StorageBackend::Postgres => postgres_storage(&config.database),
+        StorageBackend::Memory => memory_storage(),
+    };
 
     info!("✓ Application initialized with Event-Sourcing DDD architecture");
     info!("  - Domain layer: Entities (Account, LedgerEvent, AccountBalance)");
@@ -48,15 +61,276 @@ async fn main() {
     info!("  - Infrastructure layer: Repository implementations");
     info!("  - Event-Sourcing: DEBIT/CREDIT events with balance snapshots");
 
+    let idempotency = &config.idempotency;
+    if config.features.idempotency {
+        info!(
+            "✓ Idempotency keys expire after {}s",
+            idempotency.key_ttl_seconds
+        );
+    } else {
+        info!("✓ Idempotency-Key handling disabled");
+    }
+
+    // Background jobs, stopped once the server has drained
+    let mut jobs = Vec::new();
+
+    if config.features.idempotency_key_purge {
+        jobs.push(spawn_expired_key_purge(
+            storage.idempotency_repository.clone(),
+            idempotency.purge_interval(),
+        ));
+    }
+
     // Create app state
-    let state = AppState::new(mediator);
+    let mut state = AppState::new(
+        storage.mediator,
+        storage.idempotency_repository,
+        storage.health,
+    )
+    .with_idempotency_ttl(idempotency.key_ttl())
+    .with_idempotency_enabled(config.features.idempotency);
+
+    if config.auth.enabled {
+        let authenticator = match Authenticator::from_config(&config.auth) {
+            Ok(authenticator) => authenticator,
+            Err(e) => {
+                error!("Configuration error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        state = state.with_authenticator(authenticator);
+        info!(
+            "✓ Authentication required ({} API key(s), JWT {})",
+            config.auth.api_keys.len(),
+            if config.auth.jwt.is_some() {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+    } else {
+        warn!("Authentication disabled; every caller has full access");
+    }
+
+    if config.features.metrics {
+        let handle = match metrics::install_recorder() {
+            Ok(handle) => handle,
+            Err(e) => {
+                error!("Failed to install metrics recorder: {}", e);
+                std::process::exit(1);
+            }
+        };
+        jobs.push(metrics::spawn_upkeep(
+            handle.clone(),
+            METRICS_UPKEEP_INTERVAL,
+        ));
+        state = state.with_metrics(handle);
+        info!("✓ Prometheus metrics exposed at /metrics");
+    }
+
+    // Started after the metrics recorder so the first run's lag is exported
+    if config.features.snapshot_scheduler {
+        let snapshots = &config.snapshots;
+        jobs.push(spawn_snapshot_scheduler(
+            state.mediator.clone(),
+            snapshots.interval(),
+            SnapshotAccountsCommand::due(snapshots.event_threshold, snapshots.max_age_seconds),
+        ));
+        info!(
+            "✓ Balance snapshots every {} events or {}s, checked every {}s",
+            snapshots.event_threshold, snapshots.max_age_seconds, snapshots.interval_seconds
+        );
+    }
+
+    if config.features.outbox_relay {
+        let outbox = &config.outbox;
+        match outbox_sink(outbox) {
+            Some(sink) => {
+                let relay = OutboxRelay::new(
+                    storage.outbox_repository.clone(),
+                    sink,
+                    OutboxRelaySettings {
+                        batch_size: outbox.batch_size,
+                        lease: outbox.lease(),
+                        retry: RetryPolicy::new(outbox.retry_base(), outbox.retry_max()),
+                    },
+                );
+                jobs.push(spawn_outbox_relay(relay, outbox.poll_interval()));
+                info!("✓ Outbox relay polling every {}ms", outbox.poll_interval_ms);
+            }
+            None => warn!("No outbox sink configured; ledger events are not published"),
+        }
+    }
+
+    if config.features.webhook_dispatcher {
+        let webhooks = &config.webhooks;
+        let sender = match HttpWebhookSender::new(webhooks.timeout()) {
+            Ok(sender) => sender,
+            Err(e) => {
+                error!("Failed to set up the webhook sender: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let dispatcher = WebhookDispatcher::new(
+            storage.webhook_repository.clone(),
+            Arc::new(sender),
+            WebhookDispatcherSettings {
+                batch_size: webhooks.batch_size,
+                lease: webhooks.lease(),
+                retry: RetryPolicy::new(webhooks.retry_base(), webhooks.retry_max()),
+                max_attempts: webhooks.max_attempts,
+            },
+        );
+        jobs.push(spawn_webhook_dispatcher(
+            dispatcher,
+            webhooks.poll_interval(),
+        ));
+        info!(
+            "✓ Webhook dispatcher polling every {}ms, up to {} attempts per delivery",
+            webhooks.poll_interval_ms, webhooks.max_attempts
+        );
+    }
 
     // Start REST API server
     info!("\n--- Starting REST API Server ---");
-    let port = 3000;
 
-    if let Err(e) = start_server(state, port).await {
+    if let Err(e) = start_server(state, &config.server, shutdown_signal()).await {
         error!("Server error: {}", e);
         std::process::exit(1);
     }
+
+    // Jobs are periodic and idempotent, so one cut short simply runs again next start
+    let job_count = jobs.len();
+    for job in jobs {
+        job.abort();
+        let _ = job.await;
+    }
+    info!("✓ Stopped {} background job(s)", job_count);
+
+    // The server and jobs have released their pool handles, so dropping the last
+    // one closes the connections
+    if let Some(pool) = storage.pool {
+        let connections = pool.state().connections;
+        drop(pool);
+        info!("✓ Closed database pool ({} connection(s))", connections);
+    }
+
+    info!("Shutdown complete");
+}
+
+fn init_logging(config: &LoggingConfig) {
+    // The level was validated when the configuration was loaded
+    let filter = EnvFilter::new(&config.level);
+    let builder = FmtSubscriber::builder()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_thread_ids(true)
+        .with_file(true)
+        .with_line_number(true);
+
+    match config.format {
+        LogFormat::Text => builder.init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+/// Storage-backed services the API is built from
+struct Storage {
+    mediator: Mediator,
+    idempotency_repository: Arc<dyn IdempotencyRepository>,
+    outbox_repository: Arc<dyn OutboxRepository>,
+    webhook_repository: Arc<dyn WebhookRepository>,
+    health: Arc<dyn StorageHealth>,
+    /// Closed explicitly on shutdown
+    pool: Option<DbPool>,
+}
+
+fn postgres_storage(config: &DatabaseConfig) -> Storage {
+    // Establish database connection pool
+    let pool = match establish_connection_pool(config) {
+        Ok(pool) => {
+            info!(
+                "✓ Database connection pool established (max {} connections)",
+                config.max_connections
+            );
+            pool
+        }
+        Err(e) => {
+            error!("Failed to establish database connection pool: {:?}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Initialize mediator with new event-sourcing repositories
+    let outbox_repository = Arc::new(DieselOutboxRepository::new(pool.clone()));
+    let webhook_repository = Arc::new(DieselWebhookRepository::new(pool.clone()));
+    let mediator = Mediator::new(
+        Arc::new(DieselAccountRepository::new(pool.clone())),
+        Arc::new(DieselLedgerEventRepository::new(pool.clone())),
+        Arc::new(DieselAccountBalanceRepository::new(pool.clone())),
+        Arc::new(DieselTransferRepository::new(pool.clone())),
+        Arc::new(DieselJournalEntryRepository::new(pool.clone())),
+        outbox_repository.clone(),
+        webhook_repository.clone(),
+    );
+
+    Storage {
+        mediator,
+        idempotency_repository: Arc::new(DieselIdempotencyRepository::new(pool.clone())),
+        outbox_repository,
+        webhook_repository,
+        health: Arc::new(DieselStorageHealth::new(pool.clone())),
+        pool: Some(pool),
+    }
+}
+
+fn memory_storage() -> Storage {
+    warn!("Using in-memory storage; all data is lost when the server stops");
+
+    let store = InMemoryStore::new();
+    let outbox_repository = Arc::new(InMemoryOutboxRepository::new(store.clone()));
+    let webhook_repository = Arc::new(InMemoryWebhookRepository::new(store.clone()));
+    let mediator = Mediator::new(
+        Arc::new(InMemoryAccountRepository::new(store.clone())),
+        Arc::new(InMemoryLedgerEventRepository::new(store.clone())),
+        Arc::new(InMemoryAccountBalanceRepository::new(store.clone())),
+        Arc::new(InMemoryTransferRepository::new(store.clone())),
+        Arc::new(InMemoryJournalEntryRepository::new(store.clone())),
+        outbox_repository.clone(),
+        webhook_repository.clone(),
+    );
+
+    Storage {
+        mediator,
+        idempotency_repository: Arc::new(InMemoryIdempotencyRepository::new(store)),
+        outbox_repository,
+        webhook_repository,
+        health: Arc::new(InMemoryStorageHealth),
+        pool: None,
+    }
+}
+
+/// Sink the outbox relay publishes to, None when publishing is not configured.
+/// The settings each sink needs were checked when the configuration was loaded.
+fn outbox_sink(config: &OutboxConfig) -> Option<Arc<dyn MessageSink>> {
+    let timeout = config.publish_timeout();
+    let sink: Arc<dyn MessageSink> = match config.sink {
+        OutboxSink::None => return None,
+        OutboxSink::Webhook => {
+            let url = config.webhook_url.clone().unwrap_or_default();
+            match WebhookSink::new(url, timeout) {
+                Ok(sink) => Arc::new(sink),
+                Err(e) => {
+                    error!("Failed to set up the outbox webhook sink: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        OutboxSink::Nats => Arc::new(NatsSink::new(
+            config.nats_address.as_deref().unwrap_or_default(),
+            timeout,
+        )),
+        OutboxSink::File => Arc::new(FileSink::new(config.file_path.clone().unwrap_or_default())),
+    };
+    Some(sink)
 }
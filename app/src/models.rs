@@ -11,6 +11,10 @@ pub struct Account {
     pub account_name: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub balance_policy: String,
+    pub overdraft_limit: Option<i64>,
+    pub currency: String,
+    pub status: String,
 }
 
 #[derive(Insertable, Serialize, Deserialize, Debug)]
@@ -18,6 +22,10 @@ pub struct Account {
 pub struct NewAccount {
     pub account_number: String,
     pub account_name: String,
+    pub balance_policy: String,
+    pub overdraft_limit: Option<i64>,
+    pub currency: String,
+    pub status: String,
 }
 
 #[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
@@ -30,6 +38,13 @@ pub struct LedgerEvent {
     pub amount: i64,
     pub description: Option<String>,
     pub created_at: NaiveDateTime,
+    pub transfer_id: Option<i32>,
+    pub currency: String,
+    pub journal_entry_id: Option<i32>,
+    pub reverses_event_id: Option<i32>,
+    pub recorded_by: Option<String>,
+    pub version: i64,
+    pub sequence: i64,
 }
 
 #[derive(Insertable, Serialize, Deserialize, Debug)]
@@ -39,6 +54,52 @@ pub struct NewLedgerEvent {
     pub event_type: String,
     pub amount: i64,
     pub description: Option<String>,
+    pub transfer_id: Option<i32>,
+    pub currency: String,
+    pub journal_entry_id: Option<i32>,
+    pub reverses_event_id: Option<i32>,
+    pub recorded_by: Option<String>,
+    pub version: i64,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::journal_entries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct JournalEntry {
+    pub id: i32,
+    pub description: Option<String>,
+    pub currency: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = crate::schema::journal_entries)]
+pub struct NewJournalEntry {
+    pub description: Option<String>,
+    pub currency: String,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::transfers)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Transfer {
+    pub id: i32,
+    pub from_account_id: i32,
+    pub to_account_id: i32,
+    pub amount: i64,
+    pub description: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub currency: String,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = crate::schema::transfers)]
+pub struct NewTransfer {
+    pub from_account_id: i32,
+    pub to_account_id: i32,
+    pub amount: i64,
+    pub description: Option<String>,
+    pub currency: String,
 }
 
 #[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
@@ -49,6 +110,8 @@ pub struct AccountBalance {
     pub account_id: i32,
     pub balance: i64,
     pub snapshot_at: NaiveDateTime,
+    pub last_event_id: Option<i32>,
+    pub currency: String,
 }
 
 #[derive(Insertable, Serialize, Deserialize, Debug)]
@@ -56,4 +119,124 @@ pub struct AccountBalance {
 pub struct NewAccountBalance {
     pub account_id: i32,
     pub balance: i64,
+    pub last_event_id: Option<i32>,
+    pub currency: String,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::account_status_changes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AccountStatusChange {
+    pub id: i32,
+    pub account_id: i32,
+    pub from_status: String,
+    pub to_status: String,
+    pub actor: String,
+    pub reason: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = crate::schema::account_status_changes)]
+pub struct NewAccountStatusChange {
+    pub account_id: i32,
+    pub from_status: String,
+    pub to_status: String,
+    pub actor: String,
+    pub reason: String,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::idempotency_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IdempotencyKey {
+    pub key: String,
+    pub request_fingerprint: String,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub response_content_type: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::outbox_messages)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OutboxMessage {
+    pub id: i32,
+    pub topic: String,
+    pub message_key: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub published_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = crate::schema::outbox_messages)]
+pub struct NewOutboxMessage {
+    pub topic: String,
+    pub message_key: String,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::webhook_subscriptions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookSubscription {
+    pub id: i32,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub secret: String,
+    pub account_id: Option<i32>,
+    pub balance_threshold: Option<i64>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = crate::schema::webhook_subscriptions)]
+pub struct NewWebhookSubscription {
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub secret: String,
+    pub account_id: Option<i32>,
+    pub balance_threshold: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::webhook_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub subscription_id: i32,
+    pub outbox_message_id: i32,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+    pub dead_lettered_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = crate::schema::webhook_deliveries)]
+pub struct NewWebhookDelivery {
+    pub subscription_id: i32,
+    pub outbox_message_id: i32,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+}
+
+#[derive(QueryableByName, Debug, Clone)]
+pub struct BalanceDelta {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub amount: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub last_event_id: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub event_count: i64,
 }
@@ -6,6 +6,24 @@ diesel::table! {
         account_id -> Int4,
         balance -> Int8,
         snapshot_at -> Timestamp,
+        last_event_id -> Nullable<Int4>,
+        #[max_length = 3]
+        currency -> Varchar,
+    }
+}
+
+diesel::table! {
+    account_status_changes (id) {
+        id -> Int4,
+        account_id -> Int4,
+        #[max_length = 20]
+        from_status -> Varchar,
+        #[max_length = 20]
+        to_status -> Varchar,
+        #[max_length = 255]
+        actor -> Varchar,
+        reason -> Text,
+        created_at -> Timestamp,
     }
 }
 
@@ -18,6 +36,38 @@ diesel::table! {
         account_name -> Varchar,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        #[max_length = 20]
+        balance_policy -> Varchar,
+        overdraft_limit -> Nullable<Int8>,
+        #[max_length = 3]
+        currency -> Varchar,
+        #[max_length = 20]
+        status -> Varchar,
+    }
+}
+
+diesel::table! {
+    idempotency_keys (key) {
+        #[max_length = 255]
+        key -> Varchar,
+        #[max_length = 64]
+        request_fingerprint -> Varchar,
+        response_status -> Nullable<Int4>,
+        response_body -> Nullable<Text>,
+        #[max_length = 255]
+        response_content_type -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    journal_entries (id) {
+        id -> Int4,
+        description -> Nullable<Text>,
+        #[max_length = 3]
+        currency -> Varchar,
+        created_at -> Timestamp,
     }
 }
 
@@ -30,10 +80,95 @@ diesel::table! {
         amount -> Int8,
         description -> Nullable<Text>,
         created_at -> Timestamp,
+        transfer_id -> Nullable<Int4>,
+        #[max_length = 3]
+        currency -> Varchar,
+        journal_entry_id -> Nullable<Int4>,
+        reverses_event_id -> Nullable<Int4>,
+        #[max_length = 255]
+        recorded_by -> Nullable<Varchar>,
+        version -> Int8,
+        sequence -> Int8,
+    }
+}
+
+diesel::table! {
+    outbox_messages (id) {
+        id -> Int4,
+        #[max_length = 100]
+        topic -> Varchar,
+        #[max_length = 100]
+        message_key -> Varchar,
+        payload -> Jsonb,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamp,
+        published_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    transfers (id) {
+        id -> Int4,
+        from_account_id -> Int4,
+        to_account_id -> Int4,
+        amount -> Int8,
+        description -> Nullable<Text>,
+        created_at -> Timestamp,
+        #[max_length = 3]
+        currency -> Varchar,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int4,
+        subscription_id -> Int4,
+        outbox_message_id -> Int4,
+        #[max_length = 100]
+        event_type -> Varchar,
+        payload -> Jsonb,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamp,
+        delivered_at -> Nullable<Timestamp>,
+        dead_lettered_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    webhook_subscriptions (id) {
+        id -> Int4,
+        url -> Text,
+        event_types -> Array<Text>,
+        #[max_length = 100]
+        secret -> Varchar,
+        account_id -> Nullable<Int4>,
+        balance_threshold -> Nullable<Int8>,
+        created_at -> Timestamp,
     }
 }
 
 diesel::joinable!(account_balances -> accounts (account_id));
+diesel::joinable!(account_status_changes -> accounts (account_id));
 diesel::joinable!(ledger_events -> accounts (account_id));
+diesel::joinable!(ledger_events -> journal_entries (journal_entry_id));
+diesel::joinable!(ledger_events -> transfers (transfer_id));
+diesel::joinable!(webhook_deliveries -> outbox_messages (outbox_message_id));
+diesel::joinable!(webhook_deliveries -> webhook_subscriptions (subscription_id));
+diesel::joinable!(webhook_subscriptions -> accounts (account_id));
 
-diesel::allow_tables_to_appear_in_same_query!(account_balances, accounts, ledger_events,);
+diesel::allow_tables_to_appear_in_same_query!(
+    account_balances,
+    account_status_changes,
+    accounts,
+    idempotency_keys,
+    journal_entries,
+    ledger_events,
+    outbox_messages,
+    transfers,
+    webhook_deliveries,
+    webhook_subscriptions,
+);